@@ -0,0 +1,3 @@
+-- Track when an idea or comment was last edited so the UI can mark it
+ALTER TABLE ideas ADD COLUMN edited_at TIMESTAMPTZ;
+ALTER TABLE comments ADD COLUMN edited_at TIMESTAMPTZ;
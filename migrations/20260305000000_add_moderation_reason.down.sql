@@ -0,0 +1,2 @@
+ALTER TABLE comments DROP COLUMN IF EXISTS moderation_reason;
+ALTER TABLE ideas DROP COLUMN IF EXISTS moderation_reason;
@@ -0,0 +1,3 @@
+-- Why a moderator hid an idea or removed a comment, shown to its author.
+ALTER TABLE ideas ADD COLUMN moderation_reason TEXT;
+ALTER TABLE comments ADD COLUMN moderation_reason TEXT;
@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS idx_email_notifications_due;
+
+ALTER TABLE email_notifications
+    DROP COLUMN IF EXISTS sent_at,
+    DROP COLUMN IF EXISTS next_attempt_at,
+    DROP COLUMN IF EXISTS last_error,
+    DROP COLUMN IF EXISTS attempts;
@@ -0,0 +1,10 @@
+-- Delivery bookkeeping for the background SMTP worker.
+ALTER TABLE email_notifications
+    ADD COLUMN attempts INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN last_error TEXT,
+    ADD COLUMN next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ADD COLUMN sent_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_email_notifications_due
+    ON email_notifications(next_attempt_at)
+    WHERE status = 0;
@@ -0,0 +1,8 @@
+DROP INDEX IF EXISTS idx_notifications_unemailed;
+
+ALTER TABLE notifications
+    DROP COLUMN IF EXISTS emailed_at;
+
+ALTER TABLE users
+    DROP COLUMN IF EXISTS notification_digest_sent_at,
+    DROP COLUMN IF EXISTS notification_email;
@@ -0,0 +1,12 @@
+-- Per-user choice of how notifications are emailed: 'instant', 'daily' or 'off'.
+ALTER TABLE users
+    ADD COLUMN notification_email VARCHAR(16) NOT NULL DEFAULT 'off',
+    ADD COLUMN notification_digest_sent_at TIMESTAMPTZ;
+
+-- Set once a notification has been queued for email, alone or in a digest.
+ALTER TABLE notifications
+    ADD COLUMN emailed_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_notifications_unemailed
+    ON notifications(user_id, created_at)
+    WHERE emailed_at IS NULL;
@@ -0,0 +1,2 @@
+-- Scrubbed bodies cannot be restored.
+SELECT 1;
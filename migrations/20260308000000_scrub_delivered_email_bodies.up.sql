@@ -0,0 +1,3 @@
+-- Bodies of delivered or abandoned emails can hold password reset links.
+-- The worker now blanks them; clear the ones it left behind.
+UPDATE email_notifications SET body = '' WHERE status <> 0;
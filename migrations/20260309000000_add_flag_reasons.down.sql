@@ -0,0 +1,3 @@
+ALTER TABLE flags
+    DROP COLUMN IF EXISTS details,
+    DROP COLUMN IF EXISTS reason;
@@ -0,0 +1,5 @@
+-- Why a user flagged something: spam, abusive, off_topic, duplicate or other,
+-- with optional free text.
+ALTER TABLE flags
+    ADD COLUMN reason VARCHAR(20) NOT NULL DEFAULT 'other',
+    ADD COLUMN details TEXT;
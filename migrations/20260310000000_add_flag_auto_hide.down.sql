@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_votes_idea_created;
+ALTER TABLE users DROP COLUMN IF EXISTS dismissed_flags;
+ALTER TABLE comments DROP COLUMN IF EXISTS is_held;
+ALTER TABLE ideas DROP COLUMN IF EXISTS is_auto_hidden;
@@ -0,0 +1,9 @@
+-- Content hidden automatically once enough users flag it, until a moderator
+-- reviews it
+ALTER TABLE ideas ADD COLUMN is_auto_hidden BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE comments ADD COLUMN is_held BOOLEAN NOT NULL DEFAULT false;
+
+-- Flags a moderator dismissed, used to give repeat false flaggers less weight
+ALTER TABLE users ADD COLUMN dismissed_flags INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_votes_idea_created ON votes(idea_id, created_at);
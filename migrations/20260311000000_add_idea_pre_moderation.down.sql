@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_ideas_pending;
+ALTER TABLE ideas DROP COLUMN IF EXISTS is_pending;
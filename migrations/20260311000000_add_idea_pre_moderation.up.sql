@@ -0,0 +1,4 @@
+-- Ideas from new or untrusted accounts wait for a moderator's approval
+ALTER TABLE ideas ADD COLUMN is_pending BOOLEAN NOT NULL DEFAULT false;
+
+CREATE INDEX IF NOT EXISTS idx_ideas_pending ON ideas(created_at) WHERE is_pending;
@@ -0,0 +1,4 @@
+DELETE FROM flags WHERE user_id IS NULL;
+ALTER TABLE flags ALTER COLUMN user_id SET NOT NULL;
+
+DROP TABLE IF EXISTS word_filters;
@@ -0,0 +1,35 @@
+-- Terms the content filter looks for. match_mode is whole_word, substring or
+-- regex; severity is block (reject the post), hold (hide it until a moderator
+-- reviews it) or flag (publish it and add it to the flags queue).
+CREATE TABLE IF NOT EXISTS word_filters (
+    id SERIAL PRIMARY KEY,
+    term TEXT NOT NULL,
+    match_mode VARCHAR(16) NOT NULL DEFAULT 'whole_word',
+    severity VARCHAR(16) NOT NULL DEFAULT 'block',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (term, match_mode)
+);
+
+-- The list previously hard-coded in profanity.rs. Whole-word matching avoids
+-- false positives on words that merely contain one of these.
+INSERT INTO word_filters (term, match_mode, severity) VALUES
+    ('shit', 'whole_word', 'block'),
+    ('piss', 'whole_word', 'block'),
+    ('fuck', 'whole_word', 'block'),
+    ('cunt', 'whole_word', 'block'),
+    ('cocksucker', 'whole_word', 'block'),
+    ('motherfucker', 'whole_word', 'block'),
+    ('tits', 'whole_word', 'block'),
+    ('f*ck', 'whole_word', 'block'),
+    ('f**k', 'whole_word', 'block'),
+    ('sh*t', 'whole_word', 'block'),
+    ('f u c k', 'whole_word', 'block'),
+    ('s h i t', 'whole_word', 'block'),
+    ('nigger', 'whole_word', 'block'),
+    ('nigga', 'whole_word', 'block'),
+    ('fag', 'whole_word', 'block'),
+    ('faggot', 'whole_word', 'block')
+ON CONFLICT DO NOTHING;
+
+-- Flags raised by the word filter rather than by a user
+ALTER TABLE flags ALTER COLUMN user_id DROP NOT NULL;
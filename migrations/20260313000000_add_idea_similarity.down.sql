@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_ideas_content_trgm;
+DROP INDEX IF EXISTS idx_ideas_title_trgm;
+-- The pg_trgm extension is left installed; other database objects may depend on it
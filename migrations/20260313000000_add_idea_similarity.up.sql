@@ -0,0 +1,5 @@
+-- Trigram indexes for finding ideas that look like duplicates
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX idx_ideas_title_trgm ON ideas USING gin (title gin_trgm_ops);
+CREATE INDEX idx_ideas_content_trgm ON ideas USING gin (content gin_trgm_ops);
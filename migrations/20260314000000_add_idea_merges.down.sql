@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_ideas_merged_into;
+ALTER TABLE ideas DROP COLUMN IF EXISTS merged_into;
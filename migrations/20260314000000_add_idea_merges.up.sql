@@ -0,0 +1,4 @@
+-- A duplicate idea merged into another keeps its row as a redirect stub
+ALTER TABLE ideas ADD COLUMN merged_into INTEGER REFERENCES ideas(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_ideas_merged_into ON ideas(merged_into) WHERE merged_into IS NOT NULL;
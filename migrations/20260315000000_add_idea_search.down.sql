@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS idx_ideas_search_vector;
+DROP TRIGGER IF EXISTS trigger_refresh_idea_search_vector_from_comment ON comments;
+DROP FUNCTION IF EXISTS refresh_idea_search_vector_from_comment();
+DROP TRIGGER IF EXISTS trigger_refresh_idea_search_vector ON ideas;
+DROP FUNCTION IF EXISTS refresh_idea_search_vector();
+DROP FUNCTION IF EXISTS idea_search_document(INTEGER, TEXT, TEXT, TEXT);
+ALTER TABLE ideas DROP COLUMN IF EXISTS search_vector;
@@ -0,0 +1,51 @@
+-- Full-text search over idea titles, tags, content and visible comments
+ALTER TABLE ideas ADD COLUMN search_vector tsvector NOT NULL DEFAULT ''::tsvector;
+
+CREATE OR REPLACE FUNCTION idea_search_document(
+    p_idea_id INTEGER,
+    p_title TEXT,
+    p_tags TEXT,
+    p_content TEXT
+) RETURNS tsvector AS $$
+    SELECT setweight(to_tsvector('english', coalesce(p_title, '')), 'A')
+        || setweight(to_tsvector('english', replace(coalesce(p_tags, ''), ',', ' ')), 'B')
+        || setweight(to_tsvector('english', coalesce(p_content, '')), 'C')
+        || setweight(to_tsvector('english', coalesce((
+            SELECT string_agg(c.content, ' ')
+            FROM comments c
+            WHERE c.idea_id = p_idea_id AND c.is_deleted = false AND c.is_held = false
+        ), '')), 'D');
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION refresh_idea_search_vector()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.search_vector := idea_search_document(NEW.id, NEW.title, NEW.tags, NEW.content);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_refresh_idea_search_vector
+BEFORE INSERT OR UPDATE OF title, tags, content ON ideas
+FOR EACH ROW EXECUTE FUNCTION refresh_idea_search_vector();
+
+CREATE OR REPLACE FUNCTION refresh_idea_search_vector_from_comment()
+RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE ideas
+    SET search_vector = idea_search_document(id, title, tags, content)
+    WHERE id IN (
+        CASE WHEN TG_OP = 'INSERT' THEN NULL ELSE OLD.idea_id END,
+        CASE WHEN TG_OP = 'DELETE' THEN NULL ELSE NEW.idea_id END
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_refresh_idea_search_vector_from_comment
+AFTER INSERT OR DELETE OR UPDATE OF content, idea_id, is_deleted, is_held ON comments
+FOR EACH ROW EXECUTE FUNCTION refresh_idea_search_vector_from_comment();
+
+UPDATE ideas SET search_vector = idea_search_document(id, title, tags, content);
+
+CREATE INDEX idx_ideas_search_vector ON ideas USING gin (search_vector);
@@ -0,0 +1,7 @@
+DROP TRIGGER IF EXISTS trigger_refresh_idea_tag_names_from_tag ON tags;
+DROP FUNCTION IF EXISTS refresh_idea_tag_names_from_tag();
+DROP TRIGGER IF EXISTS trigger_refresh_idea_tag_names ON idea_tags;
+DROP FUNCTION IF EXISTS refresh_idea_tag_names();
+DROP FUNCTION IF EXISTS idea_tag_names(INTEGER);
+DROP TABLE IF EXISTS idea_tags;
+DROP TABLE IF EXISTS tags;
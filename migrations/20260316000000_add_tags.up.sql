@@ -0,0 +1,81 @@
+-- Tags become rows of their own, matched by slug so "VPN", "vpn " and "Vpn"
+-- are one tag. Retired tags keep their ideas but cannot be added to more.
+CREATE TABLE IF NOT EXISTS tags (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(32) NOT NULL,
+    slug VARCHAR(32) NOT NULL UNIQUE,
+    is_retired BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS idea_tags (
+    idea_id INTEGER NOT NULL REFERENCES ideas(id) ON DELETE CASCADE,
+    tag_id INTEGER NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (idea_id, tag_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_idea_tags_tag_id ON idea_tags(tag_id);
+
+-- Split the old comma-separated strings. The first idea to use a slug picks
+-- the tag's spelling.
+CREATE TEMPORARY TABLE legacy_idea_tags AS
+SELECT idea_id, name, trim(BOTH '-' FROM regexp_replace(lower(name), '[^a-z0-9]+', '-', 'g')) AS slug
+FROM (
+    SELECT i.id AS idea_id, left(regexp_replace(trim(t.tag), '\s+', ' ', 'g'), 32) AS name
+    FROM ideas i, unnest(string_to_array(i.tags, ',')) AS t(tag)
+) split;
+
+INSERT INTO tags (name, slug)
+SELECT DISTINCT ON (slug) name, slug
+FROM legacy_idea_tags
+WHERE slug <> ''
+ORDER BY slug, idea_id
+ON CONFLICT (slug) DO NOTHING;
+
+INSERT INTO idea_tags (idea_id, tag_id)
+SELECT DISTINCT l.idea_id, t.id
+FROM legacy_idea_tags l
+INNER JOIN tags t ON t.slug = l.slug
+ON CONFLICT DO NOTHING;
+
+DROP TABLE legacy_idea_tags;
+
+-- ideas.tags stays as the display string of tag names, which the search
+-- vector, similarity checks and revisions read. These triggers rebuild it
+-- whenever an idea's tags change or a tag is renamed.
+CREATE OR REPLACE FUNCTION idea_tag_names(p_idea_id INTEGER) RETURNS TEXT AS $$
+    SELECT coalesce(string_agg(t.name, ', ' ORDER BY lower(t.name)), '')
+    FROM idea_tags it
+    INNER JOIN tags t ON t.id = it.tag_id
+    WHERE it.idea_id = p_idea_id;
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION refresh_idea_tag_names()
+RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE ideas
+    SET tags = idea_tag_names(id)
+    WHERE id = CASE WHEN TG_OP = 'DELETE' THEN OLD.idea_id ELSE NEW.idea_id END;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_refresh_idea_tag_names
+AFTER INSERT OR DELETE ON idea_tags
+FOR EACH ROW EXECUTE FUNCTION refresh_idea_tag_names();
+
+CREATE OR REPLACE FUNCTION refresh_idea_tag_names_from_tag()
+RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE ideas
+    SET tags = idea_tag_names(id)
+    WHERE id IN (SELECT idea_id FROM idea_tags WHERE tag_id = NEW.id);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_refresh_idea_tag_names_from_tag
+AFTER UPDATE OF name ON tags
+FOR EACH ROW EXECUTE FUNCTION refresh_idea_tag_names_from_tag();
+
+UPDATE ideas SET tags = idea_tag_names(id);
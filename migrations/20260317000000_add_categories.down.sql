@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS category_moderators;
+DROP INDEX IF EXISTS idx_ideas_category_id;
+ALTER TABLE ideas DROP COLUMN IF EXISTS category_id;
+DROP TABLE IF EXISTS categories;
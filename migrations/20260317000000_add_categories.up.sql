@@ -0,0 +1,36 @@
+-- Service areas ideas are filed under. Archived categories keep their ideas
+-- but take no new ones.
+CREATE TABLE IF NOT EXISTS categories (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(60) NOT NULL,
+    slug VARCHAR(60) NOT NULL UNIQUE,
+    description TEXT NOT NULL DEFAULT '',
+    is_archived BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO categories (name, slug, description) VALUES
+    ('Networking', 'networking', 'Wired and wireless networks, VPN and campus connectivity'),
+    ('Identity & Access', 'identity-access', 'BlazerID, passwords, single sign-on and Duo'),
+    ('Teaching Technology', 'teaching-technology', 'Canvas, classroom technology and lecture capture'),
+    ('Research Computing', 'research-computing', 'Cheaha, research storage and scientific software'),
+    ('General', 'general', 'Anything else about UAB IT services')
+ON CONFLICT (slug) DO NOTHING;
+
+-- Every idea needs a category; the ones posted so far start in General
+ALTER TABLE ideas ADD COLUMN IF NOT EXISTS category_id INTEGER REFERENCES categories(id);
+UPDATE ideas SET category_id = (SELECT id FROM categories WHERE slug = 'general')
+WHERE category_id IS NULL;
+ALTER TABLE ideas ALTER COLUMN category_id SET NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_ideas_category_id ON ideas(category_id);
+
+-- Moderators assigned here may only moderate ideas in these categories.
+-- Moderators without any rows moderate every category, as before.
+CREATE TABLE IF NOT EXISTS category_moderators (
+    category_id INTEGER NOT NULL REFERENCES categories(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (category_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_category_moderators_user_id ON category_moderators(user_id);
@@ -0,0 +1,4 @@
+ALTER TABLE ideas DROP CONSTRAINT IF EXISTS ideas_stage_fkey;
+ALTER TABLE ideas ALTER COLUMN stage SET DEFAULT 'Ideate';
+DROP TABLE IF EXISTS stage_transitions;
+DROP TABLE IF EXISTS stages;
@@ -0,0 +1,78 @@
+-- Workflow stages ideas move through, replacing the fixed list in the code.
+-- `color` names one of the badge colors in the stylesheet. Terminal stages
+-- mark an idea as closed, whether it shipped or was declined.
+CREATE TABLE IF NOT EXISTS stages (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(50) NOT NULL UNIQUE,
+    position INTEGER NOT NULL,
+    color VARCHAR(20) NOT NULL DEFAULT 'gray',
+    is_terminal BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Names differing only in case would be confusing in filters and pickers
+CREATE UNIQUE INDEX IF NOT EXISTS idx_stages_lower_name ON stages(lower(name));
+
+-- The stages an idea may move to from each stage
+CREATE TABLE IF NOT EXISTS stage_transitions (
+    from_stage_id INTEGER NOT NULL REFERENCES stages(id) ON DELETE CASCADE,
+    to_stage_id INTEGER NOT NULL REFERENCES stages(id) ON DELETE CASCADE,
+    PRIMARY KEY (from_stage_id, to_stage_id),
+    CHECK (from_stage_id <> to_stage_id)
+);
+
+INSERT INTO stages (name, position, color, is_terminal) VALUES
+    ('Ideate', 1, 'evergreen', false),
+    ('Needs Info', 2, 'gray', false),
+    ('Review', 3, 'sky-blue', false),
+    ('In Progress', 4, 'gold', false),
+    ('Completed', 5, 'deep-gold', true),
+    ('Shipped', 6, 'campus-green', true),
+    ('Declined', 7, 'red', true),
+    ('Duplicate', 8, 'gray', true)
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO stage_transitions (from_stage_id, to_stage_id)
+SELECT f.id, t.id
+FROM (VALUES
+    ('Ideate', 'Needs Info'), ('Ideate', 'Review'), ('Ideate', 'Declined'),
+    ('Ideate', 'Duplicate'),
+    ('Needs Info', 'Ideate'), ('Needs Info', 'Review'), ('Needs Info', 'Declined'),
+    ('Review', 'Ideate'), ('Review', 'Needs Info'), ('Review', 'In Progress'),
+    ('Review', 'Declined'), ('Review', 'Duplicate'),
+    ('In Progress', 'Review'), ('In Progress', 'Completed'), ('In Progress', 'Shipped'),
+    ('In Progress', 'Declined'),
+    ('Completed', 'Shipped'), ('Completed', 'In Progress'),
+    ('Declined', 'Review')
+) AS edges (from_name, to_name)
+INNER JOIN stages f ON f.name = edges.from_name
+INNER JOIN stages t ON t.name = edges.to_name
+ON CONFLICT DO NOTHING;
+
+-- Keep any stage already in use that the code never knew about, so every
+-- idea keeps its stage; moderators can move those ideas to any seeded stage.
+-- Names are unique ignoring case, so one spelled differently only in case
+-- joins the existing stage
+INSERT INTO stages (name, position, color)
+SELECT stage, 8 + ROW_NUMBER() OVER (ORDER BY stage), 'gray'
+FROM (SELECT DISTINCT ON (lower(stage)) stage FROM ideas ORDER BY lower(stage), stage) used
+WHERE NOT EXISTS (SELECT 1 FROM stages s WHERE lower(s.name) = lower(used.stage));
+
+UPDATE ideas i
+SET stage = s.name
+FROM stages s
+WHERE lower(s.name) = lower(i.stage) AND s.name <> i.stage;
+
+INSERT INTO stage_transitions (from_stage_id, to_stage_id)
+SELECT legacy.id, seeded.id
+FROM stages legacy
+CROSS JOIN stages seeded
+WHERE legacy.position > 8 AND seeded.position <= 8
+ON CONFLICT DO NOTHING;
+
+-- ideas.stage keeps the stage name; renaming a stage renames it on its ideas,
+-- and a stage in use cannot be deleted. New ideas start in the first stage,
+-- chosen when they are created.
+ALTER TABLE ideas ALTER COLUMN stage DROP DEFAULT;
+ALTER TABLE ideas
+    ADD CONSTRAINT ideas_stage_fkey FOREIGN KEY (stage) REFERENCES stages(name) ON UPDATE CASCADE;
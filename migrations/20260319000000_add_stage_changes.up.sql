@@ -0,0 +1,25 @@
+-- Timeline of an idea's moves between stages. Stage names are copied so the
+-- history reads as it happened even after a stage is renamed or deleted.
+-- `response` is the official staff response in Markdown, if one was given.
+CREATE TABLE IF NOT EXISTS stage_changes (
+    id SERIAL PRIMARY KEY,
+    idea_id INTEGER NOT NULL REFERENCES ideas(id) ON DELETE CASCADE,
+    actor_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    from_stage VARCHAR(50) NOT NULL,
+    to_stage VARCHAR(50) NOT NULL,
+    response TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_stage_changes_idea_id ON stage_changes(idea_id, created_at);
+
+-- Earlier stage changes are in the moderation log, without responses
+INSERT INTO stage_changes (idea_id, actor_id, from_stage, to_stage, created_at)
+SELECT l.target_id, l.actor_id, l.before_value, l.after_value, l.created_at
+FROM moderation_log l
+WHERE l.action = 'update_stage'
+  AND l.target_type = 'idea'
+  AND l.before_value IS NOT NULL
+  AND l.after_value IS NOT NULL
+  AND l.before_value <> l.after_value
+  AND EXISTS (SELECT 1 FROM ideas i WHERE i.id = l.target_id);
@@ -0,0 +1,6 @@
+-- Replies to comments. Threads are one level deep: a reply's parent is
+-- always a top-level comment on the same idea, which the server ensures.
+ALTER TABLE comments
+    ADD COLUMN IF NOT EXISTS parent_id INTEGER REFERENCES comments(id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_comments_parent_id ON comments(parent_id);
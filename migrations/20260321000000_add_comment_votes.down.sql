@@ -0,0 +1,6 @@
+DROP TRIGGER IF EXISTS trigger_decrement_comment_helpful_count ON comment_votes;
+DROP TRIGGER IF EXISTS trigger_increment_comment_helpful_count ON comment_votes;
+DROP FUNCTION IF EXISTS decrement_comment_helpful_count();
+DROP FUNCTION IF EXISTS increment_comment_helpful_count();
+DROP TABLE IF EXISTS comment_votes;
+ALTER TABLE comments DROP COLUMN IF EXISTS helpful_count;
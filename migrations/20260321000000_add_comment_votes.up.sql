@@ -0,0 +1,41 @@
+-- "Helpful" votes on comments, one per user and comment. Counts are kept on
+-- the comment by triggers, like votes on ideas.
+CREATE TABLE IF NOT EXISTS comment_votes (
+    id SERIAL PRIMARY KEY,
+    comment_id INTEGER NOT NULL REFERENCES comments(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(user_id, comment_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_comment_votes_comment_id ON comment_votes(comment_id);
+
+ALTER TABLE comments ADD COLUMN IF NOT EXISTS helpful_count INTEGER NOT NULL DEFAULT 0;
+
+CREATE OR REPLACE FUNCTION increment_comment_helpful_count()
+RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE comments SET helpful_count = helpful_count + 1 WHERE id = NEW.comment_id;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trigger_increment_comment_helpful_count ON comment_votes;
+CREATE TRIGGER trigger_increment_comment_helpful_count
+    AFTER INSERT ON comment_votes
+    FOR EACH ROW
+    EXECUTE FUNCTION increment_comment_helpful_count();
+
+CREATE OR REPLACE FUNCTION decrement_comment_helpful_count()
+RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE comments SET helpful_count = helpful_count - 1 WHERE id = OLD.comment_id;
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trigger_decrement_comment_helpful_count ON comment_votes;
+CREATE TRIGGER trigger_decrement_comment_helpful_count
+    AFTER DELETE ON comment_votes
+    FOR EACH ROW
+    EXECUTE FUNCTION decrement_comment_helpful_count();
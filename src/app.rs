@@ -6,7 +6,8 @@ use leptos_router::path;
 use crate::auth::{bump_auth_refresh, get_user, use_auth_refresh, Logout};
 use crate::routes::paths;
 use crate::routes::{
-    AccountPage, AdminPage, IdeaDetailPage, IdeasPage, Login, ResetPassword, Signup,
+    AccountPage, AdminPage, IdeaDetailPage, IdeasPage, Login, NotificationBell, ResetPassword,
+    RoadmapPage, Signup, TagPage,
 };
 
 pub fn shell(options: LeptosOptions) -> impl IntoView {
@@ -45,6 +46,19 @@ fn NavBar() -> impl IntoView {
                     <img src="/uab-logo.jpg" alt="UAB IT Idea Board" class="navbar-logo"/>
                 </A>
                 <ul class="nav navbar-nav pull-xs-right">
+                    <li class="nav-item">
+                        <A href=paths::ROADMAP attr:class="nav-link">"Roadmap"</A>
+                    </li>
+                    <Suspense>
+                        {move || {
+                            matches!(auth_nav_state(user_resource.get()), AuthNavState::Authenticated)
+                                .then(|| view! {
+                                    <li class="nav-item nav-item-notifications">
+                                        <NotificationBell/>
+                                    </li>
+                                })
+                        }}
+                    </Suspense>
                     <li class="nav-item nav-item-auth">
                         <Suspense fallback=move || view! { <span class="nav-link">"…"</span> }>
                             {move || render_auth_nav_item(auth_nav_state(user_resource.get()), logout)}
@@ -111,6 +125,8 @@ fn AppRoutes() -> impl IntoView {
         <Routes fallback=|| view! { <div class="container"><p>"Page not found"</p></div> }>
             <Route path=path!("/") view=IdeasPage/>
             <Route path=path!("/ideas/:id") view=IdeaDetailPage/>
+            <Route path=path!("/tags/:slug") view=TagPage/>
+            <Route path=path!("/roadmap") view=RoadmapPage/>
             <Route path=path!("/login") view=Login/>
             <Route path=path!("/signup") view=Signup/>
             <Route path=path!("/reset_password") view=ResetPassword/>
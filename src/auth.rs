@@ -107,15 +107,222 @@ fn cas_http_timeout() -> Duration {
 }
 
 #[cfg(feature = "ssr")]
-fn build_session_cookie_header(session: &UserSession) -> Result<HeaderValue, ServerFnError> {
-    let session_json = serde_json::to_string(session)
-        .map_err(|e| ServerFnError::new(format!("Session serialization error: {e}")))?;
+const SESSION_COOKIE_NAME: &str = "user_session";
 
-    let cookie = Cookie::build(("user_session", session_json))
+#[cfg(feature = "ssr")]
+const SESSION_TTL_DAYS: i64 = 7;
+
+/// The cookie only names a session; identity and role are always re-read
+/// from the database when the session is resolved.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Serialize, Deserialize)]
+struct SessionClaims {
+    sid: String,
+    sub: i32,
+    exp: usize,
+}
+
+/// The user behind the current request, resolved once per request by
+/// [`session_middleware`] and stored in the request extensions.
+#[cfg(feature = "ssr")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CurrentUser {
+    pub session_id: i32,
+    pub user: UserSession,
+}
+
+/// Browser details recorded alongside a new session.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ClientInfo {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+#[cfg(feature = "ssr")]
+impl ClientInfo {
+    pub fn from_headers(headers: &axum::http::HeaderMap) -> Self {
+        fn header_text(headers: &axum::http::HeaderMap, name: &str) -> Option<String> {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(ToString::to_string)
+        }
+
+        // Caddy sits in front of the app, so the client address arrives as the
+        // first entry of X-Forwarded-For.
+        let ip_address = header_text(headers, "x-forwarded-for")
+            .and_then(|value| value.split(',').next().map(|ip| ip.trim().to_string()))
+            .or_else(|| header_text(headers, "x-real-ip"))
+            .map(|ip| ip.chars().take(64).collect());
+
+        Self {
+            user_agent: header_text(headers, "user-agent"),
+            ip_address,
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn session_token_secret() -> Result<String, ServerFnError> {
+    std::env::var("JWT_SECRET")
+        .map_err(|e| ServerFnError::new(format!("JWT_SECRET is not configured: {e}")))
+}
+
+#[cfg(feature = "ssr")]
+fn encode_session_token(
+    session_token: &str,
+    user_id: i32,
+    secret: &str,
+    expires_at: chrono::DateTime<chrono::Utc>,
+) -> Result<String, ServerFnError> {
+    use jsonwebtoken::{encode, EncodingKey, Header};
+
+    let claims = SessionClaims {
+        sid: session_token.to_string(),
+        sub: user_id,
+        exp: expires_at.timestamp() as usize,
+    };
+
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )
+    .map_err(|e| ServerFnError::new(format!("Failed to create session token: {e}")))
+}
+
+#[cfg(feature = "ssr")]
+fn decode_session_token(token: &str, secret: &str) -> Result<SessionClaims, ServerFnError> {
+    use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+
+    let mut validation = Validation::new(Algorithm::HS256);
+    validation.validate_exp = true;
+    validation.leeway = 0;
+
+    decode::<SessionClaims>(
+        token,
+        &DecodingKey::from_secret(secret.as_bytes()),
+        &validation,
+    )
+    .map(|data| data.claims)
+    .map_err(|e| ServerFnError::new(format!("Invalid session token: {e}")))
+}
+
+/// Verify the signature on the session cookie without consulting the session store.
+#[cfg(feature = "ssr")]
+fn session_claims_from_cookie_jar(jar: &axum_extra::extract::CookieJar) -> Option<SessionClaims> {
+    let cookie = jar.get(SESSION_COOKIE_NAME)?;
+    let secret = match session_token_secret() {
+        Ok(secret) => secret,
+        Err(error) => {
+            tracing::error!("Cannot verify session cookie: {error}");
+            return None;
+        }
+    };
+
+    match decode_session_token(cookie.value(), &secret) {
+        Ok(claims) => Some(claims),
+        Err(error) => {
+            tracing::warn!("Rejected session cookie: {error}");
+            None
+        }
+    }
+}
+
+/// Resolve the session cookie in `jar` to a live session and the user's
+/// current database row. Returns `None` when the cookie is missing, tampered
+/// with, expired, or revoked, or when the user no longer exists.
+#[cfg(feature = "ssr")]
+pub async fn resolve_current_user(jar: &axum_extra::extract::CookieJar) -> Option<CurrentUser> {
+    let claims = session_claims_from_cookie_jar(jar)?;
+
+    match crate::models::Session::touch_with_user(&claims.sid).await {
+        Ok(Some((session_id, user))) if user.id == claims.sub => Some(CurrentUser {
+            session_id,
+            user: UserSession {
+                id: user.id,
+                email: user.email,
+                name: user.name,
+                role: user.role,
+            },
+        }),
+        Ok(_) => {
+            tracing::info!(user_id = claims.sub, "Rejected revoked or expired session");
+            None
+        }
+        Err(error) => {
+            tracing::error!("Session lookup failed: {error}");
+            None
+        }
+    }
+}
+
+/// Resolve the session once per request and expose it to handlers and server
+/// functions as an `Option<CurrentUser>` extension. A cookie that no longer
+/// resolves (revoked session, deleted user) is cleared on the way out.
+#[cfg(feature = "ssr")]
+pub async fn session_middleware(
+    jar: axum_extra::extract::CookieJar,
+    mut request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Response {
+    let current_user = resolve_current_user(&jar).await;
+    let stale_cookie = current_user.is_none() && jar.get(SESSION_COOKIE_NAME).is_some();
+    request.extensions_mut().insert(current_user);
+
+    let mut response = next.run(request).await;
+    // Leave the response alone if the handler just issued a fresh session.
+    let sets_session_cookie = response
+        .headers()
+        .get_all(SET_COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .any(|value| value.starts_with(&format!("{SESSION_COOKIE_NAME}=")));
+    if stale_cookie && !sets_session_cookie {
+        if let Ok(clear_cookie) = build_clear_session_cookie_header() {
+            response.headers_mut().append(SET_COOKIE, clear_cookie);
+        }
+    }
+    response
+}
+
+/// The user behind the current server function call, taken from the
+/// per-request cache populated by [`session_middleware`].
+#[cfg(feature = "ssr")]
+pub async fn current_user() -> Result<Option<CurrentUser>, ServerFnError> {
+    use axum::Extension;
+    use axum_extra::extract::CookieJar;
+
+    if let Ok(Extension(current_user)) =
+        leptos_axum::extract::<Extension<Option<CurrentUser>>>().await
+    {
+        return Ok(current_user);
+    }
+
+    // Fall back to resolving directly if the middleware did not run.
+    let jar: CookieJar = leptos_axum::extract().await?;
+    Ok(resolve_current_user(&jar).await)
+}
+
+/// Create a server-side session for a user and build the cookie that refers to it.
+#[cfg(feature = "ssr")]
+async fn start_session(user_id: i32, client: ClientInfo) -> Result<HeaderValue, ServerFnError> {
+    let secret = session_token_secret()?;
+    let expires_at = chrono::Utc::now() + chrono::Duration::days(SESSION_TTL_DAYS);
+    let session =
+        crate::models::Session::create(user_id, client.user_agent, client.ip_address, expires_at)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Failed to create session: {e}")))?;
+    let token = encode_session_token(&session.token, user_id, &secret, expires_at)?;
+
+    let cookie = Cookie::build((SESSION_COOKIE_NAME, token))
         .path("/")
         .same_site(SameSite::Lax)
         .http_only(true)
-        .max_age(time::Duration::days(7));
+        .max_age(time::Duration::days(SESSION_TTL_DAYS));
 
     HeaderValue::from_str(&cookie.to_string())
         .map_err(|e| ServerFnError::new(format!("Cookie error: {e}")))
@@ -123,7 +330,7 @@ fn build_session_cookie_header(session: &UserSession) -> Result<HeaderValue, Ser
 
 #[cfg(feature = "ssr")]
 fn build_clear_session_cookie_header() -> Result<HeaderValue, ServerFnError> {
-    let cookie = Cookie::build(("user_session", ""))
+    let cookie = Cookie::build((SESSION_COOKIE_NAME, ""))
         .path("/")
         .max_age(time::Duration::seconds(0));
 
@@ -132,12 +339,22 @@ fn build_clear_session_cookie_header() -> Result<HeaderValue, ServerFnError> {
 }
 
 #[cfg(feature = "ssr")]
-fn set_session_cookie_response(session: &UserSession) -> Result<(), ServerFnError> {
+async fn set_session_cookie_response(user_id: i32) -> Result<(), ServerFnError> {
+    let headers: axum::http::HeaderMap = leptos_axum::extract().await?;
+    let cookie = start_session(user_id, ClientInfo::from_headers(&headers)).await?;
     let response_options = expect_context::<leptos_axum::ResponseOptions>();
-    response_options.insert_header(SET_COOKIE, build_session_cookie_header(session)?);
+    response_options.insert_header(SET_COOKIE, cookie);
     Ok(())
 }
 
+/// The ID of the session making the current request, if it is still live.
+#[cfg(feature = "ssr")]
+pub async fn current_session_id() -> Result<Option<i32>, ServerFnError> {
+    Ok(current_user()
+        .await?
+        .map(|current_user| current_user.session_id))
+}
+
 #[cfg(feature = "ssr")]
 fn find_descendant_text(node: roxmltree::Node<'_, '_>, tags: &[&str]) -> Option<String> {
     tags.iter().find_map(|tag| {
@@ -384,7 +601,10 @@ pub async fn cas_login_redirect() -> Response {
 }
 
 #[cfg(feature = "ssr")]
-pub async fn cas_callback(Query(query): Query<CasCallbackQuery>) -> Response {
+pub async fn cas_callback(
+    headers: axum::http::HeaderMap,
+    Query(query): Query<CasCallbackQuery>,
+) -> Response {
     let Some(ticket) = query.ticket.filter(|value| !value.trim().is_empty()) else {
         return Redirect::temporary("/login?cas_error=missing_ticket").into_response();
     };
@@ -413,21 +633,14 @@ pub async fn cas_callback(Query(query): Query<CasCallbackQuery>) -> Response {
         }
     };
 
-    let session = UserSession {
-        id: user.id,
-        email: user.email,
-        name: user.name,
-        role: user.role,
-    };
-
-    match build_session_cookie_header(&session) {
+    match start_session(user.id, ClientInfo::from_headers(&headers)).await {
         Ok(cookie_header) => {
             let mut response = Redirect::temporary("/").into_response();
             response.headers_mut().insert(SET_COOKIE, cookie_header);
             response
         }
         Err(error) => {
-            tracing::error!("CAS session cookie build failed: {error}");
+            tracing::error!("CAS session creation failed: {error}");
             Redirect::temporary("/login?cas_error=session").into_response()
         }
     }
@@ -453,16 +666,8 @@ pub async fn login(email: String, password: String) -> Result<LoginMessages, Ser
 
     match user {
         Some(user) => {
-            // Create session
-            let session = UserSession {
-                id: user.id,
-                email: user.email,
-                name: user.name,
-                role: user.role,
-            };
-
-            // Set session cookie
-            set_session_cookie_response(&session)?;
+            // Create session and set session cookie
+            set_session_cookie_response(user.id).await?;
 
             leptos_axum::redirect("/");
             Ok(LoginMessages::Successful)
@@ -489,28 +694,33 @@ pub enum SignupResponse {
 pub type SignupSignal = ServerAction<Signup>;
 
 pub fn validate_signup(name: String, email: String, password: String) -> Result<(), String> {
-    // Validate name
-    if name.trim().is_empty() {
-        return Err("Name cannot be empty".to_string());
-    }
-    if name.len() < 2 {
-        return Err("Name must be at least 2 characters".to_string());
-    }
+    validate_display_name(&name)?;
 
     // Validate email format
     if !email.contains('@') {
         return Err("Invalid email format".to_string());
     }
 
-    // Validate password
-    if password.len() < 8 {
-        return Err("Password must be at least 8 characters".to_string());
+    validate_password(&password)
+}
+
+/// Display name rules shared by signup and the profile page.
+pub fn validate_display_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Name cannot be empty".to_string());
+    }
+    if name.len() < 2 {
+        return Err("Name must be at least 2 characters".to_string());
+    }
+    // `users.name` is VARCHAR(100), which counts characters
+    if name.chars().count() > 100 {
+        return Err("Name cannot exceed 100 characters".to_string());
     }
 
     // Check profanity in name
     #[cfg(feature = "ssr")]
     {
-        if crate::profanity::contains_profanity(&name) {
+        if crate::profanity::contains_profanity(name) {
             return Err("Name contains inappropriate language".to_string());
         }
     }
@@ -518,6 +728,14 @@ pub fn validate_signup(name: String, email: String, password: String) -> Result<
     Ok(())
 }
 
+/// Password rules shared by signup and the profile page.
+pub fn validate_password(password: &str) -> Result<(), String> {
+    if password.len() < 8 {
+        return Err("Password must be at least 8 characters".to_string());
+    }
+    Ok(())
+}
+
 #[server]
 pub async fn signup(
     email: String,
@@ -548,14 +766,7 @@ pub async fn signup(
         .map_err(|e| ServerFnError::new(format!("Failed to create user: {}", e)))?;
 
     // Auto-login: Create session
-    let session = UserSession {
-        id: user.id,
-        email: user.email,
-        name: user.name,
-        role: user.role,
-    };
-
-    set_session_cookie_response(&session)?;
+    set_session_cookie_response(user.id).await?;
 
     Ok(SignupResponse::Success)
 }
@@ -563,6 +774,30 @@ pub async fn signup(
 // Logout
 #[server]
 pub async fn logout() -> Result<(), ServerFnError> {
+    use axum_extra::extract::CookieJar;
+
+    let jar: CookieJar = leptos_axum::extract().await?;
+    if let Some(claims) = session_claims_from_cookie_jar(&jar) {
+        crate::models::Session::revoke_by_token(&claims.sid)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Failed to end session: {e}")))?;
+    }
+
+    let response_options = expect_context::<leptos_axum::ResponseOptions>();
+    response_options.insert_header(SET_COOKIE, build_clear_session_cookie_header()?);
+
+    Ok(())
+}
+
+// Logout of every browser and device
+#[server]
+pub async fn logout_everywhere() -> Result<(), ServerFnError> {
+    let user = require_auth().await?;
+
+    crate::models::Session::revoke_all_for_user(user.id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to end sessions: {e}")))?;
+
     let response_options = expect_context::<leptos_axum::ResponseOptions>();
     response_options.insert_header(SET_COOKIE, build_clear_session_cookie_header()?);
 
@@ -572,16 +807,13 @@ pub async fn logout() -> Result<(), ServerFnError> {
 // Get current user from session
 #[server]
 pub async fn get_user() -> Result<Option<UserSession>, ServerFnError> {
-    use axum_extra::extract::CookieJar;
-
-    let jar: CookieJar = leptos_axum::extract().await?;
+    Ok(current_user().await?.map(|current_user| current_user.user))
+}
 
-    if let Some(cookie) = jar.get("user_session") {
-        let session: UserSession = serde_json::from_str(cookie.value())
-            .map_err(|e| ServerFnError::new(format!("Session deserialization error: {}", e)))?;
-        Ok(Some(session))
-    } else {
-        Ok(None)
+#[cfg(feature = "ssr")]
+fn set_response_status(status: axum::http::StatusCode) {
+    if let Some(response_options) = use_context::<leptos_axum::ResponseOptions>() {
+        response_options.set_status(status);
     }
 }
 
@@ -590,7 +822,10 @@ pub async fn get_user() -> Result<Option<UserSession>, ServerFnError> {
 pub async fn require_auth() -> Result<UserSession, ServerFnError> {
     match get_user().await? {
         Some(user) => Ok(user),
-        None => Err(ServerFnError::new("Authentication required")),
+        None => {
+            set_response_status(axum::http::StatusCode::UNAUTHORIZED);
+            Err(ServerFnError::new("Authentication required"))
+        }
     }
 }
 
@@ -601,10 +836,65 @@ pub async fn require_moderator() -> Result<UserSession, ServerFnError> {
     if user.is_moderator() {
         Ok(user)
     } else {
+        set_response_status(axum::http::StatusCode::FORBIDDEN);
         Err(ServerFnError::new("Moderator access required"))
     }
 }
 
+/// Require a moderator and return the categories they may moderate, for
+/// filtering moderation queues with `scope_covers`. The scope is empty for
+/// an admin or a moderator not limited to any category.
+#[cfg(feature = "ssr")]
+pub async fn require_moderator_scope() -> Result<(UserSession, Vec<i32>), ServerFnError> {
+    let user = require_moderator().await?;
+    if user.is_admin() {
+        return Ok((user, Vec::new()));
+    }
+    let scope = crate::models::Category::moderator_scope(user.id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to check moderator scope: {}", e)))?;
+    Ok((user, scope))
+}
+
+/// Require a moderator who may moderate ideas in `category_id`: an admin, a
+/// moderator assigned to that category, or one not limited to any.
+#[cfg(feature = "ssr")]
+pub async fn require_category_moderator(category_id: i32) -> Result<UserSession, ServerFnError> {
+    use crate::models::scope_covers;
+
+    let (user, scope) = require_moderator_scope().await?;
+    if scope_covers(&scope, category_id) {
+        Ok(user)
+    } else {
+        set_response_status(axum::http::StatusCode::FORBIDDEN);
+        Err(ServerFnError::new(
+            "You are not a moderator for this idea's category",
+        ))
+    }
+}
+
+/// Require a moderator who may moderate the idea with `idea_id`.
+#[cfg(feature = "ssr")]
+pub async fn require_idea_moderator(idea_id: i32) -> Result<UserSession, ServerFnError> {
+    require_moderator().await?;
+    let category_id = crate::models::Category::of_idea(idea_id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to fetch idea: {}", e)))?
+        .ok_or_else(|| ServerFnError::new("Idea not found"))?;
+    require_category_moderator(category_id).await
+}
+
+/// Require a moderator who may moderate the idea a comment is on.
+#[cfg(feature = "ssr")]
+pub async fn require_comment_moderator(comment_id: i32) -> Result<UserSession, ServerFnError> {
+    require_moderator().await?;
+    let category_id = crate::models::Category::of_comment(comment_id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to fetch comment: {}", e)))?
+        .ok_or_else(|| ServerFnError::new("Comment not found"))?;
+    require_category_moderator(category_id).await
+}
+
 // Helper to require admin role
 #[cfg(feature = "ssr")]
 pub async fn require_admin() -> Result<UserSession, ServerFnError> {
@@ -612,13 +902,146 @@ pub async fn require_admin() -> Result<UserSession, ServerFnError> {
     if user.is_admin() {
         Ok(user)
     } else {
+        set_response_status(axum::http::StatusCode::FORBIDDEN);
         Err(ServerFnError::new("Admin access required"))
     }
 }
 
 #[cfg(all(test, feature = "ssr"))]
 mod tests {
-    use super::parse_cas_user_info;
+    use super::{
+        decode_session_token, encode_session_token, parse_cas_user_info, validate_display_name,
+        ClientInfo, UserSession,
+    };
+    use axum::http::{HeaderMap, HeaderValue};
+
+    const TEST_SECRET: &str = "test-session-secret";
+    const TEST_SID: &str = "0123456789abcdef";
+    const TEST_USER_ID: i32 = 42;
+
+    #[test]
+    fn session_token_round_trips() {
+        let expires_at = chrono::Utc::now() + chrono::Duration::hours(1);
+        let token = encode_session_token(TEST_SID, TEST_USER_ID, TEST_SECRET, expires_at)
+            .expect("session token should encode");
+
+        let claims = decode_session_token(&token, TEST_SECRET).expect("token should verify");
+        assert_eq!(claims.sid, TEST_SID);
+        assert_eq!(claims.sub, TEST_USER_ID);
+    }
+
+    #[test]
+    fn session_token_does_not_carry_role_or_identity() {
+        let expires_at = chrono::Utc::now() + chrono::Duration::hours(1);
+        let token = encode_session_token(TEST_SID, TEST_USER_ID, TEST_SECRET, expires_at)
+            .expect("session token should encode");
+
+        let claims = jsonwebtoken::decode::<serde_json::Value>(
+            &token,
+            &jsonwebtoken::DecodingKey::from_secret(TEST_SECRET.as_bytes()),
+            &jsonwebtoken::Validation::default(),
+        )
+        .expect("token should verify")
+        .claims;
+        let keys: Vec<&str> = claims
+            .as_object()
+            .expect("payload should be an object")
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(keys, vec!["exp", "sid", "sub"]);
+    }
+
+    #[test]
+    fn session_token_rejects_tampered_payload() {
+        let expires_at = chrono::Utc::now() + chrono::Duration::hours(1);
+        let token = encode_session_token(TEST_SID, TEST_USER_ID, TEST_SECRET, expires_at)
+            .expect("session token should encode");
+        let other_user_token = encode_session_token(TEST_SID, 1, "attacker-secret", expires_at)
+            .expect("session token should encode");
+
+        // Splice another user's payload onto the original, validly signed token.
+        let original: Vec<&str> = token.split('.').collect();
+        let forged_payload = other_user_token.split('.').nth(1).expect("payload segment");
+        let forged = format!("{}.{}.{}", original[0], forged_payload, original[2]);
+
+        assert!(decode_session_token(&forged, TEST_SECRET).is_err());
+    }
+
+    #[test]
+    fn session_token_rejects_wrong_secret() {
+        let expires_at = chrono::Utc::now() + chrono::Duration::hours(1);
+        let token = encode_session_token(TEST_SID, TEST_USER_ID, "other-secret", expires_at)
+            .expect("session token should encode");
+
+        assert!(decode_session_token(&token, TEST_SECRET).is_err());
+    }
+
+    #[test]
+    fn session_token_rejects_expired_token() {
+        let expires_at = chrono::Utc::now() - chrono::Duration::minutes(5);
+        let token = encode_session_token(TEST_SID, TEST_USER_ID, TEST_SECRET, expires_at)
+            .expect("session token should encode");
+
+        assert!(decode_session_token(&token, TEST_SECRET).is_err());
+    }
+
+    #[test]
+    fn session_token_rejects_legacy_json_cookie() {
+        let legacy = serde_json::to_string(&UserSession {
+            id: TEST_USER_ID,
+            email: "user@uab.edu".to_string(),
+            name: "Test User".to_string(),
+            role: 2,
+        })
+        .expect("session should serialize");
+        assert!(decode_session_token(&legacy, TEST_SECRET).is_err());
+    }
+
+    #[test]
+    fn client_info_uses_first_forwarded_address() {
+        let mut headers = HeaderMap::new();
+        headers.insert("user-agent", HeaderValue::from_static("Mozilla/5.0"));
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("203.0.113.7, 10.0.0.2"),
+        );
+        headers.insert("x-real-ip", HeaderValue::from_static("10.0.0.2"));
+
+        let info = ClientInfo::from_headers(&headers);
+        assert_eq!(info.user_agent.as_deref(), Some("Mozilla/5.0"));
+        assert_eq!(info.ip_address.as_deref(), Some("203.0.113.7"));
+    }
+
+    #[test]
+    fn client_info_is_empty_without_headers() {
+        assert_eq!(
+            ClientInfo::from_headers(&HeaderMap::new()),
+            ClientInfo::default()
+        );
+    }
+
+    #[test]
+    fn display_name_rules_apply_outside_signup() {
+        assert!(validate_display_name("Blazer User").is_ok());
+        assert_eq!(
+            validate_display_name("   "),
+            Err("Name cannot be empty".to_string())
+        );
+        assert_eq!(
+            validate_display_name("B"),
+            Err("Name must be at least 2 characters".to_string())
+        );
+    }
+
+    #[test]
+    fn display_name_fits_the_users_table() {
+        assert!(validate_display_name(&"é".repeat(100)).is_ok());
+        assert_eq!(
+            validate_display_name(&"n".repeat(101)),
+            Err("Name cannot exceed 100 characters".to_string())
+        );
+    }
 
     #[test]
     fn parses_authentication_success_xml() {
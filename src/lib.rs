@@ -4,8 +4,13 @@ pub mod app;
 pub(crate) mod auth;
 #[cfg(feature = "ssr")]
 pub(crate) mod database;
+#[cfg(feature = "ssr")]
+pub(crate) mod mailer;
+pub(crate) mod markdown;
 pub(crate) mod models;
 #[cfg(feature = "ssr")]
+pub(crate) mod notifier;
+#[cfg(feature = "ssr")]
 pub(crate) mod profanity;
 pub(crate) mod routes;
 #[cfg(feature = "ssr")]
@@ -0,0 +1,322 @@
+//! Background delivery of the `email_notifications` queue.
+//!
+//! Request handlers only insert rows with [`EmailNotification::enqueue`]; the
+//! worker spawned from `setup::init_app` polls for due rows and sends them over
+//! SMTP, retrying failures with exponential backoff.
+
+use std::env;
+use std::time::Duration;
+
+use mail_send::mail_builder::MessageBuilder;
+use mail_send::{SmtpClient, SmtpClientBuilder};
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use crate::models::EmailNotification;
+
+/// Display name used in the From header of outgoing mail
+const SENDER_NAME: &str = "UAB IT Idea Board";
+/// How often the worker looks for due emails
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// Emails sent per poll
+const BATCH_SIZE: i64 = 20;
+/// Attempts before an email is marked failed
+const MAX_ATTEMPTS: i32 = 5;
+const BASE_RETRY_DELAY: Duration = Duration::from_secs(30);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60 * 60);
+/// Upper bound on one SMTP conversation
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How the connection to the SMTP server is secured.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SmtpSecurity {
+    /// Plain connection upgraded with STARTTLS (port 587)
+    StartTls,
+    /// TLS from the first byte (port 465)
+    ImplicitTls,
+    /// No TLS; only for local mail catchers and tests
+    Plain,
+}
+
+impl SmtpSecurity {
+    fn from_name(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "starttls" => Some(Self::StartTls),
+            "tls" | "implicit" => Some(Self::ImplicitTls),
+            "plain" | "none" => Some(Self::Plain),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct MailerConfig {
+    pub from_email: String,
+    pub password: Option<String>,
+    pub smtp_server: String,
+    pub port: u16,
+    pub security: SmtpSecurity,
+}
+
+impl MailerConfig {
+    /// Read `MAILER_EMAIL`, `MAILER_PASSWD` and `MAILER_SMTP_SERVER`, plus the
+    /// optional `MAILER_SMTP_PORT` (587) and `MAILER_SMTP_SECURITY`
+    /// (`starttls`, `tls` or `plain`; default `starttls`).
+    pub fn from_env() -> Result<Self, String> {
+        let var = |name: &str| env::var(name).map_err(|e| format!("{name} is not configured: {e}"));
+
+        let port = match env::var("MAILER_SMTP_PORT") {
+            Ok(value) => value
+                .parse()
+                .map_err(|e| format!("MAILER_SMTP_PORT is not a valid port: {e}"))?,
+            Err(_) => 587,
+        };
+        let security = match env::var("MAILER_SMTP_SECURITY") {
+            Ok(value) => SmtpSecurity::from_name(&value)
+                .ok_or_else(|| format!("MAILER_SMTP_SECURITY has unknown value {value:?}"))?,
+            Err(_) => SmtpSecurity::StartTls,
+        };
+
+        Ok(Self {
+            from_email: var("MAILER_EMAIL")?,
+            password: env::var("MAILER_PASSWD")
+                .ok()
+                .filter(|passwd| !passwd.is_empty()),
+            smtp_server: var("MAILER_SMTP_SERVER")?,
+            port,
+            security,
+        })
+    }
+}
+
+/// Start draining the queue in the background. Without SMTP settings the
+/// worker is not started and queued mail stays pending.
+pub fn spawn_email_worker() {
+    let config = match MailerConfig::from_env() {
+        Ok(config) => config,
+        Err(error) => {
+            tracing::warn!("Email worker not started: {error}");
+            return;
+        }
+    };
+
+    tokio::spawn(async move {
+        loop {
+            if let Err(error) = deliver_due(&config).await {
+                tracing::error!(?error, "failed to read the email queue");
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    });
+}
+
+/// Send every email that is currently due, returning how many were sent.
+async fn deliver_due(config: &MailerConfig) -> Result<usize, sqlx::Error> {
+    let mut sent = 0;
+    for email in EmailNotification::get_due(BATCH_SIZE).await? {
+        match send_email(config, &email.recipient_email, &email.subject, &email.body).await {
+            Ok(()) => {
+                EmailNotification::mark_sent(email.id).await?;
+                sent += 1;
+            }
+            Err(error) => {
+                let error = error.to_string();
+                let attempts = email.attempts + 1;
+                if attempts >= MAX_ATTEMPTS {
+                    tracing::error!(id = email.id, attempts, error, "giving up on email");
+                    EmailNotification::mark_failed(email.id, &error).await?;
+                } else {
+                    tracing::warn!(
+                        id = email.id,
+                        attempts,
+                        error,
+                        "email delivery failed, retrying"
+                    );
+                    let next_attempt_at = chrono::Utc::now()
+                        + chrono::Duration::from_std(retry_delay(attempts))
+                            .unwrap_or(chrono::Duration::hours(1));
+                    EmailNotification::schedule_retry(email.id, &error, next_attempt_at).await?;
+                }
+            }
+        }
+    }
+    Ok(sent)
+}
+
+/// Wait before the next attempt after `attempts` failures: doubles each time
+/// from [`BASE_RETRY_DELAY`], capped at [`MAX_RETRY_DELAY`].
+fn retry_delay(attempts: i32) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    BASE_RETRY_DELAY
+        .saturating_mul(2u32.pow(exponent))
+        .min(MAX_RETRY_DELAY)
+}
+
+/// Deliver one email over a fresh SMTP connection.
+pub(crate) async fn send_email(
+    config: &MailerConfig,
+    recipient: &str,
+    subject: &str,
+    body: &str,
+) -> Result<(), mail_send::Error> {
+    let message = MessageBuilder::new()
+        .from((SENDER_NAME, config.from_email.as_str()))
+        .to(recipient)
+        .subject(subject)
+        .text_body(body);
+
+    let mut builder = SmtpClientBuilder::new(config.smtp_server.as_str(), config.port)
+        .implicit_tls(config.security == SmtpSecurity::ImplicitTls)
+        .timeout(SMTP_TIMEOUT);
+    if let Some(password) = &config.password {
+        builder = builder.credentials((config.from_email.as_str(), password.as_str()));
+    }
+
+    match config.security {
+        SmtpSecurity::Plain => send_and_quit(builder.connect_plain().await?, message).await,
+        SmtpSecurity::StartTls | SmtpSecurity::ImplicitTls => {
+            send_and_quit(builder.connect().await?, message).await
+        }
+    }
+}
+
+async fn send_and_quit<T: AsyncRead + AsyncWrite + Unpin>(
+    mut client: SmtpClient<T>,
+    message: MessageBuilder<'_>,
+) -> Result<(), mail_send::Error> {
+    client.send(message).await?;
+    // The message is accepted at this point; a failed QUIT does not undo it
+    let _ = client.quit().await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    /// Accept one SMTP conversation on a local port and return the message
+    /// data it received. `reject_rcpt` makes the server refuse the recipient.
+    async fn fake_smtp_server(reject_rcpt: bool) -> (u16, tokio::task::JoinHandle<Option<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let (read, mut write) = socket.into_split();
+            let mut lines = BufReader::new(read).lines();
+            write.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+
+            let mut data: Option<String> = None;
+            let mut in_data = false;
+            while let Ok(Some(line)) = lines.next_line().await {
+                if in_data {
+                    if line == "." {
+                        in_data = false;
+                        write.write_all(b"250 2.0.0 queued\r\n").await.unwrap();
+                    } else {
+                        let data = data.get_or_insert_with(String::new);
+                        data.push_str(&line);
+                        data.push('\n');
+                    }
+                    continue;
+                }
+                let command = line.to_ascii_uppercase();
+                let reply: &[u8] = if command.starts_with("EHLO") {
+                    b"250 localhost\r\n"
+                } else if command.starts_with("RCPT") && reject_rcpt {
+                    b"550 5.1.1 no such user\r\n"
+                } else if command.starts_with("DATA") {
+                    in_data = true;
+                    b"354 go ahead\r\n"
+                } else if command.starts_with("QUIT") {
+                    write.write_all(b"221 bye\r\n").await.unwrap();
+                    break;
+                } else {
+                    b"250 OK\r\n"
+                };
+                write.write_all(reply).await.unwrap();
+            }
+            data
+        });
+        (port, handle)
+    }
+
+    fn plain_config(port: u16) -> MailerConfig {
+        MailerConfig {
+            from_email: "noreply@example.com".to_string(),
+            password: None,
+            smtp_server: "127.0.0.1".to_string(),
+            port,
+            security: SmtpSecurity::Plain,
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_message_to_smtp_server() {
+        let (port, server) = fake_smtp_server(false).await;
+
+        send_email(
+            &plain_config(port),
+            "someone@example.com",
+            "Queued subject",
+            "Queued body",
+        )
+        .await
+        .expect("email should be accepted");
+
+        let data = server.await.unwrap().expect("server should receive data");
+        assert!(data.contains("Subject: Queued subject"), "{data}");
+        assert!(data.contains("someone@example.com"), "{data}");
+        assert!(data.contains("Queued body"), "{data}");
+    }
+
+    #[tokio::test]
+    async fn reports_rejected_recipient() {
+        let (port, server) = fake_smtp_server(true).await;
+
+        let result = send_email(&plain_config(port), "nobody@example.com", "s", "b").await;
+
+        assert!(result.is_err());
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn reports_unreachable_server() {
+        // Bind and drop a listener to find a port nothing is listening on
+        let port = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        assert!(send_email(&plain_config(port), "a@example.com", "s", "b")
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_cap() {
+        assert_eq!(retry_delay(1), Duration::from_secs(30));
+        assert_eq!(retry_delay(2), Duration::from_secs(60));
+        assert_eq!(retry_delay(4), Duration::from_secs(240));
+        assert_eq!(retry_delay(20), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn security_names_are_case_insensitive() {
+        assert_eq!(
+            SmtpSecurity::from_name("STARTTLS"),
+            Some(SmtpSecurity::StartTls)
+        );
+        assert_eq!(
+            SmtpSecurity::from_name("tls"),
+            Some(SmtpSecurity::ImplicitTls)
+        );
+        assert_eq!(
+            SmtpSecurity::from_name(" plain "),
+            Some(SmtpSecurity::Plain)
+        );
+        assert_eq!(SmtpSecurity::from_name("ssl3"), None);
+    }
+}
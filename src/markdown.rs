@@ -0,0 +1,126 @@
+//! Markdown for staff-written text such as official responses.
+//!
+//! Raw HTML in the source is shown as text, images become their alt text and
+//! links may only point at http(s), mailto or this site, so the rendered
+//! HTML is safe to insert into the page.
+
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag, TagEnd};
+
+fn options() -> Options {
+    Options::ENABLE_STRIKETHROUGH
+}
+
+/// Whether a link target is allowed in rendered Markdown
+fn is_safe_url(url: &str) -> bool {
+    let url = url.trim_start();
+    if url.starts_with('/') || url.starts_with('#') {
+        return !url.starts_with("//");
+    }
+    let lower = url.to_ascii_lowercase();
+    lower.starts_with("https://") || lower.starts_with("http://") || lower.starts_with("mailto:")
+}
+
+/// Render Markdown to HTML that is safe to insert with `inner_html`.
+pub fn render(text: &str) -> String {
+    let events = Parser::new_ext(text, options()).filter_map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Some(Event::Text(raw)),
+        Event::Start(Tag::Image { .. }) | Event::End(TagEnd::Image) => None,
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Some(Event::Start(Tag::Link {
+            link_type,
+            dest_url: if is_safe_url(&dest_url) {
+                dest_url
+            } else {
+                CowStr::Borrowed("#")
+            },
+            title,
+            id,
+        })),
+        event => Some(event),
+    });
+
+    let mut out = String::new();
+    html::push_html(&mut out, events);
+    out
+}
+
+/// The text of some Markdown without its formatting, on one line and cut to
+/// at most `max_chars` characters at a word break, for previews.
+#[cfg(feature = "ssr")]
+pub fn excerpt(text: &str, max_chars: usize) -> String {
+    let mut plain = String::new();
+    for event in Parser::new_ext(text, options()) {
+        match event {
+            Event::Text(text) | Event::Code(text) | Event::Html(text) | Event::InlineHtml(text) => {
+                plain.push_str(&text)
+            }
+            Event::SoftBreak | Event::HardBreak | Event::End(_) => plain.push(' '),
+            _ => {}
+        }
+    }
+
+    let plain = plain.split_whitespace().collect::<Vec<_>>().join(" ");
+    if plain.chars().count() <= max_chars {
+        return plain;
+    }
+    let cut: String = plain.chars().take(max_chars.saturating_sub(1)).collect();
+    // End on a whole word unless the first word alone is too long
+    let cut = match cut.rfind(' ') {
+        Some(_) if plain[cut.len()..].starts_with(' ') => cut.as_str(),
+        Some(space) => &cut[..space],
+        None => cut.as_str(),
+    };
+    format!("{}…", cut.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{excerpt, render};
+
+    #[test]
+    fn markdown_renders_basic_formatting() {
+        assert_eq!(
+            render("We are **piloting** this in _May_.\n\n- one\n- two"),
+            "<p>We are <strong>piloting</strong> this in <em>May</em>.</p>\n<ul>\n<li>one</li>\n<li>two</li>\n</ul>\n"
+        );
+    }
+
+    #[test]
+    fn raw_html_is_shown_as_text() {
+        let html = render("<script>alert(1)</script> and <b onclick=x>bold</b>");
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("<b "));
+        assert!(html.contains("&lt;script&gt;"));
+    }
+
+    #[test]
+    fn unsafe_links_and_images_are_neutralised() {
+        assert_eq!(
+            render("[click](javascript:alert(1))"),
+            "<p><a href=\"#\">click</a></p>\n"
+        );
+        assert_eq!(
+            render("[docs](https://uab.edu/it) [home](/ideas/2)"),
+            "<p><a href=\"https://uab.edu/it\">docs</a> <a href=\"/ideas/2\">home</a></p>\n"
+        );
+        assert_eq!(
+            render("![tracker](https://x.test/p.gif)"),
+            "<p>tracker</p>\n"
+        );
+    }
+
+    #[test]
+    fn excerpts_drop_formatting_and_are_cut() {
+        assert_eq!(
+            excerpt("# Update\n\nWe are **piloting** this.\n\n- soon", 100),
+            "Update We are piloting this. soon"
+        );
+        assert_eq!(excerpt("one two three four", 10), "one two…");
+        assert_eq!(excerpt("one two three four", 9), "one two…");
+        assert_eq!(excerpt("supercalifragilistic", 6), "super…");
+    }
+}
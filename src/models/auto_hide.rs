@@ -0,0 +1,104 @@
+pub const DEFAULT_FLAG_HIDE_THRESHOLD: f64 = 3.0;
+/// Votes younger than this count as recent for the voter-percentage rule
+pub const RECENT_VOTE_DAYS: i64 = 30;
+/// The voter-percentage rule needs at least this many recent voters, so one
+/// flag on a quiet idea is not enough to hide it
+pub const MIN_RECENT_VOTERS: i64 = 10;
+
+/// When flagged content is hidden until a moderator reviews it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AutoHidePolicy {
+    /// Weighted flags that hide an item; 0 turns the rule off
+    pub threshold: f64,
+    /// Weighted flags, as a percentage of an idea's recent voters, that hide
+    /// it; 0 turns the rule off
+    pub voter_percent: u32,
+}
+
+impl Default for AutoHidePolicy {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_FLAG_HIDE_THRESHOLD,
+            voter_percent: 0,
+        }
+    }
+}
+
+impl AutoHidePolicy {
+    /// Whether an item with `flag_weight` worth of flags should be hidden.
+    /// `recent_voters` is zero for comments.
+    pub fn should_hide(&self, flag_weight: f64, recent_voters: i64) -> bool {
+        let by_count = self.threshold > 0.0 && flag_weight >= self.threshold;
+        let by_voters = self.voter_percent > 0
+            && recent_voters >= MIN_RECENT_VOTERS
+            && flag_weight * 100.0 >= f64::from(self.voter_percent) * recent_voters as f64;
+        by_count || by_voters
+    }
+
+    /// Start of the window in which votes count as recent
+    pub fn recent_votes_since(now: chrono::DateTime<chrono::Utc>) -> chrono::DateTime<chrono::Utc> {
+        now - chrono::Duration::days(RECENT_VOTE_DAYS)
+    }
+
+    /// Read the policy from `FLAG_HIDE_THRESHOLD` and `FLAG_HIDE_VOTER_PERCENT`.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            threshold: std::env::var("FLAG_HIDE_THRESHOLD")
+                .ok()
+                .and_then(|value| value.parse::<f64>().ok())
+                .filter(|threshold| *threshold >= 0.0)
+                .unwrap_or(defaults.threshold),
+            voter_percent: std::env::var("FLAG_HIDE_VOTER_PERCENT")
+                .ok()
+                .and_then(|value| value.parse::<u32>().ok())
+                .unwrap_or(defaults.voter_percent),
+        }
+    }
+}
+
+/// How much one flag counts, given how many of the flagger's earlier flags a
+/// moderator dismissed: a full flag at first, then a half, a third, and so on.
+pub fn flag_weight(dismissed_flags: i32) -> f64 {
+    1.0 / f64::from(1 + dismissed_flags.max(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{flag_weight, AutoHidePolicy};
+
+    #[test]
+    fn hides_at_threshold() {
+        let policy = AutoHidePolicy::default();
+        assert!(!policy.should_hide(2.0, 0));
+        assert!(policy.should_hide(3.0, 0));
+    }
+
+    #[test]
+    fn zero_threshold_disables_count_rule() {
+        let policy = AutoHidePolicy {
+            threshold: 0.0,
+            voter_percent: 0,
+        };
+        assert!(!policy.should_hide(50.0, 0));
+    }
+
+    #[test]
+    fn voter_percentage_needs_enough_recent_voters() {
+        let policy = AutoHidePolicy {
+            threshold: 0.0,
+            voter_percent: 20,
+        };
+        assert!(!policy.should_hide(1.0, 5));
+        assert!(!policy.should_hide(3.0, 20));
+        assert!(policy.should_hide(4.0, 20));
+    }
+
+    #[test]
+    fn dismissed_flags_reduce_weight() {
+        assert_eq!(flag_weight(0), 1.0);
+        assert_eq!(flag_weight(1), 0.5);
+        assert_eq!(flag_weight(3), 0.25);
+        assert_eq!(flag_weight(-1), 1.0);
+    }
+}
@@ -0,0 +1,261 @@
+use serde::{Deserialize, Serialize};
+
+/// Longest category name, in characters
+pub const MAX_CATEGORY_NAME_CHARS: usize = 60;
+
+/// A service area ideas are filed under, such as networking or research
+/// computing.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct Category {
+    pub id: i32,
+    pub name: String,
+    /// Used in the board's URL
+    pub slug: String,
+    pub description: String,
+    /// Keeps its ideas but takes no new ones
+    pub is_archived: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A category with the number of ideas filed under it (admin view).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CategoryWithCount {
+    pub category: Category,
+    pub idea_count: i64,
+}
+
+/// A moderator assigned to a category.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CategoryModerator {
+    pub category_id: i32,
+    pub user_id: i32,
+    pub user_name: String,
+}
+
+/// Whether a moderator assigned to `scope` may moderate an idea in
+/// `category_id`. An empty scope covers every category.
+pub fn scope_covers(scope: &[i32], category_id: i32) -> bool {
+    scope.is_empty() || scope.contains(&category_id)
+}
+
+#[cfg(feature = "ssr")]
+impl Category {
+    /// Every category, archived ones included, by name
+    pub async fn get_all() -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Category,
+            r#"
+            SELECT id, name, slug, description, is_archived, created_at
+            FROM categories
+            ORDER BY lower(name), id
+            "#
+        )
+        .fetch_all(crate::database::get_db())
+        .await
+    }
+
+    pub async fn get_by_id(id: i32) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Category,
+            r#"
+            SELECT id, name, slug, description, is_archived, created_at
+            FROM categories
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(crate::database::get_db())
+        .await
+    }
+
+    /// Every category with its idea count (admin view)
+    pub async fn get_all_with_counts() -> Result<Vec<CategoryWithCount>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT c.id, c.name, c.slug, c.description, c.is_archived, c.created_at,
+                   COUNT(i.id) AS "idea_count!"
+            FROM categories c
+            LEFT JOIN ideas i ON i.category_id = c.id
+            GROUP BY c.id
+            ORDER BY lower(c.name), c.id
+            "#
+        )
+        .fetch_all(crate::database::get_db())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| CategoryWithCount {
+                category: Category {
+                    id: r.id,
+                    name: r.name,
+                    slug: r.slug,
+                    description: r.description,
+                    is_archived: r.is_archived,
+                    created_at: r.created_at,
+                },
+                idea_count: r.idea_count,
+            })
+            .collect())
+    }
+
+    pub async fn create(name: &str, slug: &str, description: &str) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Category,
+            r#"
+            INSERT INTO categories (name, slug, description)
+            VALUES ($1, $2, $3)
+            RETURNING id, name, slug, description, is_archived, created_at
+            "#,
+            name,
+            slug,
+            description
+        )
+        .fetch_one(crate::database::get_db())
+        .await
+    }
+
+    /// Change a category's name, slug and description, returning it as it
+    /// was, or `None` if it does not exist
+    pub async fn update(
+        id: i32,
+        name: &str,
+        slug: &str,
+        description: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Category,
+            r#"
+            UPDATE categories c
+            SET name = $1, slug = $2, description = $3
+            FROM categories before
+            WHERE c.id = $4 AND before.id = c.id
+            RETURNING before.id, before.name, before.slug, before.description,
+                      before.is_archived, before.created_at
+            "#,
+            name,
+            slug,
+            description,
+            id
+        )
+        .fetch_optional(crate::database::get_db())
+        .await
+    }
+
+    /// Archive or restore a category, returning it if it exists
+    pub async fn set_archived(id: i32, archived: bool) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Category,
+            r#"
+            UPDATE categories SET is_archived = $1
+            WHERE id = $2
+            RETURNING id, name, slug, description, is_archived, created_at
+            "#,
+            archived,
+            id
+        )
+        .fetch_optional(crate::database::get_db())
+        .await
+    }
+
+    /// Every moderator assignment, by category and then moderator name
+    pub async fn get_moderators() -> Result<Vec<CategoryModerator>, sqlx::Error> {
+        sqlx::query_as!(
+            CategoryModerator,
+            r#"
+            SELECT cm.category_id, cm.user_id, u.name AS user_name
+            FROM category_moderators cm
+            INNER JOIN users u ON u.id = cm.user_id
+            ORDER BY cm.category_id, lower(u.name)
+            "#
+        )
+        .fetch_all(crate::database::get_db())
+        .await
+    }
+
+    /// Assign a moderator to a category; false if they already were
+    pub async fn add_moderator(category_id: i32, user_id: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO category_moderators (category_id, user_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            category_id,
+            user_id
+        )
+        .execute(crate::database::get_db())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Remove a moderator from a category; false if they were not assigned.
+    /// Their last category is never removed, since an empty scope would let
+    /// them moderate every category.
+    pub async fn remove_moderator(category_id: i32, user_id: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM category_moderators
+            WHERE category_id = $1 AND user_id = $2
+              AND EXISTS (
+                  SELECT 1 FROM category_moderators
+                  WHERE user_id = $2 AND category_id <> $1
+              )
+            "#,
+            category_id,
+            user_id
+        )
+        .execute(crate::database::get_db())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// The category of an idea, or `None` if there is no such idea
+    pub async fn of_idea(idea_id: i32) -> Result<Option<i32>, sqlx::Error> {
+        sqlx::query_scalar!("SELECT category_id FROM ideas WHERE id = $1", idea_id)
+            .fetch_optional(crate::database::get_db())
+            .await
+    }
+
+    /// The category of the idea a comment is on
+    pub async fn of_comment(comment_id: i32) -> Result<Option<i32>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT i.category_id
+            FROM comments c
+            INNER JOIN ideas i ON i.id = c.idea_id
+            WHERE c.id = $1
+            "#,
+            comment_id
+        )
+        .fetch_optional(crate::database::get_db())
+        .await
+    }
+
+    /// The categories a moderator is limited to; empty for every category
+    pub async fn moderator_scope(user_id: i32) -> Result<Vec<i32>, sqlx::Error> {
+        sqlx::query_scalar!(
+            "SELECT category_id FROM category_moderators WHERE user_id = $1 ORDER BY category_id",
+            user_id
+        )
+        .fetch_all(crate::database::get_db())
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::scope_covers;
+
+    #[test]
+    fn unassigned_moderators_cover_every_category() {
+        assert!(scope_covers(&[], 3));
+    }
+
+    #[test]
+    fn assigned_moderators_are_limited_to_their_categories() {
+        assert!(scope_covers(&[1, 3], 3));
+        assert!(!scope_covers(&[1, 3], 2));
+    }
+}
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "ssr")]
+use super::{CommentRevision, EditWindow};
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
 pub struct Comment {
@@ -10,6 +13,46 @@ pub struct Comment {
     pub is_pinned: bool,
     pub is_deleted: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    pub edited_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub moderation_reason: Option<String>,
+    /// Hidden automatically after enough flags, until a moderator reviews it
+    pub is_held: bool,
+    /// The top-level comment this replies to
+    pub parent_id: Option<i32>,
+    /// Users who marked the comment helpful, kept by a trigger on
+    /// `comment_votes`
+    pub helpful_count: i32,
+}
+
+/// Order of the comments on an idea. Pinned comments always come first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommentSort {
+    #[default]
+    Oldest,
+    /// Most helpful first
+    Top,
+}
+
+impl CommentSort {
+    pub const ALL: [CommentSort; 2] = [Self::Oldest, Self::Top];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Oldest => "Oldest",
+            Self::Top => "Top",
+        }
+    }
+
+    /// `ORDER BY` terms for comments aliased `c`
+    #[cfg(feature = "ssr")]
+    fn order_by(self) -> &'static str {
+        match self {
+            Self::Oldest => "(CASE WHEN c.is_pinned THEN 0 ELSE 1 END), c.created_at ASC, c.id ASC",
+            Self::Top => {
+                "(CASE WHEN c.is_pinned THEN 0 ELSE 1 END), c.helpful_count DESC, c.created_at ASC, c.id ASC"
+            }
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -20,50 +63,132 @@ pub struct CommentWithAuthor {
     pub is_idea_author: bool,
 }
 
+/// A top-level comment with its replies, in the order they are shown.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CommentThread {
+    pub comment: CommentWithAuthor,
+    pub replies: Vec<CommentWithAuthor>,
+}
+
+/// Group comments into threads, keeping their order. Replies whose parent
+/// is not in the list are shown as top-level comments.
+pub fn thread_comments(comments: Vec<CommentWithAuthor>) -> Vec<CommentThread> {
+    let top_level_ids: Vec<i32> = comments
+        .iter()
+        .filter(|cwa| cwa.comment.parent_id.is_none())
+        .map(|cwa| cwa.comment.id)
+        .collect();
+    let (replies, top_level): (Vec<_>, Vec<_>) = comments.into_iter().partition(|cwa| {
+        cwa.comment
+            .parent_id
+            .is_some_and(|parent_id| top_level_ids.contains(&parent_id))
+    });
+
+    let mut threads: Vec<CommentThread> = top_level
+        .into_iter()
+        .map(|comment| CommentThread {
+            comment,
+            replies: Vec::new(),
+        })
+        .collect();
+    for reply in replies {
+        if let Some(thread) = threads
+            .iter_mut()
+            .find(|thread| Some(thread.comment.comment.id) == reply.comment.parent_id)
+        {
+            thread.replies.push(reply);
+        }
+    }
+    threads
+}
+
+impl CommentWithAuthor {
+    /// Whether this stands in for a removed comment that still has replies
+    pub fn is_placeholder(&self) -> bool {
+        self.comment.is_deleted || self.comment.is_held
+    }
+
+    /// Strip a removed comment down to its place in the thread, so nothing
+    /// it said or who wrote it reaches other users.
+    #[cfg(feature = "ssr")]
+    fn into_placeholder(mut self) -> Self {
+        self.comment.user_id = 0;
+        self.comment.content = String::new();
+        self.comment.edited_at = None;
+        self.comment.moderation_reason = None;
+        self.author_name = String::new();
+        self.author_email = String::new();
+        self.is_idea_author = false;
+        self
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CommentWithIdea {
+    pub comment: Comment,
+    pub idea_title: String,
+}
+
 impl Comment {
+    /// Add a comment, or a reply when `parent_id` names a top-level comment
+    /// on the same idea
     #[cfg(feature = "ssr")]
-    pub async fn create(user_id: i32, idea_id: i32, content: String) -> Result<Self, sqlx::Error> {
+    pub async fn create(
+        user_id: i32,
+        idea_id: i32,
+        content: String,
+        parent_id: Option<i32>,
+    ) -> Result<Self, sqlx::Error> {
         sqlx::query_as!(
             Comment,
             r#"
-            INSERT INTO comments (user_id, idea_id, content)
-            VALUES ($1, $2, $3)
-            RETURNING id, idea_id, user_id, content, is_pinned, is_deleted, created_at
+            INSERT INTO comments (user_id, idea_id, content, parent_id)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, idea_id, user_id, content, is_pinned, is_deleted, created_at, edited_at,
+                      moderation_reason, is_held, parent_id, helpful_count
             "#,
             user_id,
             idea_id,
-            content
+            content,
+            parent_id
         )
         .fetch_one(crate::database::get_db())
         .await
     }
 
+    /// An idea's comments in `sort` order, pinned first. Without
+    /// `include_deleted`, removed comments only appear as placeholders for
+    /// the visible replies under them.
     #[cfg(feature = "ssr")]
     pub async fn get_by_idea_id(
         idea_id: i32,
         include_deleted: bool,
+        sort: CommentSort,
     ) -> Result<Vec<CommentWithAuthor>, sqlx::Error> {
         let delete_filter = if include_deleted {
             ""
         } else {
-            "AND c.is_deleted = false"
+            r#"AND ((c.is_deleted = false AND c.is_held = false) OR EXISTS (
+                SELECT 1 FROM comments r
+                WHERE r.parent_id = c.id AND r.is_deleted = false AND r.is_held = false
+            ))"#
         };
 
         let query_str = format!(
             r#"
             SELECT
-                c.id, c.idea_id, c.user_id, c.content, c.is_pinned, c.is_deleted, c.created_at,
+                c.id, c.idea_id, c.user_id, c.content, c.is_pinned, c.is_deleted, c.created_at, c.edited_at,
+                c.moderation_reason, c.is_held, c.parent_id, c.helpful_count,
                 u.name as author_name, u.email as author_email,
                 (i.user_id = c.user_id) as is_idea_author
             FROM comments c
             INNER JOIN users u ON c.user_id = u.id
             INNER JOIN ideas i ON c.idea_id = i.id
             WHERE c.idea_id = $1 {}
-            ORDER BY
-                (CASE WHEN c.is_pinned THEN 0 ELSE 1 END),
-                c.created_at ASC
+            ORDER BY {}
             "#,
-            delete_filter
+            delete_filter,
+            sort.order_by()
         );
 
         let rows = sqlx::query(&query_str)
@@ -75,7 +200,7 @@ impl Comment {
             .into_iter()
             .map(|row| {
                 use sqlx::Row;
-                CommentWithAuthor {
+                let cwa = CommentWithAuthor {
                     comment: Comment {
                         id: row.get("id"),
                         idea_id: row.get("idea_id"),
@@ -84,44 +209,147 @@ impl Comment {
                         is_pinned: row.get("is_pinned"),
                         is_deleted: row.get("is_deleted"),
                         created_at: row.get("created_at"),
+                        edited_at: row.get("edited_at"),
+                        moderation_reason: row.get("moderation_reason"),
+                        is_held: row.get("is_held"),
+                        parent_id: row.get("parent_id"),
+                        helpful_count: row.get("helpful_count"),
                     },
                     author_name: row.get("author_name"),
                     author_email: row.get("author_email"),
                     is_idea_author: row.get("is_idea_author"),
+                };
+                if !include_deleted && cwa.is_placeholder() {
+                    cwa.into_placeholder()
+                } else {
+                    cwa
                 }
             })
             .collect())
     }
 
+    #[cfg(feature = "ssr")]
+    pub async fn get_by_id(id: i32) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Comment,
+            r#"
+            SELECT id, idea_id, user_id, content, is_pinned, is_deleted, created_at, edited_at,
+                   moderation_reason, is_held, parent_id, helpful_count
+            FROM comments
+            WHERE id = $1 AND is_deleted = false
+            "#,
+            id
+        )
+        .fetch_optional(crate::database::get_db())
+        .await
+    }
+
+    /// Get a comment, including a deleted one, with the title of its idea and
+    /// the author's name (for the flags queue)
+    #[cfg(feature = "ssr")]
+    pub async fn get_with_context(
+        id: i32,
+    ) -> Result<Option<(CommentWithIdea, String)>, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                c.id, c.idea_id, c.user_id, c.content, c.is_pinned, c.is_deleted, c.created_at, c.edited_at,
+                c.moderation_reason, c.is_held, c.parent_id, c.helpful_count,
+                i.title as idea_title, u.name as author_name
+            FROM comments c
+            INNER JOIN ideas i ON c.idea_id = i.id
+            INNER JOIN users u ON c.user_id = u.id
+            WHERE c.id = $1
+            "#,
+            id
+        )
+        .fetch_optional(crate::database::get_db())
+        .await?;
+
+        Ok(row.map(|row| {
+            (
+                CommentWithIdea {
+                    comment: Comment {
+                        id: row.id,
+                        idea_id: row.idea_id,
+                        user_id: row.user_id,
+                        content: row.content,
+                        is_pinned: row.is_pinned,
+                        is_deleted: row.is_deleted,
+                        created_at: row.created_at,
+                        edited_at: row.edited_at,
+                        moderation_reason: row.moderation_reason,
+                        is_held: row.is_held,
+                        parent_id: row.parent_id,
+                        helpful_count: row.helpful_count,
+                    },
+                    idea_title: row.idea_title,
+                },
+                row.author_name,
+            )
+        }))
+    }
+
+    /// Update comment content (author only), keeping the previous version.
+    /// Returns false unless the comment is inside `window` and has no visible
+    /// replies or helpful votes yet, checked as part of the update.
     #[cfg(feature = "ssr")]
     pub async fn update_content(
         id: i32,
         user_id: i32,
+        window: EditWindow,
         content: String,
     ) -> Result<bool, sqlx::Error> {
+        let mut tx = crate::database::get_db().begin().await?;
+        CommentRevision::record(&mut tx, id, user_id).await?;
         let result = sqlx::query!(
-            "UPDATE comments SET content = $1 WHERE id = $2 AND user_id = $3",
+            r#"
+            UPDATE comments SET content = $1, edited_at = NOW()
+            WHERE id = $2 AND user_id = $3
+              AND NOW() < created_at + INTERVAL '1 minute' * $4::BIGINT
+              AND helpful_count = 0
+              AND NOT EXISTS (
+                  SELECT 1 FROM comments r
+                  WHERE r.parent_id = comments.id AND r.is_deleted = false AND r.is_held = false
+              )
+            "#,
             content,
             id,
-            user_id
+            user_id,
+            window.minutes
         )
-        .execute(crate::database::get_db())
+        .execute(&mut *tx)
         .await?;
 
-        Ok(result.rows_affected() > 0)
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+        tx.commit().await?;
+        Ok(true)
     }
 
+    /// Update comment content (moderator/admin), keeping the previous version
     #[cfg(feature = "ssr")]
-    pub async fn update_content_mod(id: i32, content: String) -> Result<bool, sqlx::Error> {
+    pub async fn update_content_mod(
+        id: i32,
+        editor_id: i32,
+        content: String,
+    ) -> Result<bool, sqlx::Error> {
+        let mut tx = crate::database::get_db().begin().await?;
+        CommentRevision::record(&mut tx, id, editor_id).await?;
         let result = sqlx::query!(
-            "UPDATE comments SET content = $1 WHERE id = $2",
+            "UPDATE comments SET content = $1, edited_at = NOW() WHERE id = $2",
             content,
             id
         )
-        .execute(crate::database::get_db())
+        .execute(&mut *tx)
         .await?;
 
-        Ok(result.rows_affected() > 0)
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+        tx.commit().await?;
+        Ok(true)
     }
 
     #[cfg(feature = "ssr")]
@@ -141,57 +369,200 @@ impl Comment {
         Ok(result.is_pinned)
     }
 
+    /// Hide a comment with the moderator's reason (moderator only)
     #[cfg(feature = "ssr")]
-    pub async fn soft_delete(id: i32) -> Result<(), sqlx::Error> {
-        sqlx::query!("UPDATE comments SET is_deleted = true WHERE id = $1", id)
-            .execute(crate::database::get_db())
-            .await?;
+    pub async fn soft_delete(id: i32, reason: String) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE comments SET is_deleted = true, moderation_reason = $1 WHERE id = $2",
+            reason,
+            id
+        )
+        .execute(crate::database::get_db())
+        .await?;
         Ok(())
     }
 
+    /// Hold a comment for review after enough flags, returning whether it was
+    /// visible until now
     #[cfg(feature = "ssr")]
-    pub async fn hard_delete(id: i32) -> Result<(), sqlx::Error> {
-        sqlx::query!("DELETE FROM comments WHERE id = $1", id)
-            .execute(crate::database::get_db())
-            .await?;
-        Ok(())
+    pub async fn hold(id: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE comments SET is_held = true WHERE id = $1 AND is_held = false AND is_deleted = false",
+            id
+        )
+        .execute(crate::database::get_db())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
     }
 
+    /// Show a held comment again, returning whether it was held
     #[cfg(feature = "ssr")]
-    pub async fn count_by_idea_id(idea_id: i32) -> Result<i64, sqlx::Error> {
+    pub async fn release_hold(id: i32) -> Result<bool, sqlx::Error> {
         let result = sqlx::query!(
-            "SELECT COUNT(*) as count FROM comments WHERE idea_id = $1",
-            idea_id
+            "UPDATE comments SET is_held = false WHERE id = $1 AND is_held = true",
+            id
+        )
+        .execute(crate::database::get_db())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Whether a comment has visible replies or anyone marked it helpful
+    #[cfg(feature = "ssr")]
+    pub async fn has_engagement(id: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            SELECT (
+                c.helpful_count > 0
+                OR EXISTS (
+                    SELECT 1 FROM comments r
+                    WHERE r.parent_id = c.id AND r.is_deleted = false AND r.is_held = false
+                )
+            ) as "engaged!"
+            FROM comments c
+            WHERE c.id = $1
+            "#,
+            id
         )
         .fetch_one(crate::database::get_db())
         .await?;
-        Ok(result.count.unwrap_or(0))
+
+        Ok(result.engaged)
     }
 
+    /// Withdraw a comment (author only), under the same conditions as
+    /// [`Comment::update_content`]
     #[cfg(feature = "ssr")]
-    pub async fn count_all_grouped() -> Result<Vec<(i32, i64)>, sqlx::Error> {
-        let rows = sqlx::query!("SELECT idea_id, COUNT(*) as count FROM comments GROUP BY idea_id")
-            .fetch_all(crate::database::get_db())
-            .await?;
-        Ok(rows
-            .into_iter()
-            .map(|r| (r.idea_id, r.count.unwrap_or(0)))
-            .collect())
+    pub async fn soft_delete_own(
+        id: i32,
+        user_id: i32,
+        window: EditWindow,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE comments SET is_deleted = true
+            WHERE id = $1 AND user_id = $2
+              AND NOW() < created_at + INTERVAL '1 minute' * $3::BIGINT
+              AND helpful_count = 0
+              AND NOT EXISTS (
+                  SELECT 1 FROM comments r
+                  WHERE r.parent_id = comments.id AND r.is_deleted = false AND r.is_held = false
+              )
+            "#,
+            id,
+            user_id,
+            window.minutes
+        )
+        .execute(crate::database::get_db())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
     }
 
+    /// Get a user's visible comments, plus those a moderator hid, with the title
+    /// of the idea each belongs to (for profile page)
     #[cfg(feature = "ssr")]
-    pub async fn get_by_user(user_id: i32) -> Result<Vec<Self>, sqlx::Error> {
-        sqlx::query_as!(
-            Comment,
+    pub async fn get_by_user(user_id: i32) -> Result<Vec<CommentWithIdea>, sqlx::Error> {
+        let rows = sqlx::query!(
             r#"
-            SELECT id, idea_id, user_id, content, is_pinned, is_deleted, created_at
-            FROM comments
-            WHERE user_id = $1
-            ORDER BY created_at DESC
+            SELECT
+                c.id, c.idea_id, c.user_id, c.content, c.is_pinned, c.is_deleted, c.created_at, c.edited_at,
+                c.moderation_reason, c.is_held, c.parent_id, c.helpful_count,
+                i.title as idea_title
+            FROM comments c
+            INNER JOIN ideas i ON c.idea_id = i.id
+            WHERE c.user_id = $1 AND (c.is_deleted = false OR c.moderation_reason IS NOT NULL)
+            ORDER BY c.created_at DESC
             "#,
             user_id
         )
         .fetch_all(crate::database::get_db())
-        .await
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| CommentWithIdea {
+                comment: Comment {
+                    id: row.id,
+                    idea_id: row.idea_id,
+                    user_id: row.user_id,
+                    content: row.content,
+                    is_pinned: row.is_pinned,
+                    is_deleted: row.is_deleted,
+                    created_at: row.created_at,
+                    edited_at: row.edited_at,
+                    moderation_reason: row.moderation_reason,
+                    is_held: row.is_held,
+                    parent_id: row.parent_id,
+                    helpful_count: row.helpful_count,
+                },
+                idea_title: row.idea_title,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{thread_comments, Comment, CommentWithAuthor};
+
+    fn comment(id: i32, parent_id: Option<i32>) -> CommentWithAuthor {
+        CommentWithAuthor {
+            comment: Comment {
+                id,
+                idea_id: 1,
+                user_id: 1,
+                content: format!("Comment {id}"),
+                is_pinned: false,
+                is_deleted: false,
+                created_at: chrono::Utc::now(),
+                edited_at: None,
+                moderation_reason: None,
+                is_held: false,
+                parent_id,
+                helpful_count: 0,
+            },
+            author_name: "Ada".to_string(),
+            author_email: "ada@example.com".to_string(),
+            is_idea_author: false,
+        }
+    }
+
+    #[test]
+    fn replies_are_grouped_under_their_parent_in_order() {
+        let threads = thread_comments(vec![
+            comment(1, None),
+            comment(2, None),
+            comment(3, Some(1)),
+            comment(4, Some(2)),
+            comment(5, Some(1)),
+        ]);
+        let shape: Vec<(i32, Vec<i32>)> = threads
+            .iter()
+            .map(|thread| {
+                (
+                    thread.comment.comment.id,
+                    thread
+                        .replies
+                        .iter()
+                        .map(|reply| reply.comment.id)
+                        .collect(),
+                )
+            })
+            .collect();
+        assert_eq!(shape, vec![(1, vec![3, 5]), (2, vec![4])]);
+    }
+
+    #[test]
+    fn replies_without_their_parent_become_top_level() {
+        let threads = thread_comments(vec![comment(3, Some(1)), comment(4, None)]);
+        let ids: Vec<i32> = threads
+            .iter()
+            .map(|thread| thread.comment.comment.id)
+            .collect();
+        assert_eq!(ids, vec![3, 4]);
+        assert!(threads.iter().all(|thread| thread.replies.is_empty()));
     }
 }
@@ -0,0 +1,59 @@
+#[cfg(feature = "ssr")]
+mod inner {
+    /// A user's "helpful" vote on a comment. `comments.helpful_count` follows
+    /// these rows through triggers.
+    pub struct CommentVote;
+
+    impl CommentVote {
+        /// Add the user's vote, or take it back if they already voted.
+        /// Returns whether the comment is now voted for.
+        pub async fn toggle(user_id: i32, comment_id: i32) -> Result<bool, sqlx::Error> {
+            let added = sqlx::query!(
+                r#"
+                INSERT INTO comment_votes (user_id, comment_id) VALUES ($1, $2)
+                ON CONFLICT (user_id, comment_id) DO NOTHING
+                "#,
+                user_id,
+                comment_id
+            )
+            .execute(crate::database::get_db())
+            .await?
+            .rows_affected()
+                > 0;
+            if added {
+                return Ok(true);
+            }
+
+            sqlx::query!(
+                "DELETE FROM comment_votes WHERE user_id = $1 AND comment_id = $2",
+                user_id,
+                comment_id
+            )
+            .execute(crate::database::get_db())
+            .await?;
+            Ok(false)
+        }
+
+        /// Ids of the comments on an idea the user voted for
+        pub async fn get_voted_comment_ids(
+            user_id: i32,
+            idea_id: i32,
+        ) -> Result<Vec<i32>, sqlx::Error> {
+            sqlx::query_scalar!(
+                r#"
+                SELECT cv.comment_id
+                FROM comment_votes cv
+                INNER JOIN comments c ON c.id = cv.comment_id
+                WHERE cv.user_id = $1 AND c.idea_id = $2
+                "#,
+                user_id,
+                idea_id
+            )
+            .fetch_all(crate::database::get_db())
+            .await
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub use inner::CommentVote;
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_EDIT_WINDOW_MINUTES: i64 = 30;
+
+/// How long authors may edit or withdraw their own ideas and comments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditWindow {
+    pub minutes: i64,
+}
+
+impl Default for EditWindow {
+    fn default() -> Self {
+        Self {
+            minutes: DEFAULT_EDIT_WINDOW_MINUTES,
+        }
+    }
+}
+
+impl EditWindow {
+    /// Whether something created at `created_at` can still be changed by its
+    /// author. The window closes early once others have engaged with it
+    /// (for ideas: the first comment or vote).
+    pub fn is_open(
+        &self,
+        created_at: chrono::DateTime<chrono::Utc>,
+        now: chrono::DateTime<chrono::Utc>,
+        has_engagement: bool,
+    ) -> bool {
+        !has_engagement && now < created_at + chrono::Duration::minutes(self.minutes)
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl EditWindow {
+    /// Read the window length from `EDIT_WINDOW_MINUTES`.
+    pub fn from_env() -> Self {
+        std::env::var("EDIT_WINDOW_MINUTES")
+            .ok()
+            .and_then(|value| value.parse::<i64>().ok())
+            .filter(|minutes| *minutes >= 0)
+            .map(|minutes| Self { minutes })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EditWindow;
+
+    fn minutes_ago(minutes: i64) -> chrono::DateTime<chrono::Utc> {
+        chrono::Utc::now() - chrono::Duration::minutes(minutes)
+    }
+
+    #[test]
+    fn open_within_window_without_engagement() {
+        let window = EditWindow::default();
+        assert!(window.is_open(minutes_ago(5), chrono::Utc::now(), false));
+    }
+
+    #[test]
+    fn closes_after_window_elapses() {
+        let window = EditWindow { minutes: 30 };
+        assert!(!window.is_open(minutes_ago(31), chrono::Utc::now(), false));
+    }
+
+    #[test]
+    fn closes_early_once_engaged() {
+        let window = EditWindow::default();
+        assert!(!window.is_open(minutes_ago(1), chrono::Utc::now(), true));
+    }
+
+    #[test]
+    fn zero_minute_window_disables_author_edits() {
+        let window = EditWindow { minutes: 0 };
+        let now = chrono::Utc::now();
+        assert!(!window.is_open(now, now, false));
+    }
+}
@@ -0,0 +1,151 @@
+#[cfg(feature = "ssr")]
+mod inner {
+    /// Values stored in `email_notifications.status`
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum EmailStatus {
+        Pending = 0,
+        Sent = 1,
+        Failed = 2,
+    }
+
+    impl EmailStatus {
+        fn as_i16(self) -> i16 {
+            self as i16
+        }
+    }
+
+    /// An outgoing email waiting in the queue.
+    #[derive(Debug, Clone, sqlx::FromRow)]
+    pub struct EmailNotification {
+        pub id: i32,
+        pub recipient_email: String,
+        pub subject: String,
+        pub body: String,
+        /// Failed attempts so far
+        pub attempts: i32,
+    }
+
+    impl EmailNotification {
+        /// Queue an email for the background worker, returning its id
+        pub async fn enqueue(
+            recipient_email: &str,
+            subject: &str,
+            body: &str,
+        ) -> Result<i32, sqlx::Error> {
+            let row = sqlx::query!(
+                r#"
+                INSERT INTO email_notifications (recipient_email, subject, body)
+                VALUES ($1, $2, $3)
+                RETURNING id
+                "#,
+                recipient_email,
+                subject,
+                body
+            )
+            .fetch_one(crate::database::get_db())
+            .await?;
+            Ok(row.id)
+        }
+
+        /// Queue several emails in one statement; `emails` holds
+        /// `(recipient_email, subject, body)` triples
+        pub async fn enqueue_many(emails: &[(String, String, String)]) -> Result<(), sqlx::Error> {
+            let (recipients, (subjects, bodies)): (Vec<String>, (Vec<String>, Vec<String>)) =
+                emails
+                    .iter()
+                    .cloned()
+                    .map(|(recipient, subject, body)| (recipient, (subject, body)))
+                    .unzip();
+            sqlx::query!(
+                r#"
+                INSERT INTO email_notifications (recipient_email, subject, body)
+                SELECT * FROM UNNEST($1::VARCHAR[], $2::VARCHAR[], $3::TEXT[])
+                "#,
+                &recipients,
+                &subjects,
+                &bodies
+            )
+            .execute(crate::database::get_db())
+            .await?;
+            Ok(())
+        }
+
+        /// Pending emails whose next attempt is due, oldest first
+        pub async fn get_due(limit: i64) -> Result<Vec<Self>, sqlx::Error> {
+            sqlx::query_as!(
+                EmailNotification,
+                r#"
+                SELECT id, recipient_email, subject, body, attempts
+                FROM email_notifications
+                WHERE status = $1 AND next_attempt_at <= NOW()
+                ORDER BY next_attempt_at, id
+                LIMIT $2
+                "#,
+                EmailStatus::Pending.as_i16(),
+                limit
+            )
+            .fetch_all(crate::database::get_db())
+            .await
+        }
+
+        /// Record delivery. The body is blanked because it can hold secrets
+        /// such as password reset links.
+        pub async fn mark_sent(id: i32) -> Result<(), sqlx::Error> {
+            sqlx::query!(
+                r#"
+                UPDATE email_notifications
+                SET status = $1, attempts = attempts + 1, last_error = NULL, sent_at = NOW(),
+                    body = ''
+                WHERE id = $2
+                "#,
+                EmailStatus::Sent.as_i16(),
+                id
+            )
+            .execute(crate::database::get_db())
+            .await?;
+            Ok(())
+        }
+
+        /// Record a failed attempt and try again at `next_attempt_at`
+        pub async fn schedule_retry(
+            id: i32,
+            error: &str,
+            next_attempt_at: chrono::DateTime<chrono::Utc>,
+        ) -> Result<(), sqlx::Error> {
+            sqlx::query!(
+                r#"
+                UPDATE email_notifications
+                SET attempts = attempts + 1, last_error = $1, next_attempt_at = $2
+                WHERE id = $3
+                "#,
+                error,
+                next_attempt_at,
+                id
+            )
+            .execute(crate::database::get_db())
+            .await?;
+            Ok(())
+        }
+
+        /// Record the last failed attempt and stop retrying. The body is
+        /// blanked as in [`Self::mark_sent`].
+        pub async fn mark_failed(id: i32, error: &str) -> Result<(), sqlx::Error> {
+            sqlx::query!(
+                r#"
+                UPDATE email_notifications
+                SET status = $1, attempts = attempts + 1, last_error = $2, body = ''
+                WHERE id = $3
+                "#,
+                EmailStatus::Failed.as_i16(),
+                error,
+                id
+            )
+            .execute(crate::database::get_db())
+            .await?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub use inner::EmailNotification;
@@ -1,13 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+/// Why a user flagged an idea or comment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FlagReason {
+    Spam,
+    Abusive,
+    OffTopic,
+    Duplicate,
+    Other,
+}
+
+impl FlagReason {
+    pub const ALL: [FlagReason; 5] = [
+        Self::Spam,
+        Self::Abusive,
+        Self::OffTopic,
+        Self::Duplicate,
+        Self::Other,
+    ];
+
+    /// Value stored in `flags.reason`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Spam => "spam",
+            Self::Abusive => "abusive",
+            Self::OffTopic => "off_topic",
+            Self::Duplicate => "duplicate",
+            Self::Other => "other",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Spam => "Spam",
+            Self::Abusive => "Abusive or offensive",
+            Self::OffTopic => "Off-topic",
+            Self::Duplicate => "Duplicate",
+            Self::Other => "Other",
+        }
+    }
+
+    pub fn from_name(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|reason| reason.as_str() == value)
+    }
+}
+
 #[cfg(feature = "ssr")]
 mod inner {
     use serde::{Deserialize, Serialize};
 
+    use super::FlagReason;
+
     #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
     pub struct Flag {
         pub id: i32,
         pub target_type: String, // 'idea' or 'comment'
         pub target_id: i32,
-        pub user_id: i32,
+        /// `None` for flags raised by the word filter
+        pub user_id: Option<i32>,
+        pub reason: String,
+        pub details: Option<String>,
         pub created_at: chrono::DateTime<chrono::Utc>,
     }
 
@@ -17,6 +71,11 @@ mod inner {
         pub target_id: i32,
         pub flag_count: i64,
         pub first_flagged: chrono::DateTime<chrono::Utc>,
+        /// Hidden automatically until a moderator reviews it
+        pub auto_hidden: bool,
+        /// Category of the idea, or of the idea the comment is on; `None` once
+        /// the item is gone
+        pub category_id: Option<i32>,
     }
 
     impl Flag {
@@ -26,28 +85,158 @@ mod inner {
                 FlaggedItem,
                 r#"
                 SELECT
-                    target_type,
-                    target_id,
+                    f.target_type,
+                    f.target_id,
                     COUNT(*) as "flag_count!",
-                    MIN(created_at) as "first_flagged!"
-                FROM flags
-                GROUP BY target_type, target_id
-                ORDER BY COUNT(*) DESC, MIN(created_at) ASC
+                    MIN(f.created_at) as "first_flagged!",
+                    CASE f.target_type
+                        WHEN 'idea' THEN EXISTS(
+                            SELECT 1 FROM ideas WHERE id = f.target_id AND is_auto_hidden
+                        )
+                        ELSE EXISTS(
+                            SELECT 1 FROM comments WHERE id = f.target_id AND is_held
+                        )
+                    END as "auto_hidden!",
+                    CASE f.target_type
+                        WHEN 'idea' THEN (
+                            SELECT category_id FROM ideas WHERE id = f.target_id
+                        )
+                        ELSE (
+                            SELECT i.category_id FROM comments c
+                            INNER JOIN ideas i ON i.id = c.idea_id
+                            WHERE c.id = f.target_id
+                        )
+                    END as category_id
+                FROM flags f
+                GROUP BY f.target_type, f.target_id
+                ORDER BY COUNT(*) DESC, MIN(f.created_at) ASC
                 "#
             )
             .fetch_all(crate::database::get_db())
             .await
         }
 
-        /// Clear all flags for a specific item
-        pub async fn clear_flags(target_type: &str, target_id: i32) -> Result<(), sqlx::Error> {
-            sqlx::query!(
+        /// All flags on one item, oldest first
+        pub async fn get_for_target(
+            target_type: &str,
+            target_id: i32,
+        ) -> Result<Vec<Self>, sqlx::Error> {
+            sqlx::query_as!(
+                Flag,
+                r#"
+                SELECT id, target_type, target_id, user_id, reason, details, created_at
+                FROM flags
+                WHERE target_type = $1 AND target_id = $2
+                ORDER BY created_at
+                "#,
+                target_type,
+                target_id
+            )
+            .fetch_all(crate::database::get_db())
+            .await
+        }
+
+        /// How many earlier flags a moderator dismissed, for each user who
+        /// flagged an item
+        pub async fn get_flagger_dismissals(
+            target_type: &str,
+            target_id: i32,
+        ) -> Result<Vec<i32>, sqlx::Error> {
+            sqlx::query_scalar!(
+                r#"
+                SELECT u.dismissed_flags
+                FROM flags f
+                INNER JOIN users u ON u.id = f.user_id
+                WHERE f.target_type = $1 AND f.target_id = $2
+                "#,
+                target_type,
+                target_id
+            )
+            .fetch_all(crate::database::get_db())
+            .await
+        }
+
+        /// Ids of the comments on an idea that a user has flagged
+        pub async fn get_user_flagged_comment_ids(
+            user_id: i32,
+            idea_id: i32,
+        ) -> Result<Vec<i32>, sqlx::Error> {
+            let rows = sqlx::query!(
+                r#"
+                SELECT f.target_id
+                FROM flags f
+                INNER JOIN comments c ON c.id = f.target_id
+                WHERE f.user_id = $1 AND f.target_type = 'comment' AND c.idea_id = $2
+                "#,
+                user_id,
+                idea_id
+            )
+            .fetch_all(crate::database::get_db())
+            .await?;
+            Ok(rows.into_iter().map(|row| row.target_id).collect())
+        }
+
+        /// Clear all flags for a specific item, returning how many were removed
+        pub async fn clear_flags(target_type: &str, target_id: i32) -> Result<u64, sqlx::Error> {
+            let result = sqlx::query!(
                 "DELETE FROM flags WHERE target_type = $1 AND target_id = $2",
                 target_type,
                 target_id
             )
             .execute(crate::database::get_db())
             .await?;
+            Ok(result.rows_affected())
+        }
+
+        /// Clear the flags on an item a moderator found acceptable, counting a
+        /// dismissed flag against each flagger. Returns how many were removed,
+        /// word filter flags included.
+        pub async fn dismiss_flags(target_type: &str, target_id: i32) -> Result<u64, sqlx::Error> {
+            let removed = sqlx::query_scalar!(
+                r#"
+                WITH removed AS (
+                    DELETE FROM flags
+                    WHERE target_type = $1 AND target_id = $2
+                    RETURNING user_id
+                ), dismissed AS (
+                    UPDATE users
+                    SET dismissed_flags = dismissed_flags + 1
+                    WHERE id IN (SELECT user_id FROM removed)
+                )
+                SELECT COUNT(*) AS "removed!" FROM removed
+                "#,
+                target_type,
+                target_id
+            )
+            .fetch_one(crate::database::get_db())
+            .await?;
+            Ok(removed as u64)
+        }
+
+        /// Flag an item on behalf of the word filter; `details` names the rule.
+        /// An item keeps at most one open filter flag however often it is
+        /// edited, since the system flag has no user to make it unique.
+        pub async fn add_filter_flag(
+            target_type: &str,
+            target_id: i32,
+            details: String,
+        ) -> Result<(), sqlx::Error> {
+            sqlx::query!(
+                r#"
+                INSERT INTO flags (user_id, target_type, target_id, reason, details)
+                SELECT NULL, $1::VARCHAR, $2, $3, $4
+                WHERE NOT EXISTS (
+                    SELECT 1 FROM flags
+                    WHERE user_id IS NULL AND target_type = $1 AND target_id = $2
+                )
+                "#,
+                target_type,
+                target_id,
+                FlagReason::Other.as_str(),
+                details
+            )
+            .execute(crate::database::get_db())
+            .await?;
             Ok(())
         }
 
@@ -73,8 +262,8 @@ mod inner {
             Ok(row.exists)
         }
 
-        /// Toggle a user flag and return the resulting flagged state.
-        pub async fn toggle_user_flag(
+        /// Remove a user's flag, returning whether there was one
+        pub async fn remove_user_flag(
             user_id: i32,
             target_type: &str,
             target_id: i32,
@@ -88,28 +277,87 @@ mod inner {
             .execute(crate::database::get_db())
             .await?
             .rows_affected();
+            Ok(deleted > 0)
+        }
 
-            if deleted > 0 {
-                return Ok(false);
-            }
-
+        /// Flag an item for a user; flagging twice keeps the first reason
+        pub async fn add_user_flag(
+            user_id: i32,
+            target_type: &str,
+            target_id: i32,
+            reason: FlagReason,
+            details: Option<String>,
+        ) -> Result<(), sqlx::Error> {
             sqlx::query!(
                 r#"
-                INSERT INTO flags (user_id, target_type, target_id)
-                VALUES ($1, $2, $3)
+                INSERT INTO flags (user_id, target_type, target_id, reason, details)
+                VALUES ($1, $2, $3, $4, $5)
                 ON CONFLICT (user_id, target_type, target_id) DO NOTHING
                 "#,
                 user_id,
                 target_type,
-                target_id
+                target_id,
+                reason.as_str(),
+                details
             )
             .execute(crate::database::get_db())
             .await?;
-
-            Ok(true)
+            Ok(())
         }
     }
 }
 
 #[cfg(feature = "ssr")]
 pub use inner::Flag;
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::{Flag, FlagReason};
+    use crate::models::User;
+
+    /// Runs against the database in `DATABASE_URL`, and is skipped without one
+    #[tokio::test]
+    async fn dismissing_counts_user_and_filter_flags() {
+        if std::env::var("DATABASE_URL").is_err() {
+            return;
+        }
+        crate::database::init_db().await.expect("database");
+
+        let stamp = chrono::Utc::now().timestamp_micros();
+        let mut flaggers = Vec::new();
+        for n in 0..2 {
+            let user = User::create(
+                format!("flagger{n}-{stamp}@example.com"),
+                format!("Flagger {n}"),
+                "password".to_string(),
+            )
+            .await
+            .expect("create flagger");
+            flaggers.push(user.id);
+        }
+        // No idea has a negative id, so only these flags are on the target
+        let target_id = -1 - (stamp % 1_000_000) as i32;
+        for flagger in &flaggers {
+            Flag::add_user_flag(*flagger, "idea", target_id, FlagReason::Spam, None)
+                .await
+                .expect("add user flag");
+        }
+        Flag::add_filter_flag("idea", target_id, "word filter".to_string())
+            .await
+            .expect("add filter flag");
+
+        let removed = Flag::dismiss_flags("idea", target_id).await;
+        let dismissals = sqlx::query_scalar!(
+            "SELECT dismissed_flags FROM users WHERE id = ANY($1)",
+            &flaggers
+        )
+        .fetch_all(crate::database::get_db())
+        .await;
+        for flagger in flaggers {
+            User::delete(flagger).await.expect("delete flagger");
+        }
+
+        assert_eq!(removed.expect("dismiss flags"), 3);
+        assert_eq!(dismissals.expect("fetch dismissals"), vec![1, 1]);
+    }
+}
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "ssr")]
+use super::{EditWindow, IdeaRevision, StageChange, Tag};
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
 pub struct Idea {
@@ -15,6 +18,14 @@ pub struct Idea {
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub vote_count: i32,
     pub comments_enabled: bool,
+    pub edited_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub moderation_reason: Option<String>,
+    /// Waiting for a moderator to approve it before it is published
+    pub is_pending: bool,
+    /// Set when this idea was merged into another as a duplicate; the page
+    /// redirects there
+    pub merged_into: Option<i32>,
+    pub category_id: i32,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -24,16 +35,63 @@ pub struct IdeaWithAuthor {
     pub author_email: String,
 }
 
-// Valid stage values
-pub const STAGES: [&str; 4] = ["Ideate", "Review", "In Progress", "Completed"];
+/// A published idea that resembles another one, scored by trigram
+/// similarity of the titles and bodies.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SimilarIdea {
+    pub id: i32,
+    pub title: String,
+    pub stage: String,
+    pub vote_count: i32,
+    /// From 0 (nothing in common) to 1 (identical)
+    pub similarity: f64,
+}
+
+/// Two published ideas that may be duplicates, older first (moderator view)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DuplicatePair {
+    pub original: SimilarIdea,
+    pub duplicate: SimilarIdea,
+}
+
+/// Ideas scoring below this are not shown as similar
+#[cfg(feature = "ssr")]
+const MIN_SIMILARITY: f64 = 0.25;
+
+/// What a merge carried over to the surviving idea
+#[cfg(feature = "ssr")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MergeSummary {
+    /// Votes moved; voters who backed both ideas keep their one vote
+    pub votes_moved: u64,
+    pub comments_moved: u64,
+}
 
 impl Idea {
     pub fn is_pinned(&self) -> bool {
         self.pinned_at.is_some()
     }
 
-    pub fn is_valid_stage(stage: &str) -> bool {
-        STAGES.contains(&stage)
+    /// Waiting for a moderator to approve it; a merged stub never is
+    pub fn awaits_approval(&self) -> bool {
+        self.is_pending && self.merged_into.is_none()
+    }
+
+    /// Why this idea cannot take part in a merge, if it cannot. Only
+    /// published ideas are merged, so a stub never comes back through the
+    /// approval queue or a released auto-hide.
+    pub fn merge_blocker(&self) -> Option<&'static str> {
+        if self.merged_into.is_some() {
+            Some("was already merged")
+        } else if self.is_pending {
+            Some("is waiting for approval")
+        } else if self.is_off_topic {
+            Some("is marked off-topic")
+        } else if !self.is_public {
+            Some("is hidden")
+        } else {
+            None
+        }
     }
 }
 
@@ -45,7 +103,8 @@ impl Idea {
             Idea,
             r#"
             SELECT id, user_id, title, content, tags, stage, is_public, is_off_topic,
-                   pinned_at, created_at, vote_count, comments_enabled
+                   pinned_at, created_at, vote_count, comments_enabled, edited_at,
+                   moderation_reason, is_pending, merged_into, category_id
             FROM ideas
             WHERE id = $1 AND is_public = true AND is_off_topic = false
             "#,
@@ -61,7 +120,8 @@ impl Idea {
             Idea,
             r#"
             SELECT id, user_id, title, content, tags, stage, is_public, is_off_topic,
-                   pinned_at, created_at, vote_count, comments_enabled
+                   pinned_at, created_at, vote_count, comments_enabled, edited_at,
+                   moderation_reason, is_pending, merged_into, category_id
             FROM ideas
             WHERE id = $1
             "#,
@@ -73,17 +133,31 @@ impl Idea {
 
     /// Get idea with author information
     pub async fn get_with_author(id: i32) -> Result<Option<IdeaWithAuthor>, sqlx::Error> {
+        Self::fetch_with_author(id, false).await
+    }
+
+    /// Get idea with author information (including hidden items for moderators)
+    pub async fn get_with_author_mod(id: i32) -> Result<Option<IdeaWithAuthor>, sqlx::Error> {
+        Self::fetch_with_author(id, true).await
+    }
+
+    async fn fetch_with_author(
+        id: i32,
+        include_hidden: bool,
+    ) -> Result<Option<IdeaWithAuthor>, sqlx::Error> {
         let result = sqlx::query!(
             r#"
             SELECT
                 i.id, i.user_id, i.title, i.content, i.tags, i.stage, i.is_public, i.is_off_topic,
-                i.pinned_at, i.created_at, i.vote_count, i.comments_enabled,
+                i.pinned_at, i.created_at, i.vote_count, i.comments_enabled, i.edited_at,
+                i.moderation_reason, i.is_pending, i.merged_into, i.category_id,
                 u.name as author_name, u.email as author_email
             FROM ideas i
             INNER JOIN users u ON i.user_id = u.id
-            WHERE i.id = $1 AND i.is_public = true AND i.is_off_topic = false
+            WHERE i.id = $1 AND ($2 OR (i.is_public = true AND i.is_off_topic = false))
             "#,
-            id
+            id,
+            include_hidden
         )
         .fetch_optional(crate::database::get_db())
         .await?;
@@ -102,28 +176,155 @@ impl Idea {
                 created_at: r.created_at,
                 vote_count: r.vote_count,
                 comments_enabled: r.comments_enabled,
+                edited_at: r.edited_at,
+                moderation_reason: r.moderation_reason,
+                is_pending: r.is_pending,
+                merged_into: r.merged_into,
+                category_id: r.category_id,
             },
             author_name: r.author_name,
             author_email: r.author_email,
         }))
     }
 
-    /// Get all public ideas (not off-topic)
-    pub async fn get_all() -> Result<Vec<IdeaWithAuthor>, sqlx::Error> {
+    /// Get ideas by user (for profile page)
+    pub async fn get_by_user(user_id: i32) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Idea,
+            r#"
+            SELECT id, user_id, title, content, tags, stage, is_public, is_off_topic,
+                   pinned_at, created_at, vote_count, comments_enabled, edited_at,
+                   moderation_reason, is_pending, merged_into, category_id
+            FROM ideas
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            "#,
+            user_id
+        )
+        .fetch_all(crate::database::get_db())
+        .await
+    }
+
+    /// Get the visible ideas among `ids`, newest first (for profile page)
+    pub async fn get_by_ids(ids: &[i32]) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Idea,
+            r#"
+            SELECT id, user_id, title, content, tags, stage, is_public, is_off_topic,
+                   pinned_at, created_at, vote_count, comments_enabled, edited_at,
+                   moderation_reason, is_pending, merged_into, category_id
+            FROM ideas
+            WHERE id = ANY($1) AND is_public = true AND is_off_topic = false
+            ORDER BY created_at DESC
+            "#,
+            ids
+        )
+        .fetch_all(crate::database::get_db())
+        .await
+    }
+
+    /// Published ideas most similar to a draft, best match first. The title
+    /// counts for more than the body (content and tags). Candidates are
+    /// narrowed with the trigram `%` operator first so the title and content
+    /// indexes are used instead of scoring every idea.
+    pub async fn find_similar(
+        title: &str,
+        body: &str,
+        limit: i64,
+    ) -> Result<Vec<SimilarIdea>, sqlx::Error> {
+        sqlx::query_as!(
+            SimilarIdea,
+            r#"
+            SELECT i.id, i.title, i.stage, i.vote_count, s.score AS "similarity!"
+            FROM ideas i
+            CROSS JOIN LATERAL (
+                SELECT (0.6 * similarity(i.title, $1)
+                        + 0.4 * similarity(i.content || ' ' || i.tags, $2))::float8 AS score
+            ) s
+            WHERE (i.title % $1 OR i.content % $2)
+              AND i.is_public = true AND i.is_off_topic = false AND s.score >= $3
+            ORDER BY s.score DESC, i.vote_count DESC
+            LIMIT $4
+            "#,
+            title,
+            body,
+            MIN_SIMILARITY,
+            limit
+        )
+        .fetch_all(crate::database::get_db())
+        .await
+    }
+
+    /// Pairs of published ideas with similar titles, most alike first
+    /// (moderator view). With a non-empty `scope` only pairs where both ideas
+    /// are in those categories are listed.
+    pub async fn get_possible_duplicates(
+        scope: &[i32],
+        limit: i64,
+    ) -> Result<Vec<DuplicatePair>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT a.id AS original_id, a.title AS original_title, a.stage AS original_stage,
+                   a.vote_count AS original_votes,
+                   b.id AS duplicate_id, b.title AS duplicate_title, b.stage AS duplicate_stage,
+                   b.vote_count AS duplicate_votes,
+                   s.score AS "similarity!"
+            FROM ideas a
+            INNER JOIN ideas b ON b.id > a.id AND a.title % b.title
+            CROSS JOIN LATERAL (
+                SELECT (0.6 * similarity(a.title, b.title)
+                        + 0.4 * similarity(a.content || ' ' || a.tags,
+                                           b.content || ' ' || b.tags))::float8 AS score
+            ) s
+            WHERE a.is_public = true AND a.is_off_topic = false
+              AND b.is_public = true AND b.is_off_topic = false
+              AND s.score >= $1
+              AND (cardinality($3::int[]) = 0
+                   OR (a.category_id = ANY($3) AND b.category_id = ANY($3)))
+            ORDER BY s.score DESC, b.created_at DESC
+            LIMIT $2
+            "#,
+            MIN_SIMILARITY,
+            limit,
+            scope
+        )
+        .fetch_all(crate::database::get_db())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| DuplicatePair {
+                original: SimilarIdea {
+                    id: r.original_id,
+                    title: r.original_title,
+                    stage: r.original_stage,
+                    vote_count: r.original_votes,
+                    similarity: r.similarity,
+                },
+                duplicate: SimilarIdea {
+                    id: r.duplicate_id,
+                    title: r.duplicate_title,
+                    stage: r.duplicate_stage,
+                    vote_count: r.duplicate_votes,
+                    similarity: r.similarity,
+                },
+            })
+            .collect())
+    }
+
+    /// Get off-topic ideas (moderator view)
+    pub async fn get_off_topic() -> Result<Vec<IdeaWithAuthor>, sqlx::Error> {
         let results = sqlx::query!(
             r#"
             SELECT
                 i.id, i.user_id, i.title, i.content, i.tags, i.stage, i.is_public, i.is_off_topic,
-                i.pinned_at, i.created_at, i.vote_count, i.comments_enabled,
+                i.pinned_at, i.created_at, i.vote_count, i.comments_enabled, i.edited_at,
+                i.moderation_reason, i.is_pending, i.merged_into, i.category_id,
                 u.name as author_name, u.email as author_email
             FROM ideas i
             INNER JOIN users u ON i.user_id = u.id
-            WHERE i.is_public = true AND i.is_off_topic = false
-            ORDER BY
-                (CASE WHEN i.pinned_at IS NOT NULL THEN 0 ELSE 1 END),
-                i.pinned_at DESC NULLS LAST,
-                i.vote_count DESC,
-                i.created_at DESC
+            WHERE i.is_off_topic = true
+            ORDER BY i.created_at DESC
             "#
         )
         .fetch_all(crate::database::get_db())
@@ -145,6 +346,11 @@ impl Idea {
                     created_at: r.created_at,
                     vote_count: r.vote_count,
                     comments_enabled: r.comments_enabled,
+                    edited_at: r.edited_at,
+                    moderation_reason: r.moderation_reason,
+                    is_pending: r.is_pending,
+                    merged_into: r.merged_into,
+                    category_id: r.category_id,
                 },
                 author_name: r.author_name,
                 author_email: r.author_email,
@@ -152,35 +358,19 @@ impl Idea {
             .collect())
     }
 
-    /// Get ideas by user (for profile page)
-    pub async fn get_by_user(user_id: i32) -> Result<Vec<Self>, sqlx::Error> {
-        sqlx::query_as!(
-            Idea,
-            r#"
-            SELECT id, user_id, title, content, tags, stage, is_public, is_off_topic,
-                   pinned_at, created_at, vote_count, comments_enabled
-            FROM ideas
-            WHERE user_id = $1
-            ORDER BY created_at DESC
-            "#,
-            user_id
-        )
-        .fetch_all(crate::database::get_db())
-        .await
-    }
-
-    /// Get off-topic ideas (moderator view)
-    pub async fn get_off_topic() -> Result<Vec<IdeaWithAuthor>, sqlx::Error> {
+    /// Get ideas waiting for approval, oldest first (moderator view)
+    pub async fn get_pending() -> Result<Vec<IdeaWithAuthor>, sqlx::Error> {
         let results = sqlx::query!(
             r#"
             SELECT
                 i.id, i.user_id, i.title, i.content, i.tags, i.stage, i.is_public, i.is_off_topic,
-                i.pinned_at, i.created_at, i.vote_count, i.comments_enabled,
+                i.pinned_at, i.created_at, i.vote_count, i.comments_enabled, i.edited_at,
+                i.moderation_reason, i.is_pending, i.merged_into, i.category_id,
                 u.name as author_name, u.email as author_email
             FROM ideas i
             INNER JOIN users u ON i.user_id = u.id
-            WHERE i.is_off_topic = true
-            ORDER BY i.created_at DESC
+            WHERE i.is_pending = true AND i.merged_into IS NULL
+            ORDER BY i.created_at ASC
             "#
         )
         .fetch_all(crate::database::get_db())
@@ -202,6 +392,11 @@ impl Idea {
                     created_at: r.created_at,
                     vote_count: r.vote_count,
                     comments_enabled: r.comments_enabled,
+                    edited_at: r.edited_at,
+                    moderation_reason: r.moderation_reason,
+                    is_pending: r.is_pending,
+                    merged_into: r.merged_into,
+                    category_id: r.category_id,
                 },
                 author_name: r.author_name,
                 author_email: r.author_email,
@@ -209,82 +404,247 @@ impl Idea {
             .collect())
     }
 
-    /// Create a new idea
+    /// Create a new idea with the given tag names in a category and starting
+    /// `stage`, published right away unless it is `pending` approval
     pub async fn create(
         user_id: i32,
         title: String,
         content: String,
-        tags: String,
+        tags: &[String],
+        category_id: i32,
+        stage: &str,
+        pending: bool,
     ) -> Result<Self, sqlx::Error> {
-        let tags_trimmed = tags.trim().to_string();
-        sqlx::query_as!(
+        let mut tx = crate::database::get_db().begin().await?;
+        let mut idea = sqlx::query_as!(
             Idea,
             r#"
-            INSERT INTO ideas (user_id, title, content, tags, stage, is_public, is_off_topic)
-            VALUES ($1, $2, $3, $4, 'Ideate', true, false)
+            INSERT INTO ideas
+                (user_id, title, content, category_id, stage, is_public, is_off_topic, is_pending)
+            VALUES ($1, $2, $3, $4, $5, NOT $6, false, $6)
             RETURNING id, user_id, title, content, tags, stage, is_public, is_off_topic,
-                      pinned_at, created_at, vote_count, comments_enabled
+                      pinned_at, created_at, vote_count, comments_enabled, edited_at,
+                      moderation_reason, is_pending, merged_into, category_id
             "#,
             user_id,
             title,
             content,
-            tags_trimmed
+            category_id,
+            stage,
+            pending
         )
-        .fetch_one(crate::database::get_db())
-        .await
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Tag::set_for_idea(&mut tx, idea.id, tags).await?;
+        idea.tags = sqlx::query_scalar!("SELECT tags FROM ideas WHERE id = $1", idea.id)
+            .fetch_one(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(idea)
     }
 
-    /// Update idea content (author only)
+    /// Update idea content (author only), keeping the previous version.
+    /// Returns false unless the idea is inside `window` and nobody has voted
+    /// or commented yet, checked as part of the update.
     pub async fn update_content(
         id: i32,
         user_id: i32,
+        window: EditWindow,
         title: String,
         content: String,
     ) -> Result<bool, sqlx::Error> {
+        let mut tx = crate::database::get_db().begin().await?;
+        IdeaRevision::record(&mut tx, id, user_id).await?;
         let result = sqlx::query!(
-            "UPDATE ideas SET title = $1, content = $2 WHERE id = $3 AND user_id = $4",
+            r#"
+            UPDATE ideas SET title = $1, content = $2, edited_at = NOW()
+            WHERE id = $3 AND user_id = $4
+              AND NOW() < created_at + INTERVAL '1 minute' * $5::BIGINT
+              AND vote_count = 0
+              AND NOT EXISTS (
+                  SELECT 1 FROM comments c WHERE c.idea_id = ideas.id AND c.is_deleted = false
+              )
+            "#,
             title,
             content,
             id,
-            user_id
+            user_id,
+            window.minutes
         )
-        .execute(crate::database::get_db())
+        .execute(&mut *tx)
         .await?;
 
-        Ok(result.rows_affected() > 0)
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+        tx.commit().await?;
+        Ok(true)
     }
 
-    /// Update idea content and tags (moderator/admin)
+    /// Update idea content, tags and category (moderator/admin), keeping the
+    /// previous version
     pub async fn update_content_mod(
         id: i32,
+        editor_id: i32,
         title: String,
         content: String,
-        tags: String,
+        tags: &[String],
+        category_id: i32,
     ) -> Result<bool, sqlx::Error> {
-        let tags_trimmed = tags.trim().to_string();
+        let mut tx = crate::database::get_db().begin().await?;
+        IdeaRevision::record(&mut tx, id, editor_id).await?;
         let result = sqlx::query!(
-            "UPDATE ideas SET title = $1, content = $2, tags = $3 WHERE id = $4",
+            r#"
+            UPDATE ideas SET title = $1, content = $2, category_id = $3, edited_at = NOW()
+            WHERE id = $4
+            "#,
             title,
             content,
-            tags_trimmed,
+            category_id,
             id
         )
-        .execute(crate::database::get_db())
+        .execute(&mut *tx)
         .await?;
 
-        Ok(result.rows_affected() > 0)
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+        Tag::set_for_idea(&mut tx, id, tags).await?;
+        tx.commit().await?;
+        Ok(true)
     }
 
-    /// Update idea stage (moderator only)
-    pub async fn update_stage(id: i32, stage: String) -> Result<(), sqlx::Error> {
-        if !Self::is_valid_stage(&stage) {
-            return Err(sqlx::Error::Protocol(format!("Invalid stage: {}", stage)));
+    /// Merge the duplicate idea into the target (moderator only). Votes move
+    /// across through the vote-count triggers, skipping voters who already
+    /// back the target; comments are re-parented; and the duplicate becomes a
+    /// hidden stub that redirects to the target. Returns `None` when either
+    /// idea is missing, already merged or not published.
+    pub async fn merge(
+        target_id: i32,
+        duplicate_id: i32,
+    ) -> Result<Option<MergeSummary>, sqlx::Error> {
+        if target_id == duplicate_id {
+            return Ok(None);
         }
+        let mut tx = crate::database::get_db().begin().await?;
 
-        sqlx::query!("UPDATE ideas SET stage = $1 WHERE id = $2", stage, id)
-            .execute(crate::database::get_db())
-            .await?;
-        Ok(())
+        let locked = sqlx::query_scalar!(
+            r#"
+            SELECT id FROM ideas
+            WHERE id = ANY($1) AND merged_into IS NULL
+              AND is_public = true AND is_pending = false AND is_off_topic = false
+            ORDER BY id
+            FOR UPDATE
+            "#,
+            &[target_id, duplicate_id][..]
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        if locked.len() != 2 {
+            return Ok(None);
+        }
+
+        let votes_moved = sqlx::query!(
+            r#"
+            WITH moved AS (
+                DELETE FROM votes WHERE idea_id = $2
+                RETURNING user_id, created_at
+            )
+            INSERT INTO votes (user_id, idea_id, created_at)
+            SELECT user_id, $1, created_at FROM moved
+            ON CONFLICT (user_id, idea_id) DO NOTHING
+            "#,
+            target_id,
+            duplicate_id
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        let comments_moved = sqlx::query!(
+            "UPDATE comments SET idea_id = $1 WHERE idea_id = $2",
+            target_id,
+            duplicate_id
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        sqlx::query!(
+            "DELETE FROM flags WHERE target_type = 'idea' AND target_id = $1",
+            duplicate_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // Stubs that pointed at the duplicate now point at the target
+        sqlx::query!(
+            r#"
+            UPDATE ideas
+            SET merged_into = $1
+            WHERE id = $2 OR merged_into = $2
+            "#,
+            target_id,
+            duplicate_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"
+            UPDATE ideas
+            SET is_public = false, is_pending = false, is_auto_hidden = false,
+                pinned_at = NULL, comments_enabled = false
+            WHERE id = $1
+            "#,
+            duplicate_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(MergeSummary {
+            votes_moved,
+            comments_moved,
+        }))
+    }
+
+    /// Move an idea from `from_stage` to `to_stage` if the workflow allows
+    /// it, adding the move and any official response to the idea's
+    /// timeline. Returns false when the idea is no longer in `from_stage` or
+    /// the workflow does not allow the move.
+    pub async fn update_stage(
+        id: i32,
+        from_stage: &str,
+        to_stage: &str,
+        actor_id: i32,
+        response: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let mut tx = crate::database::get_db().begin().await?;
+        let result = sqlx::query!(
+            r#"
+            UPDATE ideas i SET stage = $1
+            WHERE i.id = $2 AND i.stage = $3 AND EXISTS (
+                SELECT 1
+                FROM stage_transitions t
+                INNER JOIN stages f ON f.id = t.from_stage_id
+                INNER JOIN stages n ON n.id = t.to_stage_id
+                WHERE f.name = i.stage AND n.name = $1
+            )
+            "#,
+            to_stage,
+            id,
+            from_stage
+        )
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        StageChange::record(&mut tx, id, actor_id, from_stage, to_stage, response).await?;
+        tx.commit().await?;
+        Ok(true)
     }
 
     /// Toggle pinned status (moderator only)
@@ -307,11 +667,17 @@ impl Idea {
         Ok(result.is_pinned)
     }
 
-    /// Mark idea as off-topic (moderator only)
-    pub async fn mark_off_topic(id: i32, is_off_topic: bool) -> Result<(), sqlx::Error> {
+    /// Mark idea as off-topic with the moderator's reason, or restore it and
+    /// clear the reason (moderator only)
+    pub async fn mark_off_topic(
+        id: i32,
+        is_off_topic: bool,
+        reason: Option<String>,
+    ) -> Result<(), sqlx::Error> {
         sqlx::query!(
-            "UPDATE ideas SET is_off_topic = $1 WHERE id = $2",
+            "UPDATE ideas SET is_off_topic = $1, moderation_reason = $2 WHERE id = $3",
             is_off_topic,
+            reason,
             id
         )
         .execute(crate::database::get_db())
@@ -319,6 +685,54 @@ impl Idea {
         Ok(())
     }
 
+    /// Publish a pending idea, returning its author and title if it was
+    /// pending
+    pub async fn approve(id: i32) -> Result<Option<(i32, String)>, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE ideas
+            SET is_pending = false, is_public = true
+            WHERE id = $1 AND is_pending = true AND merged_into IS NULL
+            RETURNING user_id, title
+            "#,
+            id
+        )
+        .fetch_optional(crate::database::get_db())
+        .await?;
+
+        Ok(row.map(|row| (row.user_id, row.title)))
+    }
+
+    /// Hide an idea for review after enough flags, returning its title if it
+    /// was visible until now
+    pub async fn auto_hide(id: i32) -> Result<Option<String>, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE ideas
+            SET is_public = false, is_auto_hidden = true
+            WHERE id = $1 AND is_public = true AND is_auto_hidden = false
+            RETURNING title
+            "#,
+            id
+        )
+        .fetch_optional(crate::database::get_db())
+        .await?;
+
+        Ok(row.map(|row| row.title))
+    }
+
+    /// Show an auto-hidden idea again, returning whether it was hidden
+    pub async fn release_auto_hide(id: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE ideas SET is_public = true, is_auto_hidden = false WHERE id = $1 AND is_auto_hidden = true",
+            id
+        )
+        .execute(crate::database::get_db())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Toggle comments enabled status (moderator only)
     pub async fn toggle_comments(id: i32) -> Result<bool, sqlx::Error> {
         let result = sqlx::query!(
@@ -336,6 +750,70 @@ impl Idea {
         Ok(result.comments_enabled)
     }
 
+    /// Whether anyone has commented on or voted for an idea yet
+    pub async fn has_engagement(id: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            SELECT (
+                i.vote_count > 0
+                OR EXISTS (SELECT 1 FROM comments c WHERE c.idea_id = i.id AND c.is_deleted = false)
+            ) as "engaged!"
+            FROM ideas i
+            WHERE i.id = $1
+            "#,
+            id
+        )
+        .fetch_one(crate::database::get_db())
+        .await?;
+
+        Ok(result.engaged)
+    }
+
+    /// Users following an idea: its author, voters and commenters
+    pub async fn get_follower_ids(id: i32) -> Result<Vec<i32>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT user_id AS "user_id!" FROM ideas WHERE id = $1
+            UNION
+            SELECT user_id FROM votes WHERE idea_id = $1
+            UNION
+            SELECT user_id FROM comments WHERE idea_id = $1 AND is_deleted = false
+            "#,
+            id
+        )
+        .fetch_all(crate::database::get_db())
+        .await?;
+
+        Ok(rows.into_iter().map(|row| row.user_id).collect())
+    }
+
+    /// Withdraw an idea (author only), under the same conditions as
+    /// [`Idea::update_content`]
+    pub async fn delete_own(
+        id: i32,
+        user_id: i32,
+        window: EditWindow,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM ideas
+            WHERE id = $1 AND user_id = $2
+              AND NOW() < created_at + INTERVAL '1 minute' * $3::BIGINT
+              AND vote_count = 0
+              AND NOT EXISTS (
+                  SELECT 1 FROM comments c WHERE c.idea_id = ideas.id AND c.is_deleted = false
+              )
+            "#,
+            id,
+            user_id,
+            window.minutes
+        )
+        .execute(crate::database::get_db())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Delete idea (moderator only)
     pub async fn delete(id: i32) -> Result<(), sqlx::Error> {
         sqlx::query!("DELETE FROM ideas WHERE id = $1", id)
@@ -382,3 +860,75 @@ impl Idea {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Idea;
+
+    fn published_idea(id: i32) -> Idea {
+        Idea {
+            id,
+            user_id: 1,
+            title: format!("Idea {id}"),
+            content: String::new(),
+            tags: String::new(),
+            stage: "Ideate".to_string(),
+            is_public: true,
+            is_off_topic: false,
+            pinned_at: None,
+            created_at: chrono::Utc::now(),
+            vote_count: 0,
+            comments_enabled: true,
+            edited_at: None,
+            moderation_reason: None,
+            is_pending: false,
+            merged_into: None,
+            category_id: 1,
+        }
+    }
+
+    #[test]
+    fn only_published_ideas_can_be_merged() {
+        assert_eq!(published_idea(1).merge_blocker(), None);
+
+        let pending = Idea {
+            is_public: false,
+            is_pending: true,
+            ..published_idea(2)
+        };
+        let off_topic = Idea {
+            is_off_topic: true,
+            ..published_idea(3)
+        };
+        let hidden = Idea {
+            is_public: false,
+            ..published_idea(4)
+        };
+        let merged = Idea {
+            is_public: false,
+            merged_into: Some(1),
+            ..published_idea(5)
+        };
+        assert_eq!(pending.merge_blocker(), Some("is waiting for approval"));
+        assert_eq!(off_topic.merge_blocker(), Some("is marked off-topic"));
+        assert_eq!(hidden.merge_blocker(), Some("is hidden"));
+        assert_eq!(merged.merge_blocker(), Some("was already merged"));
+    }
+
+    #[test]
+    fn merged_stub_no_longer_awaits_approval() {
+        let pending = Idea {
+            is_public: false,
+            is_pending: true,
+            ..published_idea(2)
+        };
+        assert!(pending.awaits_approval());
+
+        // A stub left pending by an older merge must not be approvable
+        let merged = Idea {
+            merged_into: Some(1),
+            ..pending
+        };
+        assert!(!merged.awaits_approval());
+    }
+}
@@ -0,0 +1,500 @@
+use serde::{Deserialize, Serialize};
+
+use super::{IdeaWithAuthor, ResponseSummary};
+
+/// Marks the start of a matched term in snippets from Postgres. Control
+/// characters cannot come from posted text, so the snippet can be split
+/// safely without rendering any of it as HTML.
+#[cfg(feature = "ssr")]
+const MATCH_START: char = '\u{2}';
+#[cfg(feature = "ssr")]
+const MATCH_END: char = '\u{3}';
+
+/// Order of the ideas on the board.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IdeaSort {
+    /// Best matches for the search text first; the same as popular without
+    /// one
+    Relevance,
+    #[default]
+    Popular,
+    Recent,
+}
+
+impl IdeaSort {
+    pub const ALL: [IdeaSort; 3] = [Self::Relevance, Self::Popular, Self::Recent];
+
+    /// Value used in the board's URL
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Relevance => "relevance",
+            Self::Popular => "popular",
+            Self::Recent => "recent",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Relevance => "Best match",
+            Self::Popular => "Popular",
+            Self::Recent => "Recent",
+        }
+    }
+
+    pub fn from_name(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|sort| sort.as_str() == value)
+    }
+}
+
+/// Search text, filters and sort order for the idea board. Every field is
+/// optional; the default lists all public ideas by popularity.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct IdeaSearch {
+    /// Words to look for in titles, tags, content and comments
+    pub text: String,
+    pub sort: IdeaSort,
+    pub stage: Option<String>,
+    /// Category slug
+    pub category: Option<String>,
+    /// Tag name or slug
+    pub tag: Option<String>,
+    /// Part of the author's name
+    pub author: Option<String>,
+    /// First day ideas were posted on, inclusive
+    pub posted_from: Option<chrono::NaiveDate>,
+    /// Last day ideas were posted on, inclusive
+    pub posted_to: Option<chrono::NaiveDate>,
+    /// Only ideas the current user voted for
+    pub voted_by_me: bool,
+}
+
+impl IdeaSearch {
+    /// Query-string keys, also used by the board's filter form
+    pub const TEXT: &'static str = "q";
+    pub const SORT: &'static str = "sort";
+    pub const STAGE: &'static str = "stage";
+    pub const CATEGORY: &'static str = "category";
+    pub const TAG: &'static str = "tag";
+    pub const AUTHOR: &'static str = "author";
+    pub const POSTED_FROM: &'static str = "from";
+    pub const POSTED_TO: &'static str = "to";
+    pub const VOTED_BY_ME: &'static str = "voted";
+
+    /// Read a search from query-string values, ignoring blank or malformed
+    /// ones.
+    pub fn from_query(get: impl Fn(&str) -> Option<String>) -> Self {
+        let value = |key: &str| {
+            get(key)
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let date = |key: &str| value(key).and_then(|value| value.parse().ok());
+        Self {
+            text: value(Self::TEXT).unwrap_or_default(),
+            sort: value(Self::SORT)
+                .and_then(|sort| IdeaSort::from_name(&sort))
+                .unwrap_or_default(),
+            stage: value(Self::STAGE),
+            category: value(Self::CATEGORY),
+            tag: value(Self::TAG),
+            author: value(Self::AUTHOR),
+            posted_from: date(Self::POSTED_FROM),
+            posted_to: date(Self::POSTED_TO),
+            voted_by_me: value(Self::VOTED_BY_ME).is_some_and(|voted| voted == "1"),
+        }
+    }
+
+    /// The non-default values as query-string pairs, in a stable order.
+    pub fn to_query(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if !self.text.trim().is_empty() {
+            pairs.push((Self::TEXT, self.text.trim().to_string()));
+        }
+        if self.sort != IdeaSort::default() {
+            pairs.push((Self::SORT, self.sort.as_str().to_string()));
+        }
+        let optional = [
+            (Self::STAGE, self.stage.clone()),
+            (Self::CATEGORY, self.category.clone()),
+            (Self::TAG, self.tag.clone()),
+            (Self::AUTHOR, self.author.clone()),
+            (
+                Self::POSTED_FROM,
+                self.posted_from.map(|date| date.to_string()),
+            ),
+            (Self::POSTED_TO, self.posted_to.map(|date| date.to_string())),
+        ];
+        pairs.extend(
+            optional
+                .into_iter()
+                .filter_map(|(key, value)| value.map(|value| (key, value))),
+        );
+        if self.voted_by_me {
+            pairs.push((Self::VOTED_BY_ME, "1".to_string()));
+        }
+        pairs
+    }
+
+    /// Whether any search text or filter is set
+    pub fn is_filtered(&self) -> bool {
+        let unsorted = Self {
+            sort: IdeaSort::default(),
+            ..self.clone()
+        };
+        !unsorted.to_query().is_empty()
+    }
+}
+
+/// Part of a search snippet, highlighted when it matched the search text.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnippetPart {
+    pub text: String,
+    pub highlighted: bool,
+}
+
+/// An idea on the board, with the passage that matched the search text.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IdeaSearchResult {
+    pub idea: IdeaWithAuthor,
+    pub category_name: String,
+    /// Badge color of the idea's stage
+    pub stage_color: String,
+    /// Latest official staff response, if any
+    pub latest_response: Option<ResponseSummary>,
+    /// Visible comments on the idea
+    pub comment_count: i64,
+    /// Empty without search text, or when only the title matched
+    pub snippet: Vec<SnippetPart>,
+}
+
+/// The sort keys of the last idea on a page; the next page starts after it.
+/// Ties are broken by id, so every idea has a distinct position.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IdeaCursor {
+    /// Search rank when sorting by relevance, otherwise 1 for pinned ideas
+    /// and 0 for the rest
+    pub rank: f64,
+    /// Unset when sorting by relevance
+    pub pinned_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Unset (0) when sorting by date
+    pub votes: i32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub id: i32,
+}
+
+/// One page of the board.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IdeaPage {
+    pub ideas: Vec<IdeaSearchResult>,
+    /// Where the next page starts; `None` on the last page
+    pub next: Option<IdeaCursor>,
+}
+
+/// Split a snippet from Postgres' `ts_headline` at the match markers.
+#[cfg(feature = "ssr")]
+fn split_snippet(snippet: &str) -> Vec<SnippetPart> {
+    let mut parts = Vec::new();
+    let mut rest = snippet;
+    while let Some(start) = rest.find(MATCH_START) {
+        let (before, after) = rest.split_at(start);
+        let after = &after[MATCH_START.len_utf8()..];
+        let (matched, remainder) = after.split_once(MATCH_END).unwrap_or((after, ""));
+        push_part(&mut parts, before, false);
+        push_part(&mut parts, matched, true);
+        rest = remainder;
+    }
+    push_part(&mut parts, rest, false);
+    parts
+}
+
+#[cfg(feature = "ssr")]
+fn push_part(parts: &mut Vec<SnippetPart>, text: &str, highlighted: bool) {
+    if !text.is_empty() {
+        parts.push(SnippetPart {
+            text: text.to_string(),
+            highlighted,
+        });
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl IdeaSearch {
+    /// One page of the public ideas matching the search, starting after
+    /// `after`. `viewer_id` is needed for `voted_by_me`, which matches nothing
+    /// without it.
+    pub async fn run(
+        &self,
+        viewer_id: Option<i32>,
+        after: Option<&IdeaCursor>,
+        page_size: i64,
+    ) -> Result<IdeaPage, sqlx::Error> {
+        use super::{slugify, Idea};
+
+        let headline_options = format!(
+            "StartSel={}, StopSel={}, MaxFragments=2, MaxWords=25, MinWords=8",
+            MATCH_START, MATCH_END
+        );
+        let start = self
+            .posted_from
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|time| time.and_utc());
+        let end = self
+            .posted_to
+            .and_then(|date| date.succ_opt())
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|time| time.and_utc());
+        let voted_by = if self.voted_by_me {
+            Some(viewer_id.unwrap_or(0))
+        } else {
+            None
+        };
+        let sort = match self.sort {
+            IdeaSort::Relevance if self.text.trim().is_empty() => IdeaSort::Popular,
+            sort => sort,
+        };
+
+        // One extra row tells whether there is another page
+        let mut rows = sqlx::query!(
+            r#"
+            SELECT
+                i.id, i.user_id, i.title, i.content, i.tags, i.stage, i.is_public, i.is_off_topic,
+                i.pinned_at, i.created_at, i.vote_count, i.comments_enabled, i.edited_at,
+                i.moderation_reason, i.is_pending, i.merged_into, i.category_id,
+                u.name as author_name, u.email as author_email,
+                cat.name AS category_name,
+                st.color AS stage_color,
+                resp.to_stage AS "response_stage?",
+                resp.response AS "response?",
+                resp.created_at AS "responded_at?",
+                (
+                    SELECT COUNT(*) FROM comments cc
+                    WHERE cc.idea_id = i.id AND cc.is_deleted = false AND cc.is_held = false
+                ) AS "comment_count!",
+                sort_key.rank AS "sort_rank!",
+                sort_key.pinned_at AS sort_pinned_at,
+                sort_key.votes AS "sort_votes!",
+                CASE WHEN $1 = '' THEN NULL ELSE ts_headline(
+                    'english',
+                    i.content || coalesce(' … ' || c.text, ''),
+                    search.query,
+                    $2
+                ) END AS snippet
+            FROM ideas i
+            INNER JOIN users u ON i.user_id = u.id
+            INNER JOIN categories cat ON cat.id = i.category_id
+            INNER JOIN stages st ON st.name = i.stage
+            CROSS JOIN LATERAL (SELECT websearch_to_tsquery('english', $1) AS query) search
+            CROSS JOIN LATERAL (
+                SELECT
+                    (CASE
+                        WHEN $9 = 'relevance' THEN ts_rank(i.search_vector, search.query)
+                        WHEN i.pinned_at IS NOT NULL THEN 1
+                        ELSE 0
+                    END)::float8 AS rank,
+                    (CASE WHEN $9 = 'relevance' THEN NULL ELSE i.pinned_at END) AS pinned_at,
+                    (CASE WHEN $9 = 'recent' THEN 0 ELSE i.vote_count END) AS votes
+            ) sort_key
+            LEFT JOIN LATERAL (
+                SELECT string_agg(cm.content, ' … ') AS text
+                FROM comments cm
+                WHERE cm.idea_id = i.id AND cm.is_deleted = false AND cm.is_held = false
+                  AND $1 <> '' AND to_tsvector('english', cm.content) @@ search.query
+            ) c ON true
+            LEFT JOIN LATERAL (
+                SELECT sc.to_stage, sc.response, sc.created_at
+                FROM stage_changes sc
+                WHERE sc.idea_id = i.id AND sc.response IS NOT NULL
+                ORDER BY sc.created_at DESC, sc.id DESC
+                LIMIT 1
+            ) resp ON true
+            WHERE i.is_public = true AND i.is_off_topic = false
+              AND ($1 = '' OR i.search_vector @@ search.query)
+              AND ($3::text IS NULL OR i.stage = $3)
+              AND ($16::text IS NULL OR i.category_id = (
+                  SELECT id FROM categories WHERE slug = $16
+              ))
+              AND ($4::text IS NULL OR EXISTS (
+                  SELECT 1 FROM idea_tags it
+                  INNER JOIN tags t ON t.id = it.tag_id
+                  WHERE it.idea_id = i.id AND t.slug = $4
+              ))
+              AND ($5::text IS NULL OR position(lower($5) IN lower(u.name)) > 0)
+              AND ($6::timestamptz IS NULL OR i.created_at >= $6)
+              AND ($7::timestamptz IS NULL OR i.created_at < $7)
+              AND ($8::int IS NULL OR EXISTS (
+                  SELECT 1 FROM votes v WHERE v.idea_id = i.id AND v.user_id = $8
+              ))
+              AND ($10::float8 IS NULL OR (
+                  sort_key.rank,
+                  coalesce(sort_key.pinned_at, '-infinity'),
+                  sort_key.votes,
+                  i.created_at,
+                  i.id
+              ) < ($10, coalesce($11::timestamptz, '-infinity'), $12, $13, $14))
+            ORDER BY
+                sort_key.rank DESC,
+                coalesce(sort_key.pinned_at, '-infinity') DESC,
+                sort_key.votes DESC,
+                i.created_at DESC,
+                i.id DESC
+            LIMIT $15
+            "#,
+            self.text.trim(),
+            headline_options,
+            self.stage,
+            self.tag.as_deref().map(slugify),
+            self.author,
+            start,
+            end,
+            voted_by,
+            sort.as_str(),
+            after.map(|cursor| cursor.rank),
+            after.and_then(|cursor| cursor.pinned_at),
+            after.map(|cursor| cursor.votes),
+            after.map(|cursor| cursor.created_at),
+            after.map(|cursor| cursor.id),
+            page_size + 1,
+            self.category
+        )
+        .fetch_all(crate::database::get_db())
+        .await?;
+
+        let has_more = rows.len() as i64 > page_size;
+        rows.truncate(page_size.max(0) as usize);
+        let next = rows.last().filter(|_| has_more).map(|r| IdeaCursor {
+            rank: r.sort_rank,
+            pinned_at: r.sort_pinned_at,
+            votes: r.sort_votes,
+            created_at: r.created_at,
+            id: r.id,
+        });
+
+        let ideas = rows
+            .into_iter()
+            .map(|r| IdeaSearchResult {
+                snippet: r
+                    .snippet
+                    .as_deref()
+                    .filter(|snippet| snippet.contains(MATCH_START))
+                    .map(split_snippet)
+                    .unwrap_or_default(),
+                comment_count: r.comment_count,
+                category_name: r.category_name,
+                stage_color: r.stage_color,
+                latest_response: match (r.response_stage, r.response, r.responded_at) {
+                    (Some(stage), Some(response), Some(responded_at)) => {
+                        Some(ResponseSummary::new(stage, &response, responded_at))
+                    }
+                    _ => None,
+                },
+                idea: IdeaWithAuthor {
+                    idea: Idea {
+                        id: r.id,
+                        user_id: r.user_id,
+                        title: r.title,
+                        content: r.content,
+                        tags: r.tags.clone(),
+                        stage: r.stage,
+                        is_public: r.is_public,
+                        is_off_topic: r.is_off_topic,
+                        pinned_at: r.pinned_at,
+                        created_at: r.created_at,
+                        vote_count: r.vote_count,
+                        comments_enabled: r.comments_enabled,
+                        edited_at: r.edited_at,
+                        moderation_reason: r.moderation_reason,
+                        is_pending: r.is_pending,
+                        merged_into: r.merged_into,
+                        category_id: r.category_id,
+                    },
+                    author_name: r.author_name,
+                    author_email: r.author_email,
+                },
+            })
+            .collect();
+
+        Ok(IdeaPage { ideas, next })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_snippet, IdeaSearch, IdeaSort, SnippetPart};
+
+    fn part(text: &str, highlighted: bool) -> SnippetPart {
+        SnippetPart {
+            text: text.to_string(),
+            highlighted,
+        }
+    }
+
+    #[test]
+    fn snippets_split_at_match_markers() {
+        assert_eq!(
+            split_snippet("add \u{2}dark\u{3} \u{2}mode\u{3} please"),
+            vec![
+                part("add ", false),
+                part("dark", true),
+                part(" ", false),
+                part("mode", true),
+                part(" please", false),
+            ]
+        );
+        assert_eq!(split_snippet("no match"), vec![part("no match", false)]);
+    }
+
+    #[test]
+    fn unterminated_match_runs_to_the_end() {
+        assert_eq!(
+            split_snippet("see \u{2}canvas"),
+            vec![part("see ", false), part("canvas", true)]
+        );
+    }
+
+    #[test]
+    fn query_round_trips() {
+        let search = IdeaSearch {
+            text: "dark mode".to_string(),
+            sort: IdeaSort::Recent,
+            stage: Some("Review".to_string()),
+            category: Some("teaching-technology".to_string()),
+            tag: Some("canvas".to_string()),
+            author: None,
+            posted_from: "2026-01-01".parse().ok(),
+            posted_to: None,
+            voted_by_me: true,
+        };
+        let pairs = search.to_query();
+        let parsed = IdeaSearch::from_query(|key| {
+            pairs
+                .iter()
+                .find(|(name, _)| *name == key)
+                .map(|(_, value)| value.clone())
+        });
+        assert_eq!(parsed, search);
+    }
+
+    #[test]
+    fn blank_and_malformed_values_are_ignored() {
+        let search = IdeaSearch::from_query(|key| match key {
+            "q" => Some("  ".to_string()),
+            "sort" => Some("sideways".to_string()),
+            "from" => Some("last tuesday".to_string()),
+            "voted" => Some("yes".to_string()),
+            _ => None,
+        });
+        assert_eq!(search, IdeaSearch::default());
+        assert!(!search.is_filtered());
+        assert!(search.to_query().is_empty());
+    }
+
+    #[test]
+    fn sort_alone_is_not_a_filter() {
+        let search = IdeaSearch {
+            sort: IdeaSort::Recent,
+            ..IdeaSearch::default()
+        };
+        assert!(!search.is_filtered());
+        assert_eq!(search.to_query(), vec![("sort", "recent".to_string())]);
+    }
+}
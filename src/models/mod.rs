@@ -1,12 +1,57 @@
 mod idea;
-pub use idea::{Idea, IdeaWithAuthor};
+pub use idea::{DuplicatePair, Idea, IdeaWithAuthor, SimilarIdea};
+mod idea_search;
+pub use idea_search::{IdeaCursor, IdeaPage, IdeaSearch, IdeaSearchResult, IdeaSort, SnippetPart};
 mod vote;
 #[cfg(feature = "ssr")]
 pub use vote::Vote;
+mod edit_window;
+pub use edit_window::EditWindow;
+#[cfg(feature = "ssr")]
+mod auto_hide;
+#[cfg(feature = "ssr")]
+pub use auto_hide::{flag_weight, AutoHidePolicy};
+#[cfg(feature = "ssr")]
+mod pre_moderation;
+#[cfg(feature = "ssr")]
+pub use pre_moderation::{AuthorTrust, PreModerationPolicy};
+mod category;
+pub use category::{
+    scope_covers, Category, CategoryModerator, CategoryWithCount, MAX_CATEGORY_NAME_CHARS,
+};
 mod comment;
-pub use comment::{Comment, CommentWithAuthor};
+pub use comment::{
+    thread_comments, Comment, CommentSort, CommentThread, CommentWithAuthor, CommentWithIdea,
+};
+mod comment_vote;
+#[cfg(feature = "ssr")]
+pub use comment_vote::CommentVote;
 mod user;
 pub use user::User;
 mod flag;
 #[cfg(feature = "ssr")]
 pub use flag::Flag;
+pub use flag::FlagReason;
+mod revision;
+pub use revision::{CommentRevision, IdeaRevision};
+mod moderation_log;
+pub use moderation_log::{ModerationAction, ModerationLogEntry, ModerationLogFilter};
+mod email_notification;
+#[cfg(feature = "ssr")]
+pub use email_notification::EmailNotification;
+mod notification;
+pub use notification::{Notification, NotificationPreference, NotificationSummary};
+mod roadmap;
+pub use roadmap::{Roadmap, RoadmapIdea, RECENTLY_COMPLETED_DAYS};
+mod session;
+pub use session::Session;
+mod stage;
+pub use stage::{
+    find_stage, is_last_open_stage, next_stages, Stage, StageColor, MAX_STAGE_NAME_CHARS,
+};
+mod stage_change;
+pub use stage_change::{ResponseSummary, StageChange, MAX_RESPONSE_CHARS};
+mod tag;
+pub use tag::{parse_tag_names, slugify, Tag, TagWithCount, MAX_TAGS_PER_IDEA, MAX_TAG_CHARS};
+mod word_filter;
+pub use word_filter::{FilterMatch, FilterSeverity, MatchMode, WordFilter};
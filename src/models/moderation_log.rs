@@ -0,0 +1,247 @@
+use serde::{Deserialize, Serialize};
+
+/// Moderator and admin actions that are written to the audit log.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModerationAction {
+    DeleteIdea,
+    SetOffTopic,
+    UpdateStage,
+    TogglePin,
+    ClearFlags,
+    UpdateUserRole,
+    DeleteUser,
+    DeleteComment,
+    ToggleComments,
+    ApproveIdea,
+    RejectIdea,
+    WordFilterMatch,
+    AddWordFilter,
+    RemoveWordFilter,
+    MergeIdea,
+    RenameTag,
+    MergeTags,
+    RetireTag,
+    RestoreTag,
+    CreateCategory,
+    EditCategory,
+    ArchiveCategory,
+    RestoreCategory,
+    AssignCategoryModerator,
+    RemoveCategoryModerator,
+    CreateStage,
+    EditStage,
+    ReorderStage,
+    SetStageTransitions,
+    DeleteStage,
+}
+
+impl ModerationAction {
+    pub const ALL: [ModerationAction; 30] = [
+        Self::DeleteIdea,
+        Self::SetOffTopic,
+        Self::UpdateStage,
+        Self::TogglePin,
+        Self::ClearFlags,
+        Self::UpdateUserRole,
+        Self::DeleteUser,
+        Self::DeleteComment,
+        Self::ToggleComments,
+        Self::ApproveIdea,
+        Self::RejectIdea,
+        Self::WordFilterMatch,
+        Self::AddWordFilter,
+        Self::RemoveWordFilter,
+        Self::MergeIdea,
+        Self::RenameTag,
+        Self::MergeTags,
+        Self::RetireTag,
+        Self::RestoreTag,
+        Self::CreateCategory,
+        Self::EditCategory,
+        Self::ArchiveCategory,
+        Self::RestoreCategory,
+        Self::AssignCategoryModerator,
+        Self::RemoveCategoryModerator,
+        Self::CreateStage,
+        Self::EditStage,
+        Self::ReorderStage,
+        Self::SetStageTransitions,
+        Self::DeleteStage,
+    ];
+
+    /// Value stored in `moderation_log.action`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::DeleteIdea => "delete_idea",
+            Self::SetOffTopic => "set_off_topic",
+            Self::UpdateStage => "update_stage",
+            Self::TogglePin => "toggle_pin",
+            Self::ClearFlags => "clear_flags",
+            Self::UpdateUserRole => "update_user_role",
+            Self::DeleteUser => "delete_user",
+            Self::DeleteComment => "delete_comment",
+            Self::ToggleComments => "toggle_comments",
+            Self::ApproveIdea => "approve_idea",
+            Self::RejectIdea => "reject_idea",
+            Self::WordFilterMatch => "word_filter_match",
+            Self::AddWordFilter => "add_word_filter",
+            Self::RemoveWordFilter => "remove_word_filter",
+            Self::MergeIdea => "merge_idea",
+            Self::RenameTag => "rename_tag",
+            Self::MergeTags => "merge_tags",
+            Self::RetireTag => "retire_tag",
+            Self::RestoreTag => "restore_tag",
+            Self::CreateCategory => "create_category",
+            Self::EditCategory => "edit_category",
+            Self::ArchiveCategory => "archive_category",
+            Self::RestoreCategory => "restore_category",
+            Self::AssignCategoryModerator => "assign_category_moderator",
+            Self::RemoveCategoryModerator => "remove_category_moderator",
+            Self::CreateStage => "create_stage",
+            Self::EditStage => "edit_stage",
+            Self::ReorderStage => "reorder_stage",
+            Self::SetStageTransitions => "set_stage_transitions",
+            Self::DeleteStage => "delete_stage",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::DeleteIdea => "Delete idea",
+            Self::SetOffTopic => "Off-topic",
+            Self::UpdateStage => "Change stage",
+            Self::TogglePin => "Pin / unpin",
+            Self::ClearFlags => "Clear flags",
+            Self::UpdateUserRole => "Change role",
+            Self::DeleteUser => "Delete user",
+            Self::DeleteComment => "Delete comment",
+            Self::ToggleComments => "Lock / unlock comments",
+            Self::ApproveIdea => "Approve idea",
+            Self::RejectIdea => "Reject idea",
+            Self::WordFilterMatch => "Word filter match",
+            Self::AddWordFilter => "Add filter term",
+            Self::RemoveWordFilter => "Remove filter term",
+            Self::MergeIdea => "Merge ideas",
+            Self::RenameTag => "Rename tag",
+            Self::MergeTags => "Merge tags",
+            Self::RetireTag => "Retire tag",
+            Self::RestoreTag => "Restore tag",
+            Self::CreateCategory => "Create category",
+            Self::EditCategory => "Edit category",
+            Self::ArchiveCategory => "Archive category",
+            Self::RestoreCategory => "Restore category",
+            Self::AssignCategoryModerator => "Assign category moderator",
+            Self::RemoveCategoryModerator => "Remove category moderator",
+            Self::CreateStage => "Create stage",
+            Self::EditStage => "Edit stage",
+            Self::ReorderStage => "Reorder stage",
+            Self::SetStageTransitions => "Change stage transitions",
+            Self::DeleteStage => "Delete stage",
+        }
+    }
+
+    pub fn from_name(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.as_str() == value)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ModerationLogEntry {
+    pub id: i32,
+    pub actor_id: Option<i32>,
+    pub actor_name: Option<String>,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: i32,
+    pub before_value: Option<String>,
+    pub after_value: Option<String>,
+    pub reason: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Optional filters for listing the audit log.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModerationLogFilter {
+    pub action: Option<String>,
+    pub target_type: Option<String>,
+    pub actor: Option<String>,
+}
+
+#[cfg(feature = "ssr")]
+impl ModerationLogEntry {
+    /// Append an entry to the audit log
+    pub async fn record(
+        actor_id: i32,
+        action: ModerationAction,
+        target_type: &str,
+        target_id: i32,
+        before_value: Option<String>,
+        after_value: Option<String>,
+        reason: Option<String>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO moderation_log
+                (actor_id, action, target_type, target_id, before_value, after_value, reason)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            actor_id,
+            action.as_str(),
+            target_type,
+            target_id,
+            before_value,
+            after_value,
+            reason
+        )
+        .execute(crate::database::get_db())
+        .await?;
+        Ok(())
+    }
+
+    /// Get the most recent entries matching `filter`, newest first
+    pub async fn list(filter: &ModerationLogFilter, limit: i64) -> Result<Vec<Self>, sqlx::Error> {
+        let actor_pattern = filter
+            .actor
+            .as_deref()
+            .map(str::trim)
+            .filter(|actor| !actor.is_empty())
+            .map(|actor| format!("%{actor}%"));
+
+        sqlx::query_as!(
+            ModerationLogEntry,
+            r#"
+            SELECT
+                l.id, l.actor_id, u.name as "actor_name?", l.action, l.target_type, l.target_id,
+                l.before_value, l.after_value, l.reason, l.created_at
+            FROM moderation_log l
+            LEFT JOIN users u ON l.actor_id = u.id
+            WHERE ($1::TEXT IS NULL OR l.action = $1)
+              AND ($2::TEXT IS NULL OR l.target_type = $2)
+              AND ($3::TEXT IS NULL OR u.name ILIKE $3 OR u.email ILIKE $3)
+            ORDER BY l.created_at DESC, l.id DESC
+            LIMIT $4
+            "#,
+            filter.action,
+            filter.target_type,
+            actor_pattern,
+            limit
+        )
+        .fetch_all(crate::database::get_db())
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ModerationAction;
+
+    #[test]
+    fn action_names_round_trip() {
+        for action in ModerationAction::ALL {
+            assert_eq!(ModerationAction::from_name(action.as_str()), Some(action));
+        }
+        assert_eq!(ModerationAction::from_name("unknown"), None);
+    }
+}
@@ -0,0 +1,154 @@
+use serde::{Deserialize, Serialize};
+
+/// A message shown to one user, e.g. the reason a moderator hid their content.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct Notification {
+    pub id: i32,
+    pub user_id: i32,
+    pub message: String,
+    pub link: Option<String>,
+    pub is_read: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// How a user wants their notifications emailed. They are always shown in
+/// the app.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NotificationPreference {
+    /// One email per notification
+    Instant,
+    /// One summary email a day
+    Daily,
+    /// In-app only
+    Off,
+}
+
+impl NotificationPreference {
+    pub const ALL: [NotificationPreference; 3] = [Self::Instant, Self::Daily, Self::Off];
+
+    /// Value stored in `users.notification_email`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Instant => "instant",
+            Self::Daily => "daily",
+            Self::Off => "off",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Instant => "Email me right away",
+            Self::Daily => "Email me a daily digest",
+            Self::Off => "Don't email me",
+        }
+    }
+
+    #[cfg(feature = "ssr")]
+    pub fn from_name(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|preference| preference.as_str() == value)
+    }
+}
+
+/// Unread count and latest notifications for the NavBar bell.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct NotificationSummary {
+    pub unread: i64,
+    pub recent: Vec<Notification>,
+}
+
+#[cfg(feature = "ssr")]
+impl Notification {
+    /// Notify several users with the same message
+    pub async fn create_many(
+        user_ids: &[i32],
+        message: &str,
+        link: Option<&str>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Notification,
+            r#"
+            INSERT INTO notifications (user_id, message, link)
+            SELECT user_id, $2, $3 FROM UNNEST($1::INT[]) AS user_id
+            RETURNING id, user_id, message, link, is_read, created_at
+            "#,
+            user_ids,
+            message,
+            link
+        )
+        .fetch_all(crate::database::get_db())
+        .await
+    }
+
+    /// Get a user's latest notifications, newest first
+    pub async fn get_recent_for_user(user_id: i32, limit: i64) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Notification,
+            r#"
+            SELECT id, user_id, message, link, is_read, created_at
+            FROM notifications
+            WHERE user_id = $1
+            ORDER BY created_at DESC, id DESC
+            LIMIT $2
+            "#,
+            user_id,
+            limit
+        )
+        .fetch_all(crate::database::get_db())
+        .await
+    }
+
+    pub async fn count_unread(user_id: i32) -> Result<i64, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT COUNT(*) AS "count!" FROM notifications WHERE user_id = $1 AND is_read = false"#,
+            user_id
+        )
+        .fetch_one(crate::database::get_db())
+        .await?;
+        Ok(row.count)
+    }
+
+    /// Notifications from the last `since` that have not been emailed yet,
+    /// oldest first
+    pub async fn get_unemailed_for_user(
+        user_id: i32,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Notification,
+            r#"
+            SELECT id, user_id, message, link, is_read, created_at
+            FROM notifications
+            WHERE user_id = $1 AND emailed_at IS NULL AND created_at >= $2
+            ORDER BY created_at, id
+            "#,
+            user_id,
+            since
+        )
+        .fetch_all(crate::database::get_db())
+        .await
+    }
+
+    pub async fn mark_emailed(ids: &[i32]) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE notifications SET emailed_at = NOW() WHERE id = ANY($1)",
+            ids
+        )
+        .execute(crate::database::get_db())
+        .await?;
+        Ok(())
+    }
+
+    /// Mark all of a user's notifications as read
+    pub async fn mark_all_read(user_id: i32) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE notifications SET is_read = true WHERE user_id = $1 AND is_read = false",
+            user_id
+        )
+        .execute(crate::database::get_db())
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+/// What the pre-moderation policy needs to know about an idea's author.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuthorTrust {
+    pub role: i16,
+    pub is_cas_linked: bool,
+    pub created_on: chrono::DateTime<chrono::Utc>,
+    /// Ideas by this author that were published without or after review
+    pub approved_ideas: i64,
+}
+
+/// Which new ideas wait for a moderator's approval before they are published.
+/// Both limits default to 0, which turns pre-moderation off.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PreModerationPolicy {
+    /// Accounts younger than this many days need approval
+    pub min_account_days: i64,
+    /// Authors with fewer approved ideas than this need approval
+    pub min_approved_ideas: i64,
+}
+
+impl PreModerationPolicy {
+    /// Whether a new idea by `author` should be held for approval. Moderators
+    /// and CAS-linked accounts are trusted and never wait.
+    pub fn requires_approval(
+        &self,
+        author: &AuthorTrust,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        if author.role >= 1 || author.is_cas_linked {
+            return false;
+        }
+        let too_new = now < author.created_on + chrono::Duration::days(self.min_account_days);
+        let too_few = author.approved_ideas < self.min_approved_ideas;
+        too_new || too_few
+    }
+
+    /// Read the policy from `PREMODERATION_MIN_ACCOUNT_DAYS` and
+    /// `PREMODERATION_MIN_APPROVED_IDEAS`.
+    pub fn from_env() -> Self {
+        let limit = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.parse::<i64>().ok())
+                .filter(|limit| *limit >= 0)
+                .unwrap_or_default()
+        };
+        Self {
+            min_account_days: limit("PREMODERATION_MIN_ACCOUNT_DAYS"),
+            min_approved_ideas: limit("PREMODERATION_MIN_APPROVED_IDEAS"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AuthorTrust, PreModerationPolicy};
+
+    fn author(role: i16, is_cas_linked: bool, age_days: i64, approved_ideas: i64) -> AuthorTrust {
+        AuthorTrust {
+            role,
+            is_cas_linked,
+            created_on: chrono::Utc::now() - chrono::Duration::days(age_days),
+            approved_ideas,
+        }
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let policy = PreModerationPolicy::default();
+        assert!(!policy.requires_approval(&author(0, false, 0, 0), chrono::Utc::now()));
+    }
+
+    #[test]
+    fn holds_new_or_unproven_accounts() {
+        let policy = PreModerationPolicy {
+            min_account_days: 7,
+            min_approved_ideas: 2,
+        };
+        let now = chrono::Utc::now();
+        assert!(policy.requires_approval(&author(0, false, 1, 5), now));
+        assert!(policy.requires_approval(&author(0, false, 30, 1), now));
+        assert!(!policy.requires_approval(&author(0, false, 30, 2), now));
+    }
+
+    #[test]
+    fn trusted_accounts_bypass_the_queue() {
+        let policy = PreModerationPolicy {
+            min_account_days: 7,
+            min_approved_ideas: 2,
+        };
+        let now = chrono::Utc::now();
+        assert!(!policy.requires_approval(&author(0, true, 0, 0), now));
+        assert!(!policy.requires_approval(&author(1, false, 0, 0), now));
+    }
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+/// A previous version of an idea, saved just before it was edited.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IdeaRevision {
+    pub id: i32,
+    pub idea_id: i32,
+    pub editor_id: Option<i32>,
+    pub editor_name: Option<String>,
+    pub title: String,
+    pub content: String,
+    pub tags: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A previous version of a comment, saved just before it was edited.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CommentRevision {
+    pub id: i32,
+    pub comment_id: i32,
+    pub editor_id: Option<i32>,
+    pub editor_name: Option<String>,
+    pub content: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[cfg(feature = "ssr")]
+impl IdeaRevision {
+    /// Save the current version of an idea before `editor_id` changes it
+    pub async fn record(
+        tx: &mut sqlx::PgConnection,
+        idea_id: i32,
+        editor_id: i32,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO idea_revisions (idea_id, editor_id, title, content, tags)
+            SELECT id, $2, title, content, tags FROM ideas WHERE id = $1
+            "#,
+            idea_id,
+            editor_id
+        )
+        .execute(tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Get all saved versions of an idea, oldest first
+    pub async fn get_by_idea_id(idea_id: i32) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            IdeaRevision,
+            r#"
+            SELECT
+                r.id, r.idea_id, r.editor_id, u.name as "editor_name?",
+                r.title, r.content, r.tags, r.created_at
+            FROM idea_revisions r
+            LEFT JOIN users u ON r.editor_id = u.id
+            WHERE r.idea_id = $1
+            ORDER BY r.created_at ASC, r.id ASC
+            "#,
+            idea_id
+        )
+        .fetch_all(crate::database::get_db())
+        .await
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl CommentRevision {
+    /// Save the current version of a comment before `editor_id` changes it
+    pub async fn record(
+        tx: &mut sqlx::PgConnection,
+        comment_id: i32,
+        editor_id: i32,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO comment_revisions (comment_id, editor_id, content)
+            SELECT id, $2, content FROM comments WHERE id = $1
+            "#,
+            comment_id,
+            editor_id
+        )
+        .execute(tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Get all saved versions of a comment, oldest first
+    pub async fn get_by_comment_id(comment_id: i32) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            CommentRevision,
+            r#"
+            SELECT
+                r.id, r.comment_id, r.editor_id, u.name as "editor_name?",
+                r.content, r.created_at
+            FROM comment_revisions r
+            LEFT JOIN users u ON r.editor_id = u.id
+            WHERE r.comment_id = $1
+            ORDER BY r.created_at ASC, r.id ASC
+            "#,
+            comment_id
+        )
+        .fetch_all(crate::database::get_db())
+        .await
+    }
+}
@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+
+use super::{scope_covers, Stage};
+
+/// How long a closed idea stays in the roadmap's recently completed lane
+pub const RECENTLY_COMPLETED_DAYS: i64 = 30;
+
+/// A public idea as a card on the roadmap.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RoadmapIdea {
+    pub id: i32,
+    pub title: String,
+    pub stage: String,
+    pub vote_count: i32,
+    pub category_id: i32,
+    pub category_name: String,
+    /// When the idea moved into its current stage; `None` if it never moved
+    pub entered_stage_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Everything the roadmap page shows.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Roadmap {
+    /// Every stage in workflow order, closing stages included
+    pub stages: Vec<Stage>,
+    /// Public ideas in stages that do not close them, most votes first
+    pub open: Vec<RoadmapIdea>,
+    /// Ideas closed within [`RECENTLY_COMPLETED_DAYS`], newest first
+    pub recently_completed: Vec<RoadmapIdea>,
+    /// Categories the viewer moderates: `None` if they are not a moderator,
+    /// empty if they cover every category
+    pub moderator_scope: Option<Vec<i32>>,
+}
+
+impl Roadmap {
+    /// One column per open stage in workflow order, each with its ideas.
+    /// Ideas keep their order within a column.
+    pub fn columns(&self) -> Vec<(&Stage, Vec<&RoadmapIdea>)> {
+        self.stages
+            .iter()
+            .filter(|stage| !stage.is_terminal)
+            .map(|stage| {
+                let ideas = self
+                    .open
+                    .iter()
+                    .filter(|idea| idea.stage == stage.name)
+                    .collect();
+                (stage, ideas)
+            })
+            .collect()
+    }
+
+    /// Whether the viewer may move `idea` to another stage
+    pub fn can_move(&self, idea: &RoadmapIdea) -> bool {
+        self.moderator_scope
+            .as_deref()
+            .is_some_and(|scope| scope_covers(scope, idea.category_id))
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl RoadmapIdea {
+    /// Public ideas in stages that do not close them, most votes first
+    pub async fn get_open() -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            RoadmapIdea,
+            r#"
+            SELECT i.id, i.title, i.stage, i.vote_count, i.category_id,
+                   cat.name AS category_name,
+                   (
+                       SELECT MAX(sc.created_at) FROM stage_changes sc
+                       WHERE sc.idea_id = i.id AND sc.to_stage = i.stage
+                   ) AS entered_stage_at
+            FROM ideas i
+            INNER JOIN stages st ON st.name = i.stage
+            INNER JOIN categories cat ON cat.id = i.category_id
+            WHERE i.is_public = true AND i.is_off_topic = false AND st.is_terminal = false
+            ORDER BY i.vote_count DESC, i.created_at DESC, i.id DESC
+            "#
+        )
+        .fetch_all(crate::database::get_db())
+        .await
+    }
+
+    /// Public ideas that moved into a closing stage within the last `days`
+    /// days, most recently closed first
+    pub async fn get_recently_completed(days: i64) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            RoadmapIdea,
+            r#"
+            SELECT i.id, i.title, i.stage, i.vote_count, i.category_id,
+                   cat.name AS category_name, closed.at AS entered_stage_at
+            FROM ideas i
+            INNER JOIN stages st ON st.name = i.stage
+            INNER JOIN categories cat ON cat.id = i.category_id
+            CROSS JOIN LATERAL (
+                SELECT MAX(sc.created_at) AS at FROM stage_changes sc
+                WHERE sc.idea_id = i.id AND sc.to_stage = i.stage
+            ) closed
+            WHERE i.is_public = true AND i.is_off_topic = false AND st.is_terminal = true
+              AND closed.at >= NOW() - make_interval(days => $1::int)
+            ORDER BY closed.at DESC, i.id DESC
+            "#,
+            days as i32
+        )
+        .fetch_all(crate::database::get_db())
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Roadmap, RoadmapIdea};
+    use crate::models::Stage;
+
+    fn stage(id: i32, name: &str, is_terminal: bool) -> Stage {
+        Stage {
+            id,
+            name: name.to_string(),
+            position: id,
+            color: "gray".to_string(),
+            is_terminal,
+            next_stage_ids: Vec::new(),
+        }
+    }
+
+    fn idea(id: i32, stage: &str) -> RoadmapIdea {
+        RoadmapIdea {
+            id,
+            title: format!("Idea {id}"),
+            stage: stage.to_string(),
+            vote_count: 0,
+            category_id: 1,
+            category_name: "General".to_string(),
+            entered_stage_at: None,
+        }
+    }
+
+    #[test]
+    fn columns_cover_open_stages_in_order_and_keep_idea_order() {
+        let roadmap = Roadmap {
+            stages: vec![
+                stage(1, "Ideate", false),
+                stage(2, "In Progress", false),
+                stage(3, "Shipped", true),
+            ],
+            open: vec![
+                idea(7, "In Progress"),
+                idea(4, "Ideate"),
+                idea(9, "In Progress"),
+            ],
+            recently_completed: vec![idea(2, "Shipped")],
+            moderator_scope: None,
+        };
+        let columns: Vec<(&str, Vec<i32>)> = roadmap
+            .columns()
+            .into_iter()
+            .map(|(stage, ideas)| {
+                (
+                    stage.name.as_str(),
+                    ideas.into_iter().map(|idea| idea.id).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            columns,
+            vec![("Ideate", vec![4]), ("In Progress", vec![7, 9])]
+        );
+    }
+
+    #[test]
+    fn only_moderators_of_the_category_can_move_an_idea() {
+        let roadmap = |moderator_scope| Roadmap {
+            stages: Vec::new(),
+            open: Vec::new(),
+            recently_completed: Vec::new(),
+            moderator_scope,
+        };
+        let other_category = RoadmapIdea {
+            category_id: 2,
+            ..idea(1, "Ideate")
+        };
+        assert!(!roadmap(None).can_move(&idea(1, "Ideate")));
+        assert!(roadmap(Some(vec![])).can_move(&other_category));
+        assert!(roadmap(Some(vec![1])).can_move(&idea(1, "Ideate")));
+        assert!(!roadmap(Some(vec![1])).can_move(&other_category));
+    }
+}
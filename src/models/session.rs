@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+
+/// A server-side login session. The opaque `token` is only ever sent to the
+/// browser inside the signed session cookie, never in server function payloads.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct Session {
+    pub id: i32,
+    #[serde(skip_serializing, default)]
+    pub token: String,
+    pub user_id: i32,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub last_seen: chrono::DateTime<chrono::Utc>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[cfg(feature = "ssr")]
+impl Session {
+    /// Start a new session for a user, pruning any expired sessions first.
+    pub async fn create(
+        user_id: i32,
+        user_agent: Option<String>,
+        ip_address: Option<String>,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!("DELETE FROM sessions WHERE expires_at <= NOW()")
+            .execute(crate::database::get_db())
+            .await?;
+
+        sqlx::query_as!(
+            Session,
+            r#"
+            INSERT INTO sessions (user_id, user_agent, ip_address, expires_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, token, user_id, user_agent, ip_address, created_at, last_seen, expires_at
+            "#,
+            user_id,
+            user_agent,
+            ip_address,
+            expires_at
+        )
+        .fetch_one(crate::database::get_db())
+        .await
+    }
+
+    /// Look up a live session by token, record that it was just used, and
+    /// load the owning user's current row so role changes apply immediately.
+    pub async fn touch_with_user(
+        token: &str,
+    ) -> Result<Option<(i32, crate::models::User)>, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE sessions s
+            SET last_seen = NOW()
+            FROM users u
+            WHERE s.token = $1 AND s.expires_at > NOW() AND u.id = s.user_id
+            RETURNING s.id AS session_id, u.id, u.email, u.name, u.role, u.created_on
+            "#,
+            token
+        )
+        .fetch_optional(crate::database::get_db())
+        .await?;
+
+        Ok(row.map(|row| {
+            (
+                row.session_id,
+                crate::models::User {
+                    id: row.id,
+                    email: row.email,
+                    name: row.name,
+                    password_hash: None,
+                    role: row.role,
+                    created_on: row.created_on,
+                },
+            )
+        }))
+    }
+
+    /// Active sessions for a user, most recently used first.
+    pub async fn get_active_by_user(user_id: i32) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Session,
+            r#"
+            SELECT id, token, user_id, user_agent, ip_address, created_at, last_seen, expires_at
+            FROM sessions
+            WHERE user_id = $1 AND expires_at > NOW()
+            ORDER BY last_seen DESC
+            "#,
+            user_id
+        )
+        .fetch_all(crate::database::get_db())
+        .await
+    }
+
+    /// Revoke the session identified by its cookie token (logout).
+    pub async fn revoke_by_token(token: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!("DELETE FROM sessions WHERE token = $1", token)
+            .execute(crate::database::get_db())
+            .await?;
+        Ok(())
+    }
+
+    /// Revoke one of a user's own sessions by ID.
+    pub async fn revoke_for_user(id: i32, user_id: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM sessions WHERE id = $1 AND user_id = $2",
+            id,
+            user_id
+        )
+        .execute(crate::database::get_db())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Revoke every session of a user except one (after a password change).
+    pub async fn revoke_others_for_user(user_id: i32, keep_id: i32) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM sessions WHERE user_id = $1 AND id <> $2",
+            user_id,
+            keep_id
+        )
+        .execute(crate::database::get_db())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Revoke every session belonging to a user ("log out everywhere").
+    pub async fn revoke_all_for_user(user_id: i32) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM sessions WHERE user_id = $1", user_id)
+            .execute(crate::database::get_db())
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
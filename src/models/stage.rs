@@ -0,0 +1,364 @@
+use serde::{Deserialize, Serialize};
+
+/// Longest stage name, in characters
+pub const MAX_STAGE_NAME_CHARS: usize = 50;
+
+/// Badge colors a stage can use; each has a `stage-<name>` class in the
+/// stylesheet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StageColor {
+    Evergreen,
+    SkyBlue,
+    Gold,
+    DeepGold,
+    CampusGreen,
+    Gray,
+    Red,
+}
+
+impl StageColor {
+    pub const ALL: [StageColor; 7] = [
+        Self::Evergreen,
+        Self::SkyBlue,
+        Self::Gold,
+        Self::DeepGold,
+        Self::CampusGreen,
+        Self::Gray,
+        Self::Red,
+    ];
+
+    /// Value stored in `stages.color`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Evergreen => "evergreen",
+            Self::SkyBlue => "sky-blue",
+            Self::Gold => "gold",
+            Self::DeepGold => "deep-gold",
+            Self::CampusGreen => "campus-green",
+            Self::Gray => "gray",
+            Self::Red => "red",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Evergreen => "Evergreen",
+            Self::SkyBlue => "Sky blue",
+            Self::Gold => "Gold",
+            Self::DeepGold => "Deep gold",
+            Self::CampusGreen => "Campus green",
+            Self::Gray => "Gray",
+            Self::Red => "Red",
+        }
+    }
+
+    pub fn from_name(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|color| color.as_str() == value)
+    }
+}
+
+/// A step in the idea workflow. Ideas store the stage's name.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Stage {
+    pub id: i32,
+    pub name: String,
+    /// Order on the board and roadmap, lowest first
+    pub position: i32,
+    /// One of [`StageColor`]'s names
+    pub color: String,
+    /// The idea is closed, whether it shipped or was declined
+    pub is_terminal: bool,
+    /// Stages an idea here may move to, in workflow order
+    pub next_stage_ids: Vec<i32>,
+}
+
+impl Stage {
+    /// Whether an idea in this stage may move to `to`
+    pub fn allows(&self, to: &Stage) -> bool {
+        self.next_stage_ids.contains(&to.id)
+    }
+}
+
+/// The stage named `name`, if it still exists.
+pub fn find_stage<'a>(stages: &'a [Stage], name: &str) -> Option<&'a Stage> {
+    stages.iter().find(|stage| stage.name == name)
+}
+
+/// Stages an idea in `current` may move to, in workflow order.
+pub fn next_stages<'a>(stages: &'a [Stage], current: &str) -> Vec<&'a Stage> {
+    match find_stage(stages, current) {
+        Some(from) => stages.iter().filter(|to| from.allows(to)).collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Whether the stage with `id` is the only open one, so closing or deleting
+/// it would leave new ideas no stage to start in.
+pub fn is_last_open_stage(stages: &[Stage], id: i32) -> bool {
+    let mut open = stages.iter().filter(|stage| !stage.is_terminal);
+    matches!((open.next(), open.next()), (Some(only), None) if only.id == id)
+}
+
+#[cfg(feature = "ssr")]
+impl Stage {
+    /// Name of the first open stage in the workflow, where new ideas start,
+    /// or `None` if every stage is terminal
+    pub async fn first_open() -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar!(
+            "SELECT name FROM stages WHERE is_terminal = false ORDER BY position, id LIMIT 1"
+        )
+        .fetch_optional(crate::database::get_db())
+        .await
+    }
+
+    /// Every stage with its transitions, in workflow order
+    pub async fn get_all() -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Stage,
+            r#"
+            SELECT s.id, s.name, s.position, s.color, s.is_terminal,
+                   ARRAY(
+                       SELECT t.to_stage_id
+                       FROM stage_transitions t
+                       INNER JOIN stages n ON n.id = t.to_stage_id
+                       WHERE t.from_stage_id = s.id
+                       ORDER BY n.position, n.id
+                   ) AS "next_stage_ids!"
+            FROM stages s
+            ORDER BY s.position, s.id
+            "#
+        )
+        .fetch_all(crate::database::get_db())
+        .await
+    }
+
+    /// Add a stage at the end of the workflow, with no transitions yet
+    pub async fn create(name: &str, color: &str, is_terminal: bool) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Stage,
+            r#"
+            INSERT INTO stages (name, position, color, is_terminal)
+            SELECT $1, COALESCE(MAX(position), 0) + 1, $2, $3 FROM stages
+            RETURNING id, name, position, color, is_terminal,
+                      ARRAY[]::integer[] AS "next_stage_ids!"
+            "#,
+            name,
+            color,
+            is_terminal
+        )
+        .fetch_one(crate::database::get_db())
+        .await
+    }
+
+    /// Change a stage's name, color and terminal flag. Ideas in the stage
+    /// follow a rename. Returns the stage as it was, or `None` if it does
+    /// not exist; fails with a unique violation if the name is taken.
+    pub async fn update(
+        id: i32,
+        name: &str,
+        color: &str,
+        is_terminal: bool,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let mut tx = crate::database::get_db().begin().await?;
+        let before = sqlx::query_as!(
+            Stage,
+            r#"
+            SELECT id, name, position, color, is_terminal,
+                   ARRAY[]::integer[] AS "next_stage_ids!"
+            FROM stages
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        if before.is_none() {
+            return Ok(None);
+        }
+
+        sqlx::query!(
+            "UPDATE stages SET name = $1, color = $2, is_terminal = $3 WHERE id = $4",
+            name,
+            color,
+            is_terminal,
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(before)
+    }
+
+    /// Swap a stage with its neighbour, earlier or later in the workflow.
+    /// Returns the stage's name, or `None` if it does not exist; a stage
+    /// already at that end stays put.
+    pub async fn move_stage(id: i32, earlier: bool) -> Result<Option<String>, sqlx::Error> {
+        let mut tx = crate::database::get_db().begin().await?;
+        sqlx::query!("LOCK TABLE stages IN SHARE ROW EXCLUSIVE MODE")
+            .execute(&mut *tx)
+            .await?;
+
+        let Some(stage) = sqlx::query!("SELECT name, position FROM stages WHERE id = $1", id)
+            .fetch_optional(&mut *tx)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let neighbour = sqlx::query!(
+            r#"
+            SELECT id, position FROM stages
+            WHERE CASE WHEN $2 THEN position < $1 ELSE position > $1 END
+            ORDER BY CASE WHEN $2 THEN -position ELSE position END
+            LIMIT 1
+            "#,
+            stage.position,
+            earlier
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(neighbour) = neighbour {
+            sqlx::query!(
+                "UPDATE stages SET position = $1 WHERE id = $2",
+                neighbour.position,
+                id
+            )
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query!(
+                "UPDATE stages SET position = $1 WHERE id = $2",
+                stage.position,
+                neighbour.id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(Some(stage.name))
+    }
+
+    /// Replace the stages an idea in stage `id` may move to. Returns the
+    /// names of the stages allowed before and after, or `None` if the stage
+    /// does not exist.
+    pub async fn set_transitions(
+        id: i32,
+        to_stage_ids: &[i32],
+    ) -> Result<Option<(Vec<String>, Vec<String>)>, sqlx::Error> {
+        let mut tx = crate::database::get_db().begin().await?;
+        let exists = sqlx::query_scalar!("SELECT id FROM stages WHERE id = $1 FOR UPDATE", id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        if exists.is_none() {
+            return Ok(None);
+        }
+
+        let before = Self::next_stage_names(&mut tx, id).await?;
+        sqlx::query!("DELETE FROM stage_transitions WHERE from_stage_id = $1", id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO stage_transitions (from_stage_id, to_stage_id)
+            SELECT $1, s.id FROM stages s WHERE s.id = ANY($2) AND s.id <> $1
+            "#,
+            id,
+            to_stage_ids
+        )
+        .execute(&mut *tx)
+        .await?;
+        let after = Self::next_stage_names(&mut tx, id).await?;
+
+        tx.commit().await?;
+        Ok(Some((before, after)))
+    }
+
+    async fn next_stage_names(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        id: i32,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT n.name
+            FROM stage_transitions t
+            INNER JOIN stages n ON n.id = t.to_stage_id
+            WHERE t.from_stage_id = $1
+            ORDER BY n.position, n.id
+            "#,
+            id
+        )
+        .fetch_all(&mut **tx)
+        .await
+    }
+
+    /// Delete a stage no idea is in, returning its name, or `None` if it
+    /// does not exist. Fails with a foreign key violation while ideas are
+    /// still in it.
+    pub async fn delete(id: i32) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar!("DELETE FROM stages WHERE id = $1 RETURNING name", id)
+            .fetch_optional(crate::database::get_db())
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_last_open_stage, next_stages, Stage, StageColor};
+
+    fn stage(id: i32, name: &str, next_stage_ids: Vec<i32>) -> Stage {
+        Stage {
+            id,
+            name: name.to_string(),
+            position: id,
+            color: StageColor::Gray.as_str().to_string(),
+            is_terminal: false,
+            next_stage_ids,
+        }
+    }
+
+    #[test]
+    fn stage_colors_round_trip_through_their_names() {
+        for color in StageColor::ALL {
+            assert_eq!(StageColor::from_name(color.as_str()), Some(color));
+        }
+        assert_eq!(StageColor::from_name("purple"), None);
+    }
+
+    #[test]
+    fn next_stages_follow_the_transition_graph_in_order() {
+        let stages = vec![
+            stage(1, "Ideate", vec![3, 2]),
+            stage(2, "Review", vec![1]),
+            stage(3, "Declined", vec![]),
+        ];
+        let names = |current| {
+            next_stages(&stages, current)
+                .into_iter()
+                .map(|stage| stage.name.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names("Ideate"), vec!["Review", "Declined"]);
+        assert_eq!(names("Review"), vec!["Ideate"]);
+        assert!(names("Declined").is_empty());
+        assert!(names("Gone").is_empty());
+    }
+
+    #[test]
+    fn the_last_open_stage_cannot_be_closed() {
+        let declined = Stage {
+            is_terminal: true,
+            ..stage(3, "Declined", vec![])
+        };
+        let stages = vec![
+            stage(1, "Ideate", vec![]),
+            stage(2, "Review", vec![]),
+            declined,
+        ];
+        assert!(!is_last_open_stage(&stages, 1));
+        assert!(!is_last_open_stage(&stages, 3));
+
+        let stages = vec![stages[0].clone(), stages[2].clone()];
+        assert!(is_last_open_stage(&stages, 1));
+        assert!(!is_last_open_stage(&stages, 3));
+    }
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+/// Longest official response, in characters
+pub const MAX_RESPONSE_CHARS: usize = 5000;
+/// Length of the response preview on the board, in characters
+#[cfg(feature = "ssr")]
+pub const RESPONSE_EXCERPT_CHARS: usize = 160;
+
+/// One move of an idea between stages, on the idea's timeline.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StageChange {
+    pub id: i32,
+    pub idea_id: i32,
+    /// `None` once the moderator's account is deleted
+    pub actor_name: Option<String>,
+    pub from_stage: String,
+    pub to_stage: String,
+    /// Official staff response in Markdown
+    pub response: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// The latest official response on an idea, shortened for its board card.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ResponseSummary {
+    /// Stage the idea moved to with the response
+    pub stage: String,
+    /// Plain text of the response, cut to [`RESPONSE_EXCERPT_CHARS`]
+    pub excerpt: String,
+    pub responded_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[cfg(feature = "ssr")]
+impl StageChange {
+    /// An idea's stage changes, oldest first
+    pub async fn list_for_idea(idea_id: i32) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            StageChange,
+            r#"
+            SELECT sc.id, sc.idea_id, u.name AS "actor_name?", sc.from_stage, sc.to_stage,
+                   sc.response, sc.created_at
+            FROM stage_changes sc
+            LEFT JOIN users u ON u.id = sc.actor_id
+            WHERE sc.idea_id = $1
+            ORDER BY sc.created_at, sc.id
+            "#,
+            idea_id
+        )
+        .fetch_all(crate::database::get_db())
+        .await
+    }
+
+    /// Add a stage change to an idea's timeline
+    pub async fn record(
+        tx: &mut sqlx::PgConnection,
+        idea_id: i32,
+        actor_id: i32,
+        from_stage: &str,
+        to_stage: &str,
+        response: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO stage_changes (idea_id, actor_id, from_stage, to_stage, response)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            idea_id,
+            actor_id,
+            from_stage,
+            to_stage,
+            response
+        )
+        .execute(tx)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl ResponseSummary {
+    /// Summarize a response written in Markdown
+    pub fn new(stage: String, response: &str, responded_at: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            stage,
+            excerpt: crate::markdown::excerpt(response, RESPONSE_EXCERPT_CHARS),
+            responded_at,
+        }
+    }
+}
@@ -0,0 +1,337 @@
+use serde::{Deserialize, Serialize};
+
+/// Longest tag name, in characters
+pub const MAX_TAG_CHARS: usize = 32;
+/// Most tags one idea can have
+pub const MAX_TAGS_PER_IDEA: usize = 8;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct Tag {
+    pub id: i32,
+    pub name: String,
+    /// Lowercase name with runs of other characters replaced by dashes; two
+    /// names with the same slug are the same tag
+    pub slug: String,
+    /// Kept on the ideas that have it, but cannot be added to more
+    pub is_retired: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A tag with the number of ideas that have it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TagWithCount {
+    pub tag: Tag,
+    pub idea_count: i64,
+}
+
+/// The URL-safe form of a tag name, also used to match names that differ
+/// only in case, spacing or punctuation.
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.truncate(slug.trim_end_matches('-').len());
+    slug
+}
+
+/// Split a comma-separated tag list into names, trimming them, collapsing
+/// inner whitespace and dropping blanks and repeats of an earlier slug.
+pub fn parse_tag_names(tags: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for name in tags.split(',') {
+        let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+        if name.is_empty() || names.iter().any(|seen| slugify(seen) == slugify(&name)) {
+            continue;
+        }
+        names.push(name);
+    }
+    names
+}
+
+#[cfg(feature = "ssr")]
+impl Tag {
+    pub async fn get_by_slug(slug: &str) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Tag,
+            r#"
+            SELECT id, name, slug, is_retired, created_at
+            FROM tags
+            WHERE slug = $1
+            "#,
+            slug
+        )
+        .fetch_optional(crate::database::get_db())
+        .await
+    }
+
+    /// Every tag with the number of ideas that have it (admin view)
+    pub async fn get_all_with_counts() -> Result<Vec<TagWithCount>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT t.id, t.name, t.slug, t.is_retired, t.created_at,
+                   COUNT(it.idea_id) AS "idea_count!"
+            FROM tags t
+            LEFT JOIN idea_tags it ON it.tag_id = t.id
+            GROUP BY t.id
+            ORDER BY lower(t.name), t.id
+            "#
+        )
+        .fetch_all(crate::database::get_db())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| TagWithCount {
+                tag: Tag {
+                    id: r.id,
+                    name: r.name,
+                    slug: r.slug,
+                    is_retired: r.is_retired,
+                    created_at: r.created_at,
+                },
+                idea_count: r.idea_count,
+            })
+            .collect())
+    }
+
+    /// Tags that are not retired whose slug starts with the slug of
+    /// `prefix`, most used first, for autocomplete.
+    pub async fn suggest(prefix: &str, limit: i64) -> Result<Vec<TagWithCount>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT t.id, t.name, t.slug, t.is_retired, t.created_at,
+                   COUNT(it.idea_id) AS "idea_count!"
+            FROM tags t
+            LEFT JOIN idea_tags it ON it.tag_id = t.id
+            WHERE t.is_retired = false AND t.slug LIKE $1 || '%'
+            GROUP BY t.id
+            ORDER BY COUNT(it.idea_id) DESC, lower(t.name), t.id
+            LIMIT $2
+            "#,
+            slugify(prefix),
+            limit
+        )
+        .fetch_all(crate::database::get_db())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| TagWithCount {
+                tag: Tag {
+                    id: r.id,
+                    name: r.name,
+                    slug: r.slug,
+                    is_retired: r.is_retired,
+                    created_at: r.created_at,
+                },
+                idea_count: r.idea_count,
+            })
+            .collect())
+    }
+
+    /// Names of the retired tags among `names` that the idea does not
+    /// already have, so they cannot be added to it.
+    pub async fn retired_among(
+        names: &[String],
+        idea_id: Option<i32>,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let slugs: Vec<String> = names.iter().map(|name| slugify(name)).collect();
+        sqlx::query_scalar!(
+            r#"
+            SELECT t.name
+            FROM tags t
+            WHERE t.slug = ANY($1) AND t.is_retired = true
+              AND NOT EXISTS (
+                  SELECT 1 FROM idea_tags it WHERE it.tag_id = t.id AND it.idea_id = $2
+              )
+            ORDER BY lower(t.name)
+            "#,
+            &slugs[..],
+            idea_id
+        )
+        .fetch_all(crate::database::get_db())
+        .await
+    }
+
+    /// Replace an idea's tags with `names`, creating tags that do not exist
+    /// yet. Names matching an existing slug join that tag under its current
+    /// spelling. The `ideas.tags` display string follows through a trigger.
+    pub async fn set_for_idea(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        idea_id: i32,
+        names: &[String],
+    ) -> Result<(), sqlx::Error> {
+        let slugs: Vec<String> = names.iter().map(|name| slugify(name)).collect();
+        sqlx::query!(
+            r#"
+            INSERT INTO tags (name, slug)
+            SELECT * FROM unnest($1::text[], $2::text[])
+            ON CONFLICT (slug) DO NOTHING
+            "#,
+            names,
+            &slugs[..]
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM idea_tags it
+            USING tags t
+            WHERE it.idea_id = $1 AND t.id = it.tag_id AND NOT (t.slug = ANY($2))
+            "#,
+            idea_id,
+            &slugs[..]
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO idea_tags (idea_id, tag_id)
+            SELECT $1, id FROM tags WHERE slug = ANY($2)
+            ON CONFLICT DO NOTHING
+            "#,
+            idea_id,
+            &slugs[..]
+        )
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Rename a tag, which also changes its slug. Returns the tag as it was,
+    /// or `None` if it does not exist. Fails with a unique violation when
+    /// another tag already has the new slug; merge them instead.
+    pub async fn rename(id: i32, name: &str) -> Result<Option<Self>, sqlx::Error> {
+        let mut tx = crate::database::get_db().begin().await?;
+        let before = sqlx::query_as!(
+            Tag,
+            r#"
+            SELECT id, name, slug, is_retired, created_at
+            FROM tags
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        if before.is_none() {
+            return Ok(None);
+        }
+
+        sqlx::query!(
+            "UPDATE tags SET name = $1, slug = $2 WHERE id = $3",
+            name,
+            slugify(name),
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(before)
+    }
+
+    /// Move every idea tagged `source_id` to `target_id` and delete the
+    /// source tag. Returns the source tag and how many ideas it was on,
+    /// including those that already had the target, or `None` when either
+    /// tag is missing.
+    pub async fn merge(source_id: i32, target_id: i32) -> Result<Option<(Self, i64)>, sqlx::Error> {
+        if source_id == target_id {
+            return Ok(None);
+        }
+        let mut tx = crate::database::get_db().begin().await?;
+
+        let locked = sqlx::query_scalar!(
+            "SELECT id FROM tags WHERE id = ANY($1) ORDER BY id FOR UPDATE",
+            &[source_id, target_id][..]
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        if locked.len() != 2 {
+            return Ok(None);
+        }
+
+        let moved = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM idea_tags WHERE tag_id = $1"#,
+            source_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO idea_tags (idea_id, tag_id)
+            SELECT idea_id, $1 FROM idea_tags WHERE tag_id = $2
+            ON CONFLICT DO NOTHING
+            "#,
+            target_id,
+            source_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // Deleting the tag removes its idea_tags rows, which rebuilds the
+        // display strings without it
+        let source = sqlx::query_as!(
+            Tag,
+            r#"
+            DELETE FROM tags
+            WHERE id = $1
+            RETURNING id, name, slug, is_retired, created_at
+            "#,
+            source_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some((source, moved)))
+    }
+
+    /// Retire or restore a tag, returning it if it exists
+    pub async fn set_retired(id: i32, retired: bool) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Tag,
+            r#"
+            UPDATE tags SET is_retired = $1
+            WHERE id = $2
+            RETURNING id, name, slug, is_retired, created_at
+            "#,
+            retired,
+            id
+        )
+        .fetch_optional(crate::database::get_db())
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_tag_names, slugify};
+
+    #[test]
+    fn slugs_ignore_case_spacing_and_punctuation() {
+        assert_eq!(slugify("VPN"), "vpn");
+        assert_eq!(slugify("  vpn "), "vpn");
+        assert_eq!(slugify("Wi-Fi / Network"), "wi-fi-network");
+        assert_eq!(slugify("C++"), "c");
+        assert_eq!(slugify("!!!"), "");
+    }
+
+    #[test]
+    fn tag_lists_are_trimmed_and_deduplicated() {
+        assert_eq!(
+            parse_tag_names("VPN, vpn ,  remote   access,,Vpn"),
+            vec!["VPN".to_string(), "remote access".to_string()]
+        );
+        assert!(parse_tag_names(" , ").is_empty());
+    }
+}
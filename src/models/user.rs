@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "ssr")]
+use super::AuthorTrust;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
 pub struct User {
@@ -184,6 +187,130 @@ impl User {
         Ok(())
     }
 
+    /// Check a user's current password.
+    pub async fn verify_password(id: i32, password: String) -> Result<bool, sqlx::Error> {
+        use bcrypt::verify;
+
+        let password_hash =
+            sqlx::query_scalar!("SELECT password_hash FROM users WHERE id = $1", id)
+                .fetch_optional(crate::database::get_db())
+                .await?
+                .ok_or(sqlx::Error::RowNotFound)?;
+
+        verify(password, &password_hash)
+            .map_err(|e| sqlx::Error::Protocol(format!("Password verification failed: {e}")))
+    }
+
+    /// Update a user's password hash by ID.
+    pub async fn set_password(id: i32, password: String) -> Result<(), sqlx::Error> {
+        use bcrypt::{hash, DEFAULT_COST};
+
+        let password_hash = hash(password, DEFAULT_COST)
+            .map_err(|e| sqlx::Error::Protocol(format!("Password hashing failed: {e}")))?;
+
+        let result = sqlx::query!(
+            "UPDATE users SET password_hash = $1 WHERE id = $2",
+            password_hash,
+            id
+        )
+        .execute(crate::database::get_db())
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Update a user's display name.
+    pub async fn update_name(id: i32, name: String) -> Result<(), sqlx::Error> {
+        let result = sqlx::query!("UPDATE users SET name = $1 WHERE id = $2", name, id)
+            .execute(crate::database::get_db())
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// How the user wants notifications emailed; unknown values read as off.
+    pub async fn get_notification_preference(
+        id: i32,
+    ) -> Result<crate::models::NotificationPreference, sqlx::Error> {
+        use crate::models::NotificationPreference;
+
+        let row = sqlx::query!("SELECT notification_email FROM users WHERE id = $1", id)
+            .fetch_one(crate::database::get_db())
+            .await?;
+        Ok(NotificationPreference::from_name(&row.notification_email)
+            .unwrap_or(NotificationPreference::Off))
+    }
+
+    pub async fn set_notification_preference(
+        id: i32,
+        preference: crate::models::NotificationPreference,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE users SET notification_email = $1 WHERE id = $2",
+            preference.as_str(),
+            id
+        )
+        .execute(crate::database::get_db())
+        .await?;
+        Ok(())
+    }
+
+    /// `(id, email)` of the given users who want each notification emailed
+    pub async fn get_instant_email_recipients(
+        ids: &[i32],
+    ) -> Result<Vec<(i32, String)>, sqlx::Error> {
+        let rows = sqlx::query!(
+            "SELECT id, email FROM users WHERE id = ANY($1) AND notification_email = $2",
+            ids,
+            crate::models::NotificationPreference::Instant.as_str()
+        )
+        .fetch_all(crate::database::get_db())
+        .await?;
+        Ok(rows.into_iter().map(|row| (row.id, row.email)).collect())
+    }
+
+    /// `(id, email)` of digest subscribers who have not had a digest since
+    /// `since` and have notifications from after it that were not emailed
+    pub async fn get_due_for_digest(
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<(i32, String)>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT u.id, u.email
+            FROM users u
+            WHERE u.notification_email = $1
+              AND (u.notification_digest_sent_at IS NULL OR u.notification_digest_sent_at <= $2)
+              AND EXISTS (
+                  SELECT 1 FROM notifications n
+                  WHERE n.user_id = u.id AND n.emailed_at IS NULL AND n.created_at >= $2
+              )
+            "#,
+            crate::models::NotificationPreference::Daily.as_str(),
+            since
+        )
+        .fetch_all(crate::database::get_db())
+        .await?;
+        Ok(rows.into_iter().map(|row| (row.id, row.email)).collect())
+    }
+
+    pub async fn mark_digest_sent(id: i32) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE users SET notification_digest_sent_at = NOW() WHERE id = $1",
+            id
+        )
+        .execute(crate::database::get_db())
+        .await?;
+        Ok(())
+    }
+
     /// Update user role (admin only). Cannot change an admin's role.
     pub async fn update_role(id: i32, role: i16) -> Result<(), sqlx::Error> {
         let target = Self::get_by_id(id).await?;
@@ -195,13 +322,20 @@ impl User {
                 "Cannot change an admin's role".into(),
             ));
         }
+        let mut tx = crate::database::get_db().begin().await?;
         sqlx::query!("UPDATE users SET role = $1 WHERE id = $2", role, id)
-            .execute(crate::database::get_db())
+            .execute(&mut *tx)
+            .await?;
+        // Force a fresh login so no session keeps acting with the old role.
+        sqlx::query!("DELETE FROM sessions WHERE user_id = $1", id)
+            .execute(&mut *tx)
             .await?;
+        tx.commit().await?;
         Ok(())
     }
 
     /// Delete a user (admin only). Cannot delete any admin.
+    /// Their sessions are removed by the `sessions.user_id` cascade.
     pub async fn delete(id: i32) -> Result<(), sqlx::Error> {
         let target = Self::get_by_id(id).await?;
         let Some(u) = target else {
@@ -271,4 +405,39 @@ impl User {
         .fetch_all(crate::database::get_db())
         .await
     }
+
+    /// Role, account age and approved ideas, for the pre-moderation policy.
+    /// Only ideas still published on the board count as approved; off-topic,
+    /// hidden and merged ones earn no trust.
+    pub async fn get_author_trust(id: i32) -> Result<Option<AuthorTrust>, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                u.role, u.cas_subject IS NOT NULL as "is_cas_linked!", u.created_on,
+                (SELECT COUNT(*) FROM ideas i
+                 WHERE i.user_id = u.id AND i.is_public AND NOT i.is_off_topic
+                   AND i.merged_into IS NULL)
+                    as "approved_ideas!"
+            FROM users u
+            WHERE u.id = $1
+            "#,
+            id
+        )
+        .fetch_optional(crate::database::get_db())
+        .await?;
+
+        Ok(row.map(|row| AuthorTrust {
+            role: row.role,
+            is_cas_linked: row.is_cas_linked,
+            created_on: row.created_on,
+            approved_ideas: row.approved_ideas,
+        }))
+    }
+
+    /// Ids of all moderators and admins
+    pub async fn get_moderator_ids() -> Result<Vec<i32>, sqlx::Error> {
+        sqlx::query_scalar!("SELECT id FROM users WHERE role >= 1")
+            .fetch_all(crate::database::get_db())
+            .await
+    }
 }
@@ -48,6 +48,22 @@ mod inner {
             }
         }
 
+        /// Vote for an idea unless the user already has. Returns whether a vote
+        /// was added.
+        pub async fn add(user_id: i32, idea_id: i32) -> Result<bool, sqlx::Error> {
+            let result = sqlx::query!(
+                r#"
+                INSERT INTO votes (user_id, idea_id) VALUES ($1, $2)
+                ON CONFLICT (user_id, idea_id) DO NOTHING
+                "#,
+                user_id,
+                idea_id
+            )
+            .execute(crate::database::get_db())
+            .await?;
+            Ok(result.rows_affected() > 0)
+        }
+
         pub async fn has_voted(user_id: i32, idea_id: i32) -> Result<bool, sqlx::Error> {
             let result = sqlx::query_scalar!(
                 r#"
@@ -69,6 +85,20 @@ mod inner {
                 .await?;
             Ok(votes.into_iter().map(|v| v.idea_id).collect())
         }
+
+        /// Number of users who voted for an idea since `since`
+        pub async fn count_recent_voters(
+            idea_id: i32,
+            since: chrono::DateTime<chrono::Utc>,
+        ) -> Result<i64, sqlx::Error> {
+            sqlx::query_scalar!(
+                r#"SELECT COUNT(*) as "count!" FROM votes WHERE idea_id = $1 AND created_at >= $2"#,
+                idea_id,
+                since
+            )
+            .fetch_one(crate::database::get_db())
+            .await
+        }
     }
 }
 
@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+
+/// How a word filter term is compared with posted text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MatchMode {
+    WholeWord,
+    Substring,
+    Regex,
+}
+
+impl MatchMode {
+    pub const ALL: [MatchMode; 3] = [Self::WholeWord, Self::Substring, Self::Regex];
+
+    /// Value stored in `word_filters.match_mode`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::WholeWord => "whole_word",
+            Self::Substring => "substring",
+            Self::Regex => "regex",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::WholeWord => "Whole word",
+            Self::Substring => "Substring",
+            Self::Regex => "Regular expression",
+        }
+    }
+
+    pub fn from_name(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.as_str() == value)
+    }
+}
+
+/// What happens to a post that matches a word filter term, least severe
+/// first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum FilterSeverity {
+    /// Publish it and add it to the flags queue
+    Flag,
+    /// Hide it until a moderator reviews it
+    Hold,
+    /// Reject it
+    Block,
+}
+
+impl FilterSeverity {
+    pub const ALL: [FilterSeverity; 3] = [Self::Block, Self::Hold, Self::Flag];
+
+    /// Value stored in `word_filters.severity`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Flag => "flag",
+            Self::Hold => "hold",
+            Self::Block => "block",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Flag => "Flag for review",
+            Self::Hold => "Hold for review",
+            Self::Block => "Block",
+        }
+    }
+
+    pub fn from_name(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|severity| severity.as_str() == value)
+    }
+}
+
+/// A term the content filter looks for.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct WordFilter {
+    pub id: i32,
+    pub term: String,
+    pub match_mode: String,
+    pub severity: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// The rule a piece of text matched.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FilterMatch {
+    pub rule_id: i32,
+    pub term: String,
+    pub match_mode: MatchMode,
+    pub severity: FilterSeverity,
+}
+
+impl FilterMatch {
+    /// Short description for moderators and the audit log
+    pub fn describe(&self) -> String {
+        format!(
+            "{} \"{}\" (rule #{})",
+            self.match_mode.label().to_lowercase(),
+            self.term,
+            self.rule_id
+        )
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl WordFilter {
+    pub async fn get_all() -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WordFilter,
+            r#"
+            SELECT id, term, match_mode, severity, created_at
+            FROM word_filters
+            ORDER BY term, id
+            "#
+        )
+        .fetch_all(crate::database::get_db())
+        .await
+    }
+
+    pub async fn create(
+        term: &str,
+        match_mode: MatchMode,
+        severity: FilterSeverity,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            WordFilter,
+            r#"
+            INSERT INTO word_filters (term, match_mode, severity)
+            VALUES ($1, $2, $3)
+            RETURNING id, term, match_mode, severity, created_at
+            "#,
+            term,
+            match_mode.as_str(),
+            severity.as_str()
+        )
+        .fetch_one(crate::database::get_db())
+        .await
+    }
+
+    /// Remove a term, returning it if it existed
+    pub async fn delete(id: i32) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WordFilter,
+            r#"
+            DELETE FROM word_filters
+            WHERE id = $1
+            RETURNING id, term, match_mode, severity, created_at
+            "#,
+            id
+        )
+        .fetch_optional(crate::database::get_db())
+        .await
+    }
+}
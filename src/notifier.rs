@@ -0,0 +1,245 @@
+//! Delivery of user notifications.
+//!
+//! [`notify`] stores one in-app notification per recipient and, for users who
+//! chose instant email on their profile, queues an email right away when SMTP
+//! is configured. Users who chose the daily digest get one summary email a
+//! day from the worker spawned by [`spawn_digest_worker`]. Email itself goes
+//! through the `email_notifications` queue drained by `crate::mailer`.
+
+use std::collections::BTreeSet;
+use std::env;
+use std::time::Duration;
+
+use crate::models::{EmailNotification, Notification, User};
+
+/// How often the digest worker looks for users who are due a digest
+const DIGEST_POLL_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// Minimum time between two digests to the same user
+const DIGEST_PERIOD_HOURS: i64 = 24;
+const INSTANT_SUBJECT: &str = "New activity on UAB IT Idea Board";
+/// Used to build links in emails when `SITE_URL` is not set
+const DEFAULT_SITE_URL: &str = "http://localhost:3000";
+
+/// Notify `recipients` of something `actor_id` did. The actor is never told
+/// about their own action and each recipient is notified once. Like the audit
+/// log this is best-effort: failures are logged and never fail the caller.
+pub(crate) async fn notify(
+    recipients: impl IntoIterator<Item = i32>,
+    actor_id: Option<i32>,
+    message: String,
+    link: Option<String>,
+) {
+    let user_ids = recipient_ids(recipients, actor_id);
+    if user_ids.is_empty() {
+        return;
+    }
+
+    let created = match Notification::create_many(&user_ids, &message, link.as_deref()).await {
+        Ok(created) => created,
+        Err(e) => {
+            tracing::error!("Failed to notify users {user_ids:?}: {e}");
+            return;
+        }
+    };
+
+    // Like the digest, only queue email when a worker will send it, so the
+    // notifications are not marked as emailed for nothing
+    if crate::mailer::MailerConfig::from_env().is_err() {
+        return;
+    }
+    if let Err(e) = email_instant(&created).await {
+        tracing::error!("Failed to queue notification emails: {e}");
+    }
+}
+
+/// Queue an email for each new notification whose recipient wants them
+/// instantly. Batched so a change on a busy idea costs a fixed number of
+/// queries however many followers it has.
+async fn email_instant(created: &[Notification]) -> Result<(), sqlx::Error> {
+    let user_ids: Vec<i32> = created.iter().map(|n| n.user_id).collect();
+    let site_url = site_url();
+
+    let mut emails = Vec::new();
+    let mut emailed_ids = Vec::new();
+    for (user_id, email) in User::get_instant_email_recipients(&user_ids).await? {
+        let Some(notification) = created.iter().find(|n| n.user_id == user_id) else {
+            continue;
+        };
+        emails.push((
+            email,
+            INSTANT_SUBJECT.to_string(),
+            instant_body(notification, &site_url),
+        ));
+        emailed_ids.push(notification.id);
+    }
+    if emails.is_empty() {
+        return Ok(());
+    }
+
+    EmailNotification::enqueue_many(&emails).await?;
+    Notification::mark_emailed(&emailed_ids).await
+}
+
+/// Start sending daily digests in the background. Like the email worker it
+/// is not started without SMTP settings, so notifications are not marked as
+/// emailed when nothing will send them.
+pub fn spawn_digest_worker() {
+    if let Err(error) = crate::mailer::MailerConfig::from_env() {
+        tracing::warn!("Digest worker not started: {error}");
+        return;
+    }
+
+    tokio::spawn(async move {
+        loop {
+            if let Err(error) = send_due_digests().await {
+                tracing::error!(?error, "failed to send notification digests");
+            }
+            tokio::time::sleep(DIGEST_POLL_INTERVAL).await;
+        }
+    });
+}
+
+/// Queue a digest for every user who is due one, returning how many were
+/// queued.
+async fn send_due_digests() -> Result<usize, sqlx::Error> {
+    let since = chrono::Utc::now() - chrono::Duration::hours(DIGEST_PERIOD_HOURS);
+    let site_url = site_url();
+    let mut queued = 0;
+
+    for (user_id, email) in User::get_due_for_digest(since).await? {
+        let notifications = Notification::get_unemailed_for_user(user_id, since).await?;
+        if notifications.is_empty() {
+            continue;
+        }
+        EmailNotification::enqueue(
+            &email,
+            &digest_subject(notifications.len()),
+            &digest_body(&notifications, &site_url),
+        )
+        .await?;
+        let ids: Vec<i32> = notifications.iter().map(|n| n.id).collect();
+        Notification::mark_emailed(&ids).await?;
+        User::mark_digest_sent(user_id).await?;
+        queued += 1;
+    }
+    Ok(queued)
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+/// Sorted, de-duplicated recipients without the user who caused the
+/// notification.
+fn recipient_ids(recipients: impl IntoIterator<Item = i32>, actor_id: Option<i32>) -> Vec<i32> {
+    recipients
+        .into_iter()
+        .filter(|id| Some(*id) != actor_id)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Public base URL used for links in emails, from `SITE_URL`.
+fn site_url() -> String {
+    env::var("SITE_URL")
+        .ok()
+        .map(|url| url.trim().trim_end_matches('/').to_string())
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| DEFAULT_SITE_URL.to_string())
+}
+
+fn notification_line(notification: &Notification, site_url: &str) -> String {
+    match &notification.link {
+        Some(link) => format!("{}\n{site_url}{link}", notification.message),
+        None => notification.message.clone(),
+    }
+}
+
+fn preferences_footer(site_url: &str) -> String {
+    format!("You can change how you receive these emails on your profile: {site_url}/profile")
+}
+
+fn instant_body(notification: &Notification, site_url: &str) -> String {
+    format!(
+        "{}\n\n{}",
+        notification_line(notification, site_url),
+        preferences_footer(site_url)
+    )
+}
+
+fn digest_subject(count: usize) -> String {
+    match count {
+        1 => "Your UAB IT Idea Board digest: 1 update".to_string(),
+        n => format!("Your UAB IT Idea Board digest: {n} updates"),
+    }
+}
+
+fn digest_body(notifications: &[Notification], site_url: &str) -> String {
+    let mut body = String::from("Here is what happened on the idea board in the last day:\n\n");
+    for notification in notifications {
+        body.push_str("- ");
+        body.push_str(&notification_line(notification, site_url).replace('\n', "\n  "));
+        body.push('\n');
+    }
+    body.push('\n');
+    body.push_str(&preferences_footer(site_url));
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(id: i32, message: &str, link: Option<&str>) -> Notification {
+        Notification {
+            id,
+            user_id: 1,
+            message: message.to_string(),
+            link: link.map(str::to_string),
+            is_read: false,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn recipients_are_deduplicated_and_exclude_the_actor() {
+        assert_eq!(recipient_ids([3, 1, 3, 2, 1], Some(2)), vec![1, 3]);
+        assert_eq!(recipient_ids([5, 5], None), vec![5]);
+        assert!(recipient_ids([4], Some(4)).is_empty());
+    }
+
+    #[test]
+    fn instant_body_links_to_the_site() {
+        let body = instant_body(
+            &notification(1, "New comment on \"Bike racks\"", Some("/ideas/7")),
+            "https://ideas.example.edu",
+        );
+
+        assert!(
+            body.starts_with("New comment on \"Bike racks\"\nhttps://ideas.example.edu/ideas/7")
+        );
+        assert!(body.ends_with("https://ideas.example.edu/profile"));
+    }
+
+    #[test]
+    fn digest_lists_every_notification() {
+        let notifications = [
+            notification(1, "First", Some("/ideas/1")),
+            notification(2, "Second", None),
+        ];
+
+        let body = digest_body(&notifications, "https://ideas.example.edu");
+
+        assert!(
+            body.contains("- First\n  https://ideas.example.edu/ideas/1\n"),
+            "{body}"
+        );
+        assert!(body.contains("- Second\n"), "{body}");
+        assert_eq!(
+            digest_subject(2),
+            "Your UAB IT Idea Board digest: 2 updates"
+        );
+        assert_eq!(digest_subject(1), "Your UAB IT Idea Board digest: 1 update");
+    }
+}
@@ -1,29 +1,19 @@
-//! Profanity filter for content moderation
-//! Filters out common offensive words and their variations
-
-/// List of blocked words (the 7 dirty words and common slurs)
-const BLOCKED_WORDS: &[&str] = &[
-    // The 7 dirty words
-    "shit",
-    "piss",
-    "fuck",
-    "cunt",
-    "cocksucker",
-    "motherfucker",
-    "tits",
-    // Common variations
-    "f*ck",
-    "f**k",
-    "sh*t",
-    "sh1t",
-    "f u c k",
-    "s h i t",
-    // Common slurs (abbreviated for safety)
-    "n1gger",
-    "n1gga",
-    "f4g",
-    "f4gg0t",
-];
+//! Word filter for content moderation
+//! Checks posted text against the terms in the `word_filters` table, which
+//! admins manage from the dashboard. The terms are compiled once and cached;
+//! [`reload`] refreshes the cache after the table changes.
+
+use std::sync::{Arc, LazyLock, RwLock};
+
+use regex::{Regex, RegexBuilder};
+
+use crate::models::{FilterMatch, FilterSeverity, MatchMode, WordFilter};
+
+/// Compiled regular expressions are capped so one rule cannot use up memory
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+static ACTIVE: LazyLock<RwLock<Arc<WordFilterSet>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(WordFilterSet::default())));
 
 /// Character substitutions commonly used to bypass filters
 fn normalize_text(text: &str) -> String {
@@ -39,49 +29,236 @@ fn normalize_text(text: &str) -> String {
         .replace('!', "i")
         .replace('+', "t")
         .replace('v', "u")
-        .replace(' ', "")
 }
 
-/// Check if text contains profanity
-pub fn contains_profanity(text: &str) -> bool {
-    let normalized = normalize_text(text);
+struct CompiledRule {
+    rule: FilterMatch,
+    matcher: Matcher,
+}
+
+enum Matcher {
+    /// Matched against the lowercased and the normalized text, so trailing
+    /// punctuation such as "!" still ends a word
+    Pattern(Regex),
+    /// Normalized term without spaces, matched against the normalized text
+    /// without spaces
+    Substring(String),
+}
+
+/// The word filter rules, ready to check text against.
+#[derive(Default)]
+pub struct WordFilterSet {
+    rules: Vec<CompiledRule>,
+}
+
+impl WordFilterSet {
+    /// Compile `filters`, skipping any with an unknown mode or severity or an
+    /// invalid pattern.
+    pub fn new(filters: &[WordFilter]) -> Self {
+        let rules = filters
+            .iter()
+            .filter_map(|filter| match compile(filter) {
+                Ok(rule) => Some(rule),
+                Err(e) => {
+                    tracing::warn!("Skipping word filter rule #{}: {e}", filter.id);
+                    None
+                }
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// The most severe rule any of `texts` matches.
+    pub fn check(&self, texts: &[&str]) -> Option<FilterMatch> {
+        let prepared: Vec<Prepared> = texts.iter().map(|text| Prepared::new(text)).collect();
+        self.rules
+            .iter()
+            .filter(|rule| prepared.iter().any(|text| rule.matches(text)))
+            .map(|rule| rule.rule.clone())
+            .max_by_key(|rule| rule.severity)
+    }
+}
+
+/// Text in the forms the matchers compare against
+struct Prepared {
+    lower: String,
+    normalized: String,
+    compact: String,
+}
+
+impl Prepared {
+    fn new(text: &str) -> Self {
+        let normalized = normalize_text(text);
+        Self {
+            lower: text.to_lowercase(),
+            compact: normalized.replace(' ', ""),
+            normalized,
+        }
+    }
+}
 
-    for word in BLOCKED_WORDS {
-        let normalized_word = normalize_text(word);
-        if normalized.contains(&normalized_word) {
-            return true;
+impl CompiledRule {
+    fn matches(&self, text: &Prepared) -> bool {
+        match &self.matcher {
+            Matcher::Pattern(regex) => {
+                regex.is_match(&text.lower) || regex.is_match(&text.normalized)
+            }
+            Matcher::Substring(term) => text.compact.contains(term.as_str()),
         }
     }
+}
+
+fn compile(filter: &WordFilter) -> Result<CompiledRule, String> {
+    let match_mode = MatchMode::from_name(&filter.match_mode)
+        .ok_or_else(|| format!("unknown match mode {}", filter.match_mode))?;
+    let severity = FilterSeverity::from_name(&filter.severity)
+        .ok_or_else(|| format!("unknown severity {}", filter.severity))?;
+    let matcher = compile_term(&filter.term, match_mode)?;
+    Ok(CompiledRule {
+        rule: FilterMatch {
+            rule_id: filter.id,
+            term: filter.term.clone(),
+            match_mode,
+            severity,
+        },
+        matcher,
+    })
+}
+
+fn compile_term(term: &str, match_mode: MatchMode) -> Result<Matcher, String> {
+    let build = |pattern: &str| {
+        RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .size_limit(REGEX_SIZE_LIMIT)
+            .build()
+            .map(Matcher::Pattern)
+            .map_err(|e| e.to_string())
+    };
+    match match_mode {
+        MatchMode::WholeWord => build(&format!(
+            r"(?:^|\W){}(?:\W|$)",
+            regex::escape(&normalize_text(term))
+        )),
+        MatchMode::Substring => Ok(Matcher::Substring(normalize_text(term).replace(' ', ""))),
+        MatchMode::Regex => build(term),
+    }
+}
 
-    false
+/// Check that a new term can be compiled, so admins see a bad pattern right
+/// away.
+pub fn validate_term(term: &str, match_mode: MatchMode) -> Result<(), String> {
+    compile_term(term, match_mode).map(|_| ())
+}
+
+/// The rules currently in effect.
+pub fn active() -> Arc<WordFilterSet> {
+    ACTIVE
+        .read()
+        .map(|set| Arc::clone(&set))
+        .unwrap_or_default()
+}
+
+/// Load the rules from the database into the cache.
+pub async fn reload() -> Result<(), sqlx::Error> {
+    let set = Arc::new(WordFilterSet::new(&WordFilter::get_all().await?));
+    if let Ok(mut active) = ACTIVE.write() {
+        *active = set;
+    }
+    Ok(())
+}
+
+/// Check if text matches any word filter rule. Used for display names, which
+/// cannot be held for review, so every severity counts.
+pub fn contains_profanity(text: &str) -> bool {
+    active().check(&[text]).is_some()
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
+    fn rule(id: i32, term: &str, match_mode: MatchMode, severity: FilterSeverity) -> WordFilter {
+        WordFilter {
+            id,
+            term: term.to_string(),
+            match_mode: match_mode.as_str().to_string(),
+            severity: severity.as_str().to_string(),
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    /// A few of the terms the migration seeds, plus one rule per other mode
+    /// and severity
+    pub(crate) fn test_filters() -> WordFilterSet {
+        let whole = |id, term| rule(id, term, MatchMode::WholeWord, FilterSeverity::Block);
+        WordFilterSet::new(&[
+            whole(1, "shit"),
+            whole(2, "fuck"),
+            whole(3, "cunt"),
+            whole(4, "f*ck"),
+            whole(5, "f u c k"),
+            rule(6, "darn", MatchMode::Substring, FilterSeverity::Hold),
+            rule(7, r"buy\s+now", MatchMode::Regex, FilterSeverity::Flag),
+        ])
+    }
+
+    fn matched(text: &str) -> Option<i32> {
+        test_filters().check(&[text]).map(|m| m.rule_id)
+    }
+
     #[test]
     fn test_basic_profanity() {
-        assert!(contains_profanity("this is shit"));
-        assert!(contains_profanity("what the fuck"));
+        assert_eq!(matched("this is shit"), Some(1));
+        assert_eq!(matched("what the fuck!"), Some(2));
     }
 
     #[test]
     fn test_clean_text() {
-        assert!(!contains_profanity("this is a good idea"));
-        assert!(!contains_profanity("improve the system"));
+        assert_eq!(matched("this is a good idea"), None);
+        assert_eq!(matched("improve the system"), None);
     }
 
     #[test]
     fn test_variations() {
-        assert!(contains_profanity("f*ck this"));
-        assert!(contains_profanity("sh1t"));
-        assert!(contains_profanity("f u c k"));
+        assert_eq!(matched("f*ck this"), Some(4));
+        assert_eq!(matched("sh1t"), Some(1));
+        assert_eq!(matched("f u c k"), Some(5));
     }
 
     #[test]
     fn test_number_substitution() {
-        assert!(contains_profanity("sh1t"));
-        assert!(contains_profanity("fvck"));
+        assert_eq!(matched("sh1t"), Some(1));
+        assert_eq!(matched("fvck"), Some(2));
+    }
+
+    #[test]
+    fn whole_words_do_not_match_inside_other_words() {
+        assert_eq!(matched("Scunthorpe bus routes"), None);
+        assert_eq!(matched("shitake"), None);
+    }
+
+    #[test]
+    fn substring_and_regex_modes() {
+        assert_eq!(matched("darnation"), Some(6));
+        assert_eq!(matched("BUY   NOW while stocks last"), Some(7));
+    }
+
+    #[test]
+    fn most_severe_match_wins() {
+        let found = test_filters()
+            .check(&["buy now", "darn it, shit"])
+            .expect("should match");
+        assert_eq!(found.severity, FilterSeverity::Block);
+        assert_eq!(found.describe(), "whole word \"shit\" (rule #1)");
+    }
+
+    #[test]
+    fn invalid_rules_are_skipped() {
+        let set = WordFilterSet::new(&[
+            rule(1, "(unclosed", MatchMode::Regex, FilterSeverity::Block),
+            rule(2, "spam", MatchMode::WholeWord, FilterSeverity::Flag),
+        ]);
+        assert_eq!(set.check(&["(unclosed spam"]).map(|m| m.rule_id), Some(2));
+        assert!(validate_term("(unclosed", MatchMode::Regex).is_err());
     }
 }
@@ -1,12 +1,278 @@
-// Simple profile/account page: show current user from session or prompt to log in.
+// Profile/account page: the current user's ideas, comments, votes and
+// notifications, plus account settings and active sessions.
 
 use leptos::prelude::*;
 use leptos_meta::Title;
 use leptos_router::components::A;
 
 use crate::auth::{get_user, use_auth_refresh};
+use crate::models::{CommentWithIdea, Idea, Notification, NotificationPreference, Session};
+#[cfg(feature = "ssr")]
+use crate::routes::error_helpers::server_fn_error_with_log;
 use crate::routes::paths;
 
+mod components;
+use components::{
+    DisplayNameForm, NotificationPreferenceForm, PasswordForm, ProfileTabs, SessionsPanel,
+};
+
+// ============================================================================
+// SERVER FUNCTIONS
+// ============================================================================
+
+#[server]
+pub async fn get_my_ideas() -> Result<Vec<Idea>, ServerFnError> {
+    use crate::auth::require_auth;
+    let user = require_auth().await?;
+
+    Idea::get_by_user(user.id).await.map_err(|e| {
+        server_fn_error_with_log(
+            "Failed to fetch user ideas",
+            e,
+            "Failed to fetch your ideas",
+        )
+    })
+}
+
+#[server]
+pub async fn get_my_comments() -> Result<Vec<CommentWithIdea>, ServerFnError> {
+    use crate::auth::require_auth;
+    use crate::models::Comment;
+    let user = require_auth().await?;
+
+    Comment::get_by_user(user.id).await.map_err(|e| {
+        server_fn_error_with_log(
+            "Failed to fetch user comments",
+            e,
+            "Failed to fetch your comments",
+        )
+    })
+}
+
+#[server]
+pub async fn get_my_voted_ideas() -> Result<Vec<Idea>, ServerFnError> {
+    use crate::auth::require_auth;
+    use crate::models::Vote;
+    let user = require_auth().await?;
+
+    let voted_ids = Vote::get_voted_ideas(user.id).await.map_err(|e| {
+        server_fn_error_with_log(
+            "Failed to fetch user votes",
+            e,
+            "Failed to fetch your votes",
+        )
+    })?;
+
+    Idea::get_by_ids(&voted_ids).await.map_err(|e| {
+        server_fn_error_with_log(
+            "Failed to fetch voted ideas",
+            e,
+            "Failed to fetch your votes",
+        )
+    })
+}
+
+#[server]
+pub async fn get_my_notifications() -> Result<Vec<Notification>, ServerFnError> {
+    use crate::auth::require_auth;
+    let user = require_auth().await?;
+
+    Notification::get_recent_for_user(user.id, NOTIFICATIONS_SHOWN)
+        .await
+        .map_err(|e| {
+            server_fn_error_with_log(
+                "Failed to fetch notifications",
+                e,
+                "Failed to fetch your notifications",
+            )
+        })
+}
+
+#[server]
+pub async fn mark_my_notifications_read() -> Result<(), ServerFnError> {
+    use crate::auth::require_auth;
+    let user = require_auth().await?;
+
+    Notification::mark_all_read(user.id).await.map_err(|e| {
+        server_fn_error_with_log(
+            "Failed to mark notifications read",
+            e,
+            "Failed to update your notifications",
+        )
+    })
+}
+
+#[server]
+pub async fn get_notification_preference() -> Result<NotificationPreference, ServerFnError> {
+    use crate::auth::require_auth;
+    use crate::models::User;
+    let user = require_auth().await?;
+
+    User::get_notification_preference(user.id)
+        .await
+        .map_err(|e| {
+            server_fn_error_with_log(
+                "Failed to fetch notification preference",
+                e,
+                "Failed to fetch your email settings",
+            )
+        })
+}
+
+#[server]
+pub async fn update_notification_preference(preference: String) -> Result<String, ServerFnError> {
+    use crate::auth::require_auth;
+    use crate::models::User;
+    let user = require_auth().await?;
+
+    let Some(preference) = NotificationPreference::from_name(&preference) else {
+        return Ok(String::from("Choose how you want to be emailed"));
+    };
+
+    User::set_notification_preference(user.id, preference)
+        .await
+        .map_err(|e| {
+            server_fn_error_with_log(
+                "Failed to update notification preference",
+                e,
+                "Failed to update your email settings",
+            )
+        })?;
+
+    Ok(String::from("Email settings saved"))
+}
+
+#[server]
+pub async fn update_display_name(name: String) -> Result<String, ServerFnError> {
+    use crate::auth::{require_auth, validate_display_name};
+    use crate::models::User;
+    let user = require_auth().await?;
+
+    let name = name.trim().to_string();
+    if let Err(message) = validate_display_name(&name) {
+        return Ok(message);
+    }
+
+    User::update_name(user.id, name).await.map_err(|e| {
+        server_fn_error_with_log(
+            "Failed to update display name",
+            e,
+            "Failed to update your name",
+        )
+    })?;
+
+    Ok(String::from(NAME_UPDATED))
+}
+
+#[server]
+pub async fn change_password(
+    current_password: String,
+    new_password: String,
+    confirm: String,
+) -> Result<String, ServerFnError> {
+    use crate::auth::{current_session_id, require_auth};
+    use crate::models::User;
+    let user = require_auth().await?;
+
+    if let Err(message) = validate_password_change(&new_password, &confirm) {
+        return Ok(message);
+    }
+
+    let current_matches = User::verify_password(user.id, current_password)
+        .await
+        .map_err(|e| {
+            server_fn_error_with_log(
+                "Failed to verify password",
+                e,
+                "Failed to change your password",
+            )
+        })?;
+    if !current_matches {
+        return Ok(String::from("Current password is incorrect"));
+    }
+
+    User::set_password(user.id, new_password)
+        .await
+        .map_err(|e| {
+            server_fn_error_with_log(
+                "Failed to set password",
+                e,
+                "Failed to change your password",
+            )
+        })?;
+
+    // Sign out other browsers so a leaked password stops working everywhere.
+    if let Some(session_id) = current_session_id().await? {
+        Session::revoke_others_for_user(user.id, session_id)
+            .await
+            .map_err(|e| {
+                server_fn_error_with_log(
+                    "Failed to revoke sessions",
+                    e,
+                    "Failed to change your password",
+                )
+            })?;
+    }
+
+    Ok(String::from(PASSWORD_CHANGED))
+}
+
+#[server]
+pub async fn get_active_sessions() -> Result<Vec<ActiveSession>, ServerFnError> {
+    use crate::auth::{current_session_id, require_auth};
+    let user = require_auth().await?;
+    let current_id = current_session_id().await?;
+
+    let sessions = Session::get_active_by_user(user.id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to fetch sessions: {e}")))?;
+
+    Ok(sessions
+        .into_iter()
+        .map(|session| ActiveSession {
+            is_current: Some(session.id) == current_id,
+            session,
+        })
+        .collect())
+}
+
+#[server]
+pub async fn revoke_session(session_id: i32) -> Result<(), ServerFnError> {
+    use crate::auth::require_auth;
+    let user = require_auth().await?;
+
+    let revoked = Session::revoke_for_user(session_id, user.id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to revoke session: {e}")))?;
+
+    if !revoked {
+        return Err(ServerFnError::new("Session not found"));
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ActiveSession {
+    pub session: Session,
+    pub is_current: bool,
+}
+
+/// Most recent notifications listed in the Notifications tab
+const NOTIFICATIONS_SHOWN: i64 = 100;
+
+const NAME_UPDATED: &str = "Display name updated";
+#[cfg(feature = "ssr")]
+const PASSWORD_CHANGED: &str = "Password changed. Other sessions have been signed out.";
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
 #[component]
 pub fn AccountPage() -> impl IntoView {
     let auth_refresh = use_auth_refresh();
@@ -23,13 +289,25 @@ pub fn AccountPage() -> impl IntoView {
                 {move || user_resource.get().map(|result| {
                     match result {
                         Ok(Some(user)) => view! {
-                            <div class="auth-page">
+                            <div class="auth-page profile-page">
                                 <div class="row">
                                     <div class="col-md-6 offset-md-3 col-xs-12">
-                                        <p class="text-xs-center">"Logged in as " <strong>{user.name.clone()}</strong> " (" {user.email} ")"</p>
+                                        <p class="text-xs-center">"Logged in as " <strong>{user.name.clone()}</strong> " (" {user.email.clone()} ")"</p>
                                         <p class="text-xs-center">
                                             <A href=paths::HOME>"Back to Idea Board"</A>
+                                            {user.is_moderator().then(|| view! {
+                                                " · "
+                                                <A href=paths::ADMIN>"Admin Dashboard"</A>
+                                            })}
                                         </p>
+                                        <ProfileTabs/>
+                                        <section class="profile-settings" aria-labelledby="settings-heading">
+                                            <h2 id="settings-heading">"Account Settings"</h2>
+                                            <DisplayNameForm current_name=user.name/>
+                                            <PasswordForm/>
+                                            <NotificationPreferenceForm/>
+                                        </section>
+                                        <SessionsPanel/>
                                     </div>
                                 </div>
                             </div>
@@ -54,3 +332,42 @@ pub fn AccountPage() -> impl IntoView {
         </div>
     }
 }
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+#[cfg(feature = "ssr")]
+fn validate_password_change(new_password: &str, confirm: &str) -> Result<(), String> {
+    crate::auth::validate_password(new_password)?;
+    if new_password != confirm {
+        return Err(String::from("New passwords do not match"));
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::validate_password_change;
+
+    #[test]
+    fn password_change_accepts_matching_passwords() {
+        assert!(validate_password_change("correct horse", "correct horse").is_ok());
+    }
+
+    #[test]
+    fn password_change_rejects_short_password() {
+        assert_eq!(
+            validate_password_change("short", "short"),
+            Err(String::from("Password must be at least 8 characters"))
+        );
+    }
+
+    #[test]
+    fn password_change_rejects_mismatched_confirmation() {
+        assert_eq!(
+            validate_password_change("correct horse", "correct horsf"),
+            Err(String::from("New passwords do not match"))
+        );
+    }
+}
@@ -0,0 +1,7 @@
+mod sessions;
+mod settings;
+mod tabs;
+
+pub(super) use sessions::SessionsPanel;
+pub(super) use settings::{DisplayNameForm, NotificationPreferenceForm, PasswordForm};
+pub(super) use tabs::ProfileTabs;
@@ -0,0 +1,90 @@
+use leptos::prelude::*;
+
+use crate::auth::{bump_auth_refresh, LogoutEverywhere};
+use crate::routes::async_helpers::spawn_server_action_refetch_resource;
+use crate::routes::view_helpers::format_relative_time;
+
+use super::super::{get_active_sessions, revoke_session, ActiveSession};
+
+#[component]
+pub fn SessionsPanel() -> impl IntoView {
+    let sessions = Resource::new(|| (), |_| async { get_active_sessions().await });
+    let logout_everywhere = ServerAction::<LogoutEverywhere>::new();
+
+    Effect::new(move |_| {
+        if let Some(Ok(())) = logout_everywhere.value().get() {
+            bump_auth_refresh();
+        }
+    });
+
+    view! {
+        <section class="sessions-panel" aria-labelledby="sessions-heading">
+            <h2 id="sessions-heading">"Active Sessions"</h2>
+            <Suspense fallback=|| view! { <p>"Loading sessions…"</p> }>
+                {move || sessions.get().map(|result| match result {
+                    Ok(list) => view! {
+                        <ul class="session-list">
+                            <For
+                                each=move || list.clone()
+                                key=|active| active.session.id
+                                children=move |active: ActiveSession| {
+                                    view! { <SessionRow active=active sessions=sessions/> }
+                                }
+                            />
+                        </ul>
+                    }
+                        .into_any(),
+                    Err(_) => view! { <p class="error">"Failed to load sessions"</p> }.into_any(),
+                })}
+            </Suspense>
+            <ActionForm action=logout_everywhere>
+                <button type="submit" class="btn btn-danger">"Log out everywhere"</button>
+            </ActionForm>
+        </section>
+    }
+}
+
+#[component]
+fn SessionRow(
+    active: ActiveSession,
+    sessions: Resource<Result<Vec<ActiveSession>, ServerFnError>>,
+) -> impl IntoView {
+    let session_id = active.session.id;
+    let is_current = active.is_current;
+    let device = active
+        .session
+        .user_agent
+        .clone()
+        .unwrap_or_else(|| "Unknown device".to_string());
+    let ip_address = active
+        .session
+        .ip_address
+        .clone()
+        .unwrap_or_else(|| "unknown address".to_string());
+    let last_seen = format_relative_time(&active.session.last_seen);
+
+    view! {
+        <li class="session-item" class:current=is_current>
+            <div class="session-info">
+                <span class="session-device">{device}</span>
+                <span class="session-meta">{format!("{ip_address} · active {last_seen}")}</span>
+            </div>
+            {if is_current {
+                view! { <span class="session-current-badge">"This browser"</span> }.into_any()
+            } else {
+                view! {
+                    <button
+                        type="button"
+                        class="btn btn-secondary session-revoke-btn"
+                        on:click=move |_| {
+                            spawn_server_action_refetch_resource(revoke_session(session_id), sessions);
+                        }
+                    >
+                        "Revoke"
+                    </button>
+                }
+                    .into_any()
+            }}
+        </li>
+    }
+}
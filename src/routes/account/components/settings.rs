@@ -0,0 +1,122 @@
+use leptos::prelude::*;
+
+use crate::auth::bump_auth_refresh;
+use crate::models::NotificationPreference;
+use crate::routes::reset_password::action_status_message;
+
+use super::super::{
+    get_notification_preference, ChangePassword, UpdateDisplayName, UpdateNotificationPreference,
+    NAME_UPDATED,
+};
+
+#[component]
+pub fn DisplayNameForm(current_name: String) -> impl IntoView {
+    let update_name = ServerAction::<UpdateDisplayName>::new();
+    let result_of_call = update_name.value();
+
+    // Refresh the NavBar and profile header with the new name
+    Effect::new(move |_| {
+        if let Some(Ok(message)) = result_of_call.get() {
+            if message == NAME_UPDATED {
+                bump_auth_refresh();
+            }
+        }
+    });
+
+    let status = move || {
+        action_status_message(
+            || result_of_call.get(),
+            "Problem while updating display name",
+        )
+    };
+
+    view! {
+        <ActionForm action=update_name>
+            <fieldset class="form-group">
+                <label for="profile-name">"Display name"</label>
+                <input id="profile-name" name="name" class="form-control" type="text"
+                    value=current_name autocomplete="name" maxlength="100" required />
+            </fieldset>
+            <p class="form-status" aria-live="polite">{status}</p>
+            <button type="submit" class="btn btn-primary">"Save name"</button>
+        </ActionForm>
+    }
+}
+
+#[component]
+pub fn PasswordForm() -> impl IntoView {
+    let change_password = ServerAction::<ChangePassword>::new();
+    let result_of_call = change_password.value();
+
+    let status =
+        move || action_status_message(|| result_of_call.get(), "Problem while changing password");
+
+    view! {
+        <ActionForm action=change_password>
+            <fieldset class="form-group">
+                <label for="profile-current-password">"Current password"</label>
+                <input id="profile-current-password" name="current_password" class="form-control"
+                    type="password" autocomplete="current-password" required />
+            </fieldset>
+            <fieldset class="form-group">
+                <label for="profile-new-password">"New password"</label>
+                <input id="profile-new-password" name="new_password" class="form-control"
+                    type="password" autocomplete="new-password" minlength="8" required />
+            </fieldset>
+            <fieldset class="form-group">
+                <label for="profile-confirm-password">"Confirm new password"</label>
+                <input id="profile-confirm-password" name="confirm" class="form-control"
+                    type="password" autocomplete="new-password" minlength="8" required />
+            </fieldset>
+            <p class="form-status" aria-live="polite">{status}</p>
+            <button type="submit" class="btn btn-primary">"Change password"</button>
+        </ActionForm>
+    }
+}
+
+#[component]
+pub fn NotificationPreferenceForm() -> impl IntoView {
+    let update_preference = ServerAction::<UpdateNotificationPreference>::new();
+    let result_of_call = update_preference.value();
+    let current = Resource::new(|| (), |_| get_notification_preference());
+
+    let status = move || {
+        action_status_message(
+            || result_of_call.get(),
+            "Problem while updating email settings",
+        )
+    };
+
+    view! {
+        <ActionForm action=update_preference>
+            <fieldset class="form-group notification-preference">
+                <legend>"Email notifications"</legend>
+                <p class="form-hint">
+                    "Stage changes, new comments and pins on ideas you wrote, voted for or commented on. They always appear under the bell."
+                </p>
+                <Suspense fallback=|| view! { <p>"Loading…"</p> }>
+                    {move || current.get().map(|result| {
+                        let selected = result.unwrap_or(NotificationPreference::Off);
+                        NotificationPreference::ALL
+                            .into_iter()
+                            .map(|preference| view! {
+                                <label class="notification-preference-option">
+                                    <input
+                                        type="radio"
+                                        name="preference"
+                                        value=preference.as_str()
+                                        checked=preference == selected
+                                    />
+                                    " "
+                                    {preference.label()}
+                                </label>
+                            })
+                            .collect_view()
+                    })}
+                </Suspense>
+            </fieldset>
+            <p class="form-status" aria-live="polite">{status}</p>
+            <button type="submit" class="btn btn-primary">"Save email settings"</button>
+        </ActionForm>
+    }
+}
@@ -0,0 +1,248 @@
+use leptos::prelude::*;
+
+use crate::models::{find_stage, CommentWithIdea, Idea, Notification, Stage};
+use crate::routes::async_helpers::spawn_server_action_refetch_resource;
+use crate::routes::ideas::list_stages;
+use crate::routes::view_helpers::{
+    format_relative_time, idea_visibility_notice, moderation_notice, stage_badge_color,
+    REVIEW_NOTICE,
+};
+
+use super::super::{
+    get_my_comments, get_my_ideas, get_my_notifications, get_my_voted_ideas,
+    mark_my_notifications_read, NOTIFICATIONS_SHOWN,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ProfileTab {
+    Ideas,
+    Comments,
+    Votes,
+    Notifications,
+}
+
+#[component]
+pub fn ProfileTabs() -> impl IntoView {
+    let active_tab = RwSignal::new(ProfileTab::Ideas);
+
+    view! {
+        <section class="profile-activity" aria-label="Your activity">
+            <div class="profile-tabs" role="tablist">
+                <button
+                    type="button"
+                    role="tab"
+                    class:active=move || active_tab.get() == ProfileTab::Ideas
+                    on:click=move |_| active_tab.set(ProfileTab::Ideas)
+                >"My Ideas"</button>
+                <button
+                    type="button"
+                    role="tab"
+                    class:active=move || active_tab.get() == ProfileTab::Comments
+                    on:click=move |_| active_tab.set(ProfileTab::Comments)
+                >"My Comments"</button>
+                <button
+                    type="button"
+                    role="tab"
+                    class:active=move || active_tab.get() == ProfileTab::Votes
+                    on:click=move |_| active_tab.set(ProfileTab::Votes)
+                >"My Votes"</button>
+                <button
+                    type="button"
+                    role="tab"
+                    class:active=move || active_tab.get() == ProfileTab::Notifications
+                    on:click=move |_| active_tab.set(ProfileTab::Notifications)
+                >"Notifications"</button>
+            </div>
+
+            <div class="profile-tab-content" role="tabpanel">
+                {move || match active_tab.get() {
+                    ProfileTab::Ideas => view! { <MyIdeasTab/> }.into_any(),
+                    ProfileTab::Comments => view! { <MyCommentsTab/> }.into_any(),
+                    ProfileTab::Votes => view! { <MyVotesTab/> }.into_any(),
+                    ProfileTab::Notifications => view! { <NotificationsTab/> }.into_any(),
+                }}
+            </div>
+        </section>
+    }
+}
+
+#[component]
+fn MyIdeasTab() -> impl IntoView {
+    let ideas = Resource::new(|| (), |_| async { get_my_ideas().await });
+    let stages = Resource::new(|| (), |_| async { list_stages().await });
+
+    view! {
+        <Suspense fallback=|| view! { <p>"Loading your ideas…"</p> }>
+            {move || ideas.get().map(|result| match result {
+                Ok(list) if list.is_empty() => {
+                    view! { <p class="empty-state">"You haven't submitted any ideas yet"</p> }.into_any()
+                }
+                Ok(list) => {
+                    let stages = stages.get().and_then(Result::ok).unwrap_or_default();
+                    view! { <ProfileIdeaList ideas=list stages=stages/> }.into_any()
+                }
+                Err(_) => view! { <p class="error">"Failed to load your ideas"</p> }.into_any(),
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+fn MyCommentsTab() -> impl IntoView {
+    let comments = Resource::new(|| (), |_| async { get_my_comments().await });
+
+    view! {
+        <Suspense fallback=|| view! { <p>"Loading your comments…"</p> }>
+            {move || comments.get().map(|result| match result {
+                Ok(list) if list.is_empty() => {
+                    view! { <p class="empty-state">"You haven't commented on any ideas yet"</p> }.into_any()
+                }
+                Ok(list) => view! {
+                    <ul class="profile-list">
+                        <For
+                            each=move || list.clone()
+                            key=|item| item.comment.id
+                            children=move |item: CommentWithIdea| {
+                                let hidden_notice = if item.comment.is_deleted {
+                                    Some(moderation_notice(item.comment.moderation_reason.as_deref()))
+                                } else {
+                                    item.comment.is_held.then(|| REVIEW_NOTICE.to_string())
+                                };
+                                view! {
+                                    <li class="profile-item">
+                                        <a class="profile-item-title" href=format!("/ideas/{}", item.comment.idea_id)>
+                                            {item.idea_title}
+                                        </a>
+                                        <p class="profile-item-body">{item.comment.content}</p>
+                                        {hidden_notice.map(|notice| view! { <p class="moderation-notice">{notice}</p> })}
+                                        <span class="profile-item-meta">
+                                            {format_relative_time(&item.comment.created_at)}
+                                        </span>
+                                    </li>
+                                }
+                            }
+                        />
+                    </ul>
+                }
+                    .into_any(),
+                Err(_) => view! { <p class="error">"Failed to load your comments"</p> }.into_any(),
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+fn MyVotesTab() -> impl IntoView {
+    let ideas = Resource::new(|| (), |_| async { get_my_voted_ideas().await });
+    let stages = Resource::new(|| (), |_| async { list_stages().await });
+
+    view! {
+        <Suspense fallback=|| view! { <p>"Loading your votes…"</p> }>
+            {move || ideas.get().map(|result| match result {
+                Ok(list) if list.is_empty() => {
+                    view! { <p class="empty-state">"You haven't voted for any ideas yet"</p> }.into_any()
+                }
+                Ok(list) => {
+                    let stages = stages.get().and_then(Result::ok).unwrap_or_default();
+                    view! { <ProfileIdeaList ideas=list stages=stages/> }.into_any()
+                }
+                Err(_) => view! { <p class="error">"Failed to load your votes"</p> }.into_any(),
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+fn NotificationsTab() -> impl IntoView {
+    let notifications = Resource::new(|| (), |_| async { get_my_notifications().await });
+    let has_unread = move || {
+        notifications
+            .get()
+            .and_then(|result| result.ok())
+            .is_some_and(|list| list.iter().any(|n| !n.is_read))
+    };
+
+    view! {
+        <Suspense fallback=|| view! { <p>"Loading your notifications…"</p> }>
+            <Show when=has_unread>
+                <button
+                    type="button"
+                    class="btn btn-secondary notifications-mark-read"
+                    on:click=move |_| {
+                        spawn_server_action_refetch_resource(mark_my_notifications_read(), notifications);
+                    }
+                >"Mark all as read"</button>
+            </Show>
+            {move || notifications.get().map(|result| match result {
+                Ok(list) if list.is_empty() => {
+                    view! { <p class="empty-state">"You have no notifications"</p> }.into_any()
+                }
+                Ok(list) => view! {
+                    {(list.len() as i64 >= NOTIFICATIONS_SHOWN).then(|| view! {
+                        <p class="profile-item-meta">
+                            {format!("Showing your latest {NOTIFICATIONS_SHOWN} notifications")}
+                        </p>
+                    })}
+                    <ul class="profile-list">
+                        <For
+                            each=move || list.clone()
+                            key=|n| (n.id, n.is_read)
+                            children=move |n: Notification| {
+                                view! {
+                                    <li class="profile-item notification-item" class:unread=!n.is_read>
+                                        {match n.link {
+                                            Some(link) => view! {
+                                                <a class="profile-item-body" href=link>{n.message}</a>
+                                            }
+                                                .into_any(),
+                                            None => view! { <p class="profile-item-body">{n.message}</p> }.into_any(),
+                                        }}
+                                        <span class="profile-item-meta">{format_relative_time(&n.created_at)}</span>
+                                    </li>
+                                }
+                            }
+                        />
+                    </ul>
+                }
+                    .into_any(),
+                Err(_) => view! { <p class="error">"Failed to load your notifications"</p> }.into_any(),
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+fn ProfileIdeaList(ideas: Vec<Idea>, stages: Vec<Stage>) -> impl IntoView {
+    view! {
+        <ul class="profile-list">
+            <For
+                each=move || ideas.clone()
+                key=|idea| idea.id
+                children=move |idea: Idea| {
+                    let stage_color = stage_badge_color(
+                        find_stage(&stages, &idea.stage).map_or("", |stage| stage.color.as_str()),
+                    );
+                    let hidden_notice = idea_visibility_notice(&idea);
+                    view! {
+                        <li class="profile-item">
+                            <div class="profile-item-header">
+                                <a class="profile-item-title" href=format!("/ideas/{}", idea.id)>
+                                    {idea.title.clone()}
+                                </a>
+                                <span class=format!("stage-badge stage-{}", stage_color)>{idea.stage.clone()}</span>
+                            </div>
+                            <span class="profile-item-meta">
+                                {format!(
+                                    "{} votes · {}",
+                                    idea.vote_count,
+                                    format_relative_time(&idea.created_at),
+                                )}
+                            </span>
+                            {hidden_notice.map(|notice| view! { <p class="moderation-notice">{notice}</p> })}
+                        </li>
+                    }
+                }
+            />
+        </ul>
+    }
+}
@@ -1,22 +1,30 @@
 use crate::auth::{get_user, UserSession};
 #[cfg(feature = "ssr")]
-use crate::models::{Flag, Idea};
-use crate::models::{IdeaWithAuthor, User};
+use crate::models::{Category, Flag, Idea, ModerationAction, Stage, StageColor, Tag};
+use crate::models::{
+    CategoryModerator, CategoryWithCount, DuplicatePair, FilterMatch, FlagReason, IdeaWithAuthor,
+    ModerationLogEntry, ModerationLogFilter, TagWithCount, User, WordFilter,
+};
 use leptos::prelude::*;
 use leptos_meta::Title;
 use leptos_router::components::A;
 
+#[cfg(feature = "ssr")]
+use crate::routes::paths;
+
+#[cfg(feature = "ssr")]
+use crate::auth::CurrentUser;
 #[cfg(feature = "ssr")]
 use async_stream::try_stream;
 #[cfg(feature = "ssr")]
+use axum::Extension;
+#[cfg(feature = "ssr")]
 use axum::{
     body::{Body, Bytes},
     http::{header, HeaderMap, HeaderValue, StatusCode},
     response::IntoResponse,
 };
 #[cfg(feature = "ssr")]
-use axum_extra::extract::CookieJar;
-#[cfg(feature = "ssr")]
 use futures_util::TryStreamExt;
 
 mod components;
@@ -28,8 +36,8 @@ use components::AdminDashboard;
 
 #[server]
 pub async fn get_admin_stats() -> Result<AdminStats, ServerFnError> {
-    use crate::auth::require_moderator;
-    require_moderator().await?;
+    use crate::auth::require_moderator_scope;
+    let (_, scope) = require_moderator_scope().await?;
 
     let (total_ideas, total_votes) = Idea::get_statistics()
         .await
@@ -43,7 +51,9 @@ pub async fn get_admin_stats() -> Result<AdminStats, ServerFnError> {
     let flagged_items = Flag::get_flagged_items()
         .await
         .map_err(|e| ServerFnError::new(format!("Failed to get flagged items: {}", e)))?
-        .len() as i64;
+        .iter()
+        .filter(|item| flagged_item_in_scope(item.category_id, &scope))
+        .count() as i64;
 
     Ok(AdminStats {
         total_ideas,
@@ -55,8 +65,9 @@ pub async fn get_admin_stats() -> Result<AdminStats, ServerFnError> {
 
 #[server]
 pub async fn get_flagged_content() -> Result<Vec<FlaggedItemDetail>, ServerFnError> {
-    use crate::auth::require_moderator;
-    require_moderator().await?;
+    use crate::auth::require_moderator_scope;
+    use crate::models::Comment;
+    let (_, scope) = require_moderator_scope().await?;
 
     let flagged_items = Flag::get_flagged_items()
         .await
@@ -64,24 +75,63 @@ pub async fn get_flagged_content() -> Result<Vec<FlaggedItemDetail>, ServerFnErr
 
     let mut details = Vec::new();
     for item in flagged_items {
-        let content = if item.target_type == "idea" {
-            Idea::get_with_author(item.target_id)
+        if !flagged_item_in_scope(item.category_id, &scope) {
+            continue;
+        }
+        // Items deleted or marked off-topic since they were flagged drop out of
+        // the queue; auto-hidden ones stay until reviewed
+        let context = if item.target_type == "idea" {
+            Idea::get_with_author_mod(item.target_id)
                 .await
                 .map_err(|e| ServerFnError::new(format!("Failed to get idea: {}", e)))?
-                .map(|iwa| format!("{}: {}", iwa.idea.title, iwa.idea.content))
+                .filter(|iwa| !iwa.idea.is_off_topic)
+                .map(|iwa| {
+                    (
+                        format!("{}: {}", iwa.idea.title, iwa.idea.content),
+                        iwa.author_name,
+                        iwa.idea.id,
+                        iwa.idea.title,
+                    )
+                })
         } else {
-            // For comments, just get the content
-            Some(format!("Comment ID: {}", item.target_id))
+            Comment::get_with_context(item.target_id)
+                .await
+                .map_err(|e| ServerFnError::new(format!("Failed to get comment: {}", e)))?
+                .filter(|(cwi, _)| !cwi.comment.is_deleted)
+                .map(|(cwi, author_name)| {
+                    (
+                        cwi.comment.content,
+                        author_name,
+                        cwi.comment.idea_id,
+                        cwi.idea_title,
+                    )
+                })
+        };
+        let Some((content, author_name, idea_id, idea_title)) = context else {
+            continue;
         };
 
-        if let Some(content_text) = content {
-            details.push(FlaggedItemDetail {
-                target_type: item.target_type,
-                target_id: item.target_id,
-                flag_count: item.flag_count,
-                content_preview: content_text.chars().take(200).collect(),
-            });
-        }
+        let flags = Flag::get_for_target(&item.target_type, item.target_id)
+            .await
+            .map_err(|e| ServerFnError::new(format!("Failed to get flags: {}", e)))?;
+        let (reasons, flag_details) = summarize_flag_reasons(
+            flags
+                .iter()
+                .map(|flag| (flag.reason.as_str(), flag.details.as_deref())),
+        );
+
+        details.push(FlaggedItemDetail {
+            target_type: item.target_type,
+            target_id: item.target_id,
+            flag_count: item.flag_count,
+            content_preview: content.chars().take(200).collect(),
+            author_name,
+            idea_id,
+            idea_title,
+            reasons,
+            details: flag_details,
+            auto_hidden: item.auto_hidden,
+        });
     }
 
     Ok(details)
@@ -89,12 +139,36 @@ pub async fn get_flagged_content() -> Result<Vec<FlaggedItemDetail>, ServerFnErr
 
 #[server]
 pub async fn clear_flags_action(target_type: String, target_id: i32) -> Result<(), ServerFnError> {
-    use crate::auth::require_moderator;
-    require_moderator().await?;
+    use crate::auth::{require_comment_moderator, require_idea_moderator};
+    use crate::routes::validation_helpers::validate_flag_target;
+    validate_flag_target(&target_type)?;
+    let user = if target_type == "comment" {
+        require_comment_moderator(target_id).await?
+    } else {
+        require_idea_moderator(target_id).await?
+    };
 
-    Flag::clear_flags(&target_type, target_id)
+    // Unflagging means the flags were unfounded: restore the item if it was
+    // auto-hidden and give the flaggers less weight next time
+    let cleared = Flag::dismiss_flags(&target_type, target_id)
         .await
         .map_err(|e| ServerFnError::new(format!("Failed to clear flags: {}", e)))?;
+    let restored = release_auto_hide(&target_type, target_id).await?;
+
+    record_moderation(
+        &user,
+        ModerationAction::ClearFlags,
+        &target_type,
+        target_id,
+        Some(format!("{cleared} flags")),
+        restored.then(|| "restored".to_string()),
+        None,
+    )
+    .await;
+
+    if restored && target_type == "comment" {
+        announce_released_comment(target_id).await;
+    }
 
     Ok(())
 }
@@ -103,66 +177,337 @@ pub async fn clear_flags_action(target_type: String, target_id: i32) -> Result<(
 pub async fn mark_idea_off_topic_action(
     idea_id: i32,
     is_off_topic: bool,
+    reason: Option<String>,
 ) -> Result<(), ServerFnError> {
-    use crate::auth::require_moderator;
-    require_moderator().await?;
+    use crate::auth::require_idea_moderator;
+    use crate::routes::validation_helpers::validate_moderation_reason;
+    let user = require_idea_moderator(idea_id).await?;
+
+    // Hiding an idea needs a reason for the author; restoring clears it
+    let reason = if is_off_topic {
+        Some(validate_moderation_reason(
+            reason.as_deref().unwrap_or_default(),
+        )?)
+    } else {
+        None
+    };
+
+    let before = fetch_idea_for_log(idea_id).await?;
 
-    Idea::mark_off_topic(idea_id, is_off_topic)
+    Idea::mark_off_topic(idea_id, is_off_topic, reason.clone())
         .await
         .map_err(|e| ServerFnError::new(format!("Failed to mark as off-topic: {}", e)))?;
 
-    // Clear flags when marking off-topic
+    // Clear flags when marking off-topic; the off-topic flag now hides it
     if is_off_topic {
         Flag::clear_flags("idea", idea_id)
             .await
             .map_err(|e| ServerFnError::new(format!("Failed to clear flags: {}", e)))?;
+        release_auto_hide("idea", idea_id).await?;
+    }
+
+    record_moderation(
+        &user,
+        ModerationAction::SetOffTopic,
+        "idea",
+        idea_id,
+        Some(before.is_off_topic.to_string()),
+        Some(is_off_topic.to_string()),
+        reason.clone(),
+    )
+    .await;
+
+    if let Some(reason) = reason {
+        notify_author(
+            before.user_id,
+            format!(
+                "Your idea \"{}\" was hidden by a moderator: {}",
+                before.title, reason
+            ),
+            Some(format!("/ideas/{}", idea_id)),
+        )
+        .await;
     }
 
     Ok(())
 }
 
 #[server]
-pub async fn delete_idea_action(idea_id: i32) -> Result<(), ServerFnError> {
-    use crate::auth::require_moderator;
-    require_moderator().await?;
+pub async fn delete_idea_action(idea_id: i32, reason: String) -> Result<(), ServerFnError> {
+    use crate::auth::require_idea_moderator;
+    use crate::routes::validation_helpers::validate_moderation_reason;
+    let user = require_idea_moderator(idea_id).await?;
+    let reason = validate_moderation_reason(&reason)?;
+
+    let before = fetch_idea_for_log(idea_id).await?;
 
     Idea::delete(idea_id)
         .await
         .map_err(|e| ServerFnError::new(format!("Failed to delete idea: {}", e)))?;
 
+    notify_author(
+        before.user_id,
+        format!(
+            "Your idea \"{}\" was deleted by a moderator: {}",
+            before.title, reason
+        ),
+        None,
+    )
+    .await;
+
+    record_moderation(
+        &user,
+        ModerationAction::DeleteIdea,
+        "idea",
+        idea_id,
+        Some(format!("{}: {}", before.title, before.content)),
+        None,
+        Some(reason),
+    )
+    .await;
+
     Ok(())
 }
 
+/// Move an idea to another stage, optionally with an official response in
+/// Markdown that is shown on the idea's timeline.
 #[server]
-pub async fn update_idea_stage_action(idea_id: i32, stage: String) -> Result<(), ServerFnError> {
-    use crate::auth::require_moderator;
-    require_moderator().await?;
+pub async fn update_idea_stage_action(
+    idea_id: i32,
+    stage: String,
+    response: Option<String>,
+) -> Result<(), ServerFnError> {
+    use crate::auth::require_idea_moderator;
+    use crate::routes::validation_helpers::validate_official_response;
+    let user = require_idea_moderator(idea_id).await?;
+
+    let response = validate_official_response(response.as_deref())?;
+    let before = fetch_idea_for_log(idea_id).await?;
+    if before.stage == stage {
+        return Ok(());
+    }
 
-    Idea::update_stage(idea_id, stage)
+    let moved = Idea::update_stage(idea_id, &before.stage, &stage, user.id, response.as_deref())
         .await
         .map_err(|e| ServerFnError::new(format!("Failed to update stage: {}", e)))?;
+    if !moved {
+        return Err(ServerFnError::new(format!(
+            "Ideas in {} cannot move to {}",
+            before.stage, stage
+        )));
+    }
+
+    record_moderation(
+        &user,
+        ModerationAction::UpdateStage,
+        "idea",
+        idea_id,
+        Some(before.stage.clone()),
+        Some(stage.clone()),
+        None,
+    )
+    .await;
+
+    let message = if response.is_some() {
+        format!(
+            "\"{}\" moved to the {} stage with an official response",
+            before.title, stage
+        )
+    } else {
+        format!("\"{}\" moved to the {} stage", before.title, stage)
+    };
+    notify_followers(idea_id, user.id, message).await;
 
     Ok(())
 }
 
 #[server]
 pub async fn toggle_idea_pin_action(idea_id: i32) -> Result<bool, ServerFnError> {
-    use crate::auth::require_moderator;
-    require_moderator().await?;
+    use crate::auth::require_idea_moderator;
+    let user = require_idea_moderator(idea_id).await?;
 
-    Idea::toggle_pin(idea_id)
+    let before = fetch_idea_for_log(idea_id).await?;
+
+    let pinned = Idea::toggle_pin(idea_id)
         .await
-        .map_err(|e| ServerFnError::new(format!("Failed to toggle pin: {}", e)))
+        .map_err(|e| ServerFnError::new(format!("Failed to toggle pin: {}", e)))?;
+
+    record_moderation(
+        &user,
+        ModerationAction::TogglePin,
+        "idea",
+        idea_id,
+        Some((!pinned).to_string()),
+        Some(pinned.to_string()),
+        None,
+    )
+    .await;
+
+    if pinned {
+        // A moderator pinning their own idea is not told about it
+        crate::notifier::notify(
+            [before.user_id],
+            Some(user.id),
+            format!(
+                "Your idea \"{}\" was pinned to the top of the board",
+                before.title
+            ),
+            Some(format!("/ideas/{}", idea_id)),
+        )
+        .await;
+    }
+
+    Ok(pinned)
 }
 
 #[server]
 pub async fn get_off_topic_ideas() -> Result<Vec<IdeaWithAuthor>, ServerFnError> {
-    use crate::auth::require_moderator;
-    require_moderator().await?;
+    use crate::auth::require_moderator_scope;
+    let (_, scope) = require_moderator_scope().await?;
+
+    let ideas = Idea::get_off_topic()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to get off-topic ideas: {}", e)))?;
+    Ok(ideas_in_scope(ideas, &scope))
+}
+
+#[server]
+pub async fn get_pending_ideas() -> Result<Vec<IdeaWithAuthor>, ServerFnError> {
+    use crate::auth::require_moderator_scope;
+    let (_, scope) = require_moderator_scope().await?;
+
+    let ideas = Idea::get_pending()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to get pending ideas: {}", e)))?;
+    Ok(ideas_in_scope(ideas, &scope))
+}
+
+#[server]
+pub async fn approve_idea_action(idea_id: i32) -> Result<(), ServerFnError> {
+    use crate::auth::require_idea_moderator;
+    let user = require_idea_moderator(idea_id).await?;
+
+    let Some((author_id, title)) = Idea::approve(idea_id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to approve idea: {}", e)))?
+    else {
+        return Err(ServerFnError::new("Idea is not waiting for approval"));
+    };
+
+    record_moderation(
+        &user,
+        ModerationAction::ApproveIdea,
+        "idea",
+        idea_id,
+        Some("pending".to_string()),
+        Some("published".to_string()),
+        None,
+    )
+    .await;
+
+    notify_author(
+        author_id,
+        format!("Your idea \"{}\" was approved and is now public", title),
+        Some(format!("/ideas/{}", idea_id)),
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Merge a duplicate idea into the one that stays, moving its votes and
+/// comments across.
+#[server]
+pub async fn merge_ideas_action(target_id: i32, duplicate_id: i32) -> Result<(), ServerFnError> {
+    use crate::auth::require_idea_moderator;
+    require_idea_moderator(duplicate_id).await?;
+    let user = require_idea_moderator(target_id).await?;
+
+    if target_id == duplicate_id {
+        return Err(ServerFnError::new("An idea cannot be merged into itself"));
+    }
+    let target = fetch_idea_for_log(target_id).await?;
+    let duplicate = fetch_idea_for_log(duplicate_id).await?;
+    for (which, idea) in [("The idea to keep", &target), ("The duplicate", &duplicate)] {
+        if let Some(blocker) = idea.merge_blocker() {
+            return Err(ServerFnError::new(format!("{} {}", which, blocker)));
+        }
+    }
+
+    let Some(summary) = Idea::merge(target_id, duplicate_id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to merge ideas: {}", e)))?
+    else {
+        return Err(ServerFnError::new(
+            "One of these ideas was merged or unpublished meanwhile",
+        ));
+    };
+
+    record_moderation(
+        &user,
+        ModerationAction::MergeIdea,
+        "idea",
+        duplicate_id,
+        Some(duplicate.title.clone()),
+        Some(format!(
+            "merged into #{} ({} votes, {} comments moved)",
+            target_id, summary.votes_moved, summary.comments_moved
+        )),
+        None,
+    )
+    .await;
+
+    notify_author(
+        duplicate.user_id,
+        format!(
+            "Your idea \"{}\" was merged into \"{}\", along with its votes and comments",
+            duplicate.title, target.title
+        ),
+        Some(format!("/ideas/{}", target_id)),
+    )
+    .await;
+
+    Ok(())
+}
 
-    Idea::get_off_topic()
+#[server]
+pub async fn reject_idea_action(idea_id: i32, reason: String) -> Result<(), ServerFnError> {
+    use crate::auth::require_idea_moderator;
+    use crate::routes::validation_helpers::validate_moderation_reason;
+    let user = require_idea_moderator(idea_id).await?;
+    let reason = validate_moderation_reason(&reason)?;
+
+    let before = fetch_idea_for_log(idea_id).await?;
+    if !before.awaits_approval() {
+        return Err(ServerFnError::new("Idea is not waiting for approval"));
+    }
+
+    Idea::delete(idea_id)
         .await
-        .map_err(|e| ServerFnError::new(format!("Failed to get off-topic ideas: {}", e)))
+        .map_err(|e| ServerFnError::new(format!("Failed to reject idea: {}", e)))?;
+
+    notify_author(
+        before.user_id,
+        format!(
+            "Your idea \"{}\" was not approved: {}",
+            before.title, reason
+        ),
+        None,
+    )
+    .await;
+
+    record_moderation(
+        &user,
+        ModerationAction::RejectIdea,
+        "idea",
+        idea_id,
+        Some(format!("{}: {}", before.title, before.content)),
+        None,
+        Some(reason),
+    )
+    .await;
+
+    Ok(())
 }
 
 #[server]
@@ -178,26 +523,631 @@ pub async fn get_all_users_admin() -> Result<Vec<User>, ServerFnError> {
 #[server]
 pub async fn update_user_role_action(user_id: i32, role: i16) -> Result<(), ServerFnError> {
     use crate::auth::require_admin;
-    require_admin().await?;
+    let admin = require_admin().await?;
+
+    let before = fetch_user_for_log(user_id).await?;
 
     User::update_role(user_id, role)
         .await
-        .map_err(|e| ServerFnError::new(format!("Failed to update role: {}", e)))
+        .map_err(|e| ServerFnError::new(format!("Failed to update role: {}", e)))?;
+
+    record_moderation(
+        &admin,
+        ModerationAction::UpdateUserRole,
+        "user",
+        user_id,
+        Some(role_name(before.role).to_string()),
+        Some(role_name(role).to_string()),
+        None,
+    )
+    .await;
+
+    Ok(())
 }
 
 #[server]
 pub async fn delete_user_action(user_id: i32) -> Result<(), ServerFnError> {
     use crate::auth::require_admin;
-    require_admin().await?;
+    let admin = require_admin().await?;
+
+    let before = fetch_user_for_log(user_id).await?;
 
     User::delete(user_id)
         .await
-        .map_err(|e| ServerFnError::new(format!("Failed to delete user: {}", e)))
+        .map_err(|e| ServerFnError::new(format!("Failed to delete user: {}", e)))?;
+
+    record_moderation(
+        &admin,
+        ModerationAction::DeleteUser,
+        "user",
+        user_id,
+        Some(format!("{} <{}>", before.name, before.email)),
+        None,
+        None,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// How many possible duplicate pairs the moderator view lists
+#[cfg(feature = "ssr")]
+const DUPLICATE_PAIRS_LIMIT: i64 = 50;
+
+#[server]
+pub async fn get_possible_duplicates() -> Result<Vec<DuplicatePair>, ServerFnError> {
+    use crate::auth::require_moderator_scope;
+    let (_, scope) = require_moderator_scope().await?;
+
+    Idea::get_possible_duplicates(&scope, DUPLICATE_PAIRS_LIMIT)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to get possible duplicates: {}", e)))
+}
+
+#[server]
+pub async fn get_word_filters() -> Result<Vec<WordFilter>, ServerFnError> {
+    use crate::auth::require_admin;
+    require_admin().await?;
+
+    WordFilter::get_all()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to get word filter: {}", e)))
+}
+
+#[server]
+pub async fn add_word_filter(
+    term: String,
+    match_mode: String,
+    severity: String,
+) -> Result<(), ServerFnError> {
+    use crate::auth::require_admin;
+    use crate::models::{FilterSeverity, MatchMode};
+    let admin = require_admin().await?;
+
+    let term = term.trim();
+    if term.is_empty() {
+        return Err(ServerFnError::new("Enter a term to filter"));
+    }
+    if term.len() > 200 {
+        return Err(ServerFnError::new("Term cannot exceed 200 characters"));
+    }
+    let match_mode = MatchMode::from_name(&match_mode)
+        .ok_or_else(|| ServerFnError::new("Unknown match mode"))?;
+    let severity = FilterSeverity::from_name(&severity)
+        .ok_or_else(|| ServerFnError::new("Unknown severity"))?;
+    crate::profanity::validate_term(term, match_mode)
+        .map_err(|e| ServerFnError::new(format!("Invalid pattern: {e}")))?;
+
+    let filter = WordFilter::create(term, match_mode, severity)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                ServerFnError::new("That term is already in the filter")
+            }
+            e => ServerFnError::new(format!("Failed to add term: {}", e)),
+        })?;
+    reload_word_filter().await?;
+
+    record_moderation(
+        &admin,
+        ModerationAction::AddWordFilter,
+        "word_filter",
+        filter.id,
+        None,
+        Some(format!(
+            "{} ({}, {})",
+            filter.term,
+            match_mode.label(),
+            severity.label()
+        )),
+        None,
+    )
+    .await;
+
+    Ok(())
+}
+
+#[server]
+pub async fn remove_word_filter(filter_id: i32) -> Result<(), ServerFnError> {
+    use crate::auth::require_admin;
+    let admin = require_admin().await?;
+
+    let filter = WordFilter::delete(filter_id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to remove term: {}", e)))?
+        .ok_or_else(|| ServerFnError::new("Term not found"))?;
+    reload_word_filter().await?;
+
+    record_moderation(
+        &admin,
+        ModerationAction::RemoveWordFilter,
+        "word_filter",
+        filter.id,
+        Some(format!(
+            "{} ({}, {})",
+            filter.term, filter.match_mode, filter.severity
+        )),
+        None,
+        None,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Check text against the word filter without posting it.
+#[server]
+pub async fn test_word_filter(text: String) -> Result<Option<FilterMatch>, ServerFnError> {
+    use crate::auth::require_admin;
+    require_admin().await?;
+
+    Ok(crate::profanity::active().check(&[&text]))
+}
+
+#[server]
+pub async fn get_tags() -> Result<Vec<TagWithCount>, ServerFnError> {
+    use crate::auth::require_admin;
+    require_admin().await?;
+
+    Tag::get_all_with_counts()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to get tags: {}", e)))
+}
+
+#[server]
+pub async fn rename_tag(tag_id: i32, name: String) -> Result<(), ServerFnError> {
+    use crate::auth::require_admin;
+    use crate::routes::validation_helpers::validate_idea_tags;
+    let admin = require_admin().await?;
+
+    let name = match validate_idea_tags(&name)?.as_slice() {
+        [name] => name.clone(),
+        _ => return Err(ServerFnError::new("Enter one new name for the tag")),
+    };
+    let before = Tag::rename(tag_id, &name)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                ServerFnError::new("Another tag already has that name; merge the two tags instead")
+            }
+            e => ServerFnError::new(format!("Failed to rename tag: {}", e)),
+        })?
+        .ok_or_else(|| ServerFnError::new("Tag not found"))?;
+
+    record_moderation(
+        &admin,
+        ModerationAction::RenameTag,
+        "tag",
+        tag_id,
+        Some(before.name),
+        Some(name),
+        None,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Move every idea with the source tag to the target tag and delete the
+/// source.
+#[server]
+pub async fn merge_tags(source_id: i32, target_id: i32) -> Result<(), ServerFnError> {
+    use crate::auth::require_admin;
+    let admin = require_admin().await?;
+
+    if source_id == target_id {
+        return Err(ServerFnError::new("Cannot merge a tag into itself"));
+    }
+    let (source, moved) = Tag::merge(source_id, target_id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to merge tags: {}", e)))?
+        .ok_or_else(|| ServerFnError::new("Tag not found"))?;
+
+    record_moderation(
+        &admin,
+        ModerationAction::MergeTags,
+        "tag",
+        target_id,
+        Some(source.name),
+        Some(format!("{} ideas moved", moved)),
+        None,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Retire a tag so it cannot be added to more ideas, or restore it.
+#[server]
+pub async fn set_tag_retired(tag_id: i32, retired: bool) -> Result<(), ServerFnError> {
+    use crate::auth::require_admin;
+    let admin = require_admin().await?;
+
+    let tag = Tag::set_retired(tag_id, retired)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to update tag: {}", e)))?
+        .ok_or_else(|| ServerFnError::new("Tag not found"))?;
+
+    let action = if retired {
+        ModerationAction::RetireTag
+    } else {
+        ModerationAction::RestoreTag
+    };
+    record_moderation(&admin, action, "tag", tag.id, None, Some(tag.name), None).await;
+
+    Ok(())
+}
+
+#[server]
+pub async fn get_categories_admin() -> Result<Vec<CategoryWithCount>, ServerFnError> {
+    use crate::auth::require_admin;
+    require_admin().await?;
+
+    Category::get_all_with_counts()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to get categories: {}", e)))
+}
+
+#[server]
+pub async fn get_category_moderators() -> Result<Vec<CategoryModerator>, ServerFnError> {
+    use crate::auth::require_admin;
+    require_admin().await?;
+
+    Category::get_moderators()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to get category moderators: {}", e)))
+}
+
+#[server]
+pub async fn create_category(name: String, description: String) -> Result<(), ServerFnError> {
+    use crate::auth::require_admin;
+    use crate::routes::validation_helpers::validate_category_name;
+    let admin = require_admin().await?;
+
+    let (name, slug) = validate_category_name(&name)?;
+    let description = validate_category_description(&description)?;
+    let category = Category::create(&name, &slug, &description)
+        .await
+        .map_err(category_write_error)?;
+
+    record_moderation(
+        &admin,
+        ModerationAction::CreateCategory,
+        "category",
+        category.id,
+        None,
+        Some(category.name),
+        None,
+    )
+    .await;
+
+    Ok(())
+}
+
+#[server]
+pub async fn update_category(
+    category_id: i32,
+    name: String,
+    description: String,
+) -> Result<(), ServerFnError> {
+    use crate::auth::require_admin;
+    use crate::routes::validation_helpers::validate_category_name;
+    let admin = require_admin().await?;
+
+    let (name, slug) = validate_category_name(&name)?;
+    let description = validate_category_description(&description)?;
+    let before = Category::update(category_id, &name, &slug, &description)
+        .await
+        .map_err(category_write_error)?
+        .ok_or_else(|| ServerFnError::new("Category not found"))?;
+
+    record_moderation(
+        &admin,
+        ModerationAction::EditCategory,
+        "category",
+        category_id,
+        Some(before.name),
+        Some(name),
+        None,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Archive a category so it takes no new ideas, or restore it.
+#[server]
+pub async fn set_category_archived(category_id: i32, archived: bool) -> Result<(), ServerFnError> {
+    use crate::auth::require_admin;
+    let admin = require_admin().await?;
+
+    let category = Category::set_archived(category_id, archived)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to update category: {}", e)))?
+        .ok_or_else(|| ServerFnError::new("Category not found"))?;
+
+    let action = if archived {
+        ModerationAction::ArchiveCategory
+    } else {
+        ModerationAction::RestoreCategory
+    };
+    record_moderation(
+        &admin,
+        action,
+        "category",
+        category.id,
+        None,
+        Some(category.name),
+        None,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Limit a moderator to a category. Moderators with no categories
+/// moderate every category.
+#[server]
+pub async fn assign_category_moderator(
+    category_id: i32,
+    user_id: i32,
+) -> Result<(), ServerFnError> {
+    use crate::auth::require_admin;
+    let admin = require_admin().await?;
+
+    let user = fetch_user_for_log(user_id).await?;
+    if user.role < 1 {
+        return Err(ServerFnError::new(
+            "Make the user a moderator before assigning categories",
+        ));
+    }
+    let category = fetch_category_for_log(category_id).await?;
+    let added = Category::add_moderator(category_id, user_id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to assign moderator: {}", e)))?;
+    if !added {
+        return Ok(());
+    }
+
+    record_moderation(
+        &admin,
+        ModerationAction::AssignCategoryModerator,
+        "category",
+        category_id,
+        None,
+        Some(format!("{} → {}", user.name, category.name)),
+        None,
+    )
+    .await;
+
+    Ok(())
+}
+
+#[server]
+pub async fn remove_category_moderator(
+    category_id: i32,
+    user_id: i32,
+) -> Result<(), ServerFnError> {
+    use crate::auth::require_admin;
+    let admin = require_admin().await?;
+
+    let user = fetch_user_for_log(user_id).await?;
+    let category = fetch_category_for_log(category_id).await?;
+    let scope = Category::moderator_scope(user_id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to remove moderator: {}", e)))?;
+    if scope == [category_id] {
+        return Err(ServerFnError::new(format!(
+            "{} is only assigned to {}; without it they would moderate every category. \
+             Assign another category first, or demote them instead.",
+            user.name, category.name
+        )));
+    }
+    let removed = Category::remove_moderator(category_id, user_id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to remove moderator: {}", e)))?;
+    if !removed {
+        return Ok(());
+    }
+
+    record_moderation(
+        &admin,
+        ModerationAction::RemoveCategoryModerator,
+        "category",
+        category_id,
+        Some(format!("{} → {}", user.name, category.name)),
+        None,
+        None,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Add a stage at the end of the workflow. It has no transitions until
+/// they are set.
+#[server]
+pub async fn create_stage(
+    name: String,
+    color: String,
+    is_terminal: bool,
+) -> Result<(), ServerFnError> {
+    use crate::auth::require_admin;
+    let admin = require_admin().await?;
+
+    let (name, color) = validate_stage(&name, &color)?;
+    let stage = Stage::create(&name, color.as_str(), is_terminal)
+        .await
+        .map_err(stage_write_error)?;
+
+    record_moderation(
+        &admin,
+        ModerationAction::CreateStage,
+        "stage",
+        stage.id,
+        None,
+        Some(stage.name),
+        None,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Rename, recolor or change whether a stage closes its ideas. Ideas in
+/// the stage follow a rename.
+#[server]
+pub async fn edit_stage(
+    stage_id: i32,
+    name: String,
+    color: String,
+    is_terminal: bool,
+) -> Result<(), ServerFnError> {
+    use crate::auth::require_admin;
+    let admin = require_admin().await?;
+
+    let (name, color) = validate_stage(&name, &color)?;
+    if is_terminal {
+        ensure_open_stage_remains(stage_id).await?;
+    }
+    let before = Stage::update(stage_id, &name, color.as_str(), is_terminal)
+        .await
+        .map_err(stage_write_error)?
+        .ok_or_else(|| ServerFnError::new("Stage not found"))?;
+
+    fn describe(name: &str, color: &str, is_terminal: bool) -> String {
+        let label = StageColor::from_name(color).map_or(color, |found| found.label());
+        let closing = if is_terminal { ", closes ideas" } else { "" };
+        format!("{name} ({label}{closing})")
+    }
+    record_moderation(
+        &admin,
+        ModerationAction::EditStage,
+        "stage",
+        stage_id,
+        Some(describe(&before.name, &before.color, before.is_terminal)),
+        Some(describe(&name, color.as_str(), is_terminal)),
+        None,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Move a stage one place earlier or later in the workflow.
+#[server]
+pub async fn move_stage(stage_id: i32, earlier: bool) -> Result<(), ServerFnError> {
+    use crate::auth::require_admin;
+    let admin = require_admin().await?;
+
+    let name = Stage::move_stage(stage_id, earlier)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to move stage: {}", e)))?
+        .ok_or_else(|| ServerFnError::new("Stage not found"))?;
+
+    record_moderation(
+        &admin,
+        ModerationAction::ReorderStage,
+        "stage",
+        stage_id,
+        None,
+        Some(format!(
+            "{} moved {}",
+            name,
+            if earlier { "earlier" } else { "later" }
+        )),
+        None,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Replace the stages an idea in this stage may move to.
+#[server]
+pub async fn set_stage_transitions(
+    stage_id: i32,
+    to_stage_ids: Vec<i32>,
+) -> Result<(), ServerFnError> {
+    use crate::auth::require_admin;
+    let admin = require_admin().await?;
+
+    let (before, after) = Stage::set_transitions(stage_id, &to_stage_ids)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to update transitions: {}", e)))?
+        .ok_or_else(|| ServerFnError::new("Stage not found"))?;
+
+    if before != after {
+        record_moderation(
+            &admin,
+            ModerationAction::SetStageTransitions,
+            "stage",
+            stage_id,
+            Some(before.join(", ")),
+            Some(after.join(", ")),
+            None,
+        )
+        .await;
+    }
+
+    Ok(())
+}
+
+/// Delete a stage no idea is in.
+#[server]
+pub async fn delete_stage(stage_id: i32) -> Result<(), ServerFnError> {
+    use crate::auth::require_admin;
+    let admin = require_admin().await?;
+
+    ensure_open_stage_remains(stage_id).await?;
+    let name = Stage::delete(stage_id)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_foreign_key_violation() => ServerFnError::new(
+                "Ideas are still in this stage; move them to another stage first",
+            ),
+            e => ServerFnError::new(format!("Failed to delete stage: {}", e)),
+        })?
+        .ok_or_else(|| ServerFnError::new("Stage not found"))?;
+
+    record_moderation(
+        &admin,
+        ModerationAction::DeleteStage,
+        "stage",
+        stage_id,
+        Some(name),
+        None,
+        None,
+    )
+    .await;
+
+    Ok(())
+}
+
+#[server]
+pub async fn get_moderation_log(
+    filter: ModerationLogFilter,
+) -> Result<Vec<ModerationLogEntry>, ServerFnError> {
+    use crate::auth::require_admin;
+    require_admin().await?;
+
+    let non_empty = |value: Option<String>| value.filter(|v| !v.trim().is_empty());
+    let filter = ModerationLogFilter {
+        action: non_empty(filter.action),
+        target_type: non_empty(filter.target_type),
+        actor: non_empty(filter.actor),
+    };
+
+    ModerationLogEntry::list(&filter, AUDIT_LOG_PAGE_SIZE)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to get moderation log: {}", e)))
 }
 
 #[cfg(feature = "ssr")]
-pub async fn admin_export_ideas_csv(jar: CookieJar) -> Result<impl IntoResponse, StatusCode> {
-    require_admin_cookie_jar(&jar).await?;
+pub async fn admin_export_ideas_csv(
+    Extension(current_user): Extension<Option<CurrentUser>>,
+) -> Result<impl IntoResponse, StatusCode> {
+    require_admin_export(&current_user)?;
     Ok((
         csv_download_headers("ideas_export.csv")?,
         ideas_csv_body().await?,
@@ -205,8 +1155,21 @@ pub async fn admin_export_ideas_csv(jar: CookieJar) -> Result<impl IntoResponse,
 }
 
 #[cfg(feature = "ssr")]
-pub async fn admin_export_comments_csv(jar: CookieJar) -> Result<impl IntoResponse, StatusCode> {
-    require_admin_cookie_jar(&jar).await?;
+pub async fn admin_export_moderation_log_csv(
+    Extension(current_user): Extension<Option<CurrentUser>>,
+) -> Result<impl IntoResponse, StatusCode> {
+    require_admin_export(&current_user)?;
+    Ok((
+        csv_download_headers("moderation_log_export.csv")?,
+        moderation_log_csv_body().await?,
+    ))
+}
+
+#[cfg(feature = "ssr")]
+pub async fn admin_export_comments_csv(
+    Extension(current_user): Extension<Option<CurrentUser>>,
+) -> Result<impl IntoResponse, StatusCode> {
+    require_admin_export(&current_user)?;
     Ok((
         csv_download_headers("comments_export.csv")?,
         comments_csv_body().await?,
@@ -231,6 +1194,16 @@ pub struct FlaggedItemDetail {
     pub target_id: i32,
     pub flag_count: i64,
     pub content_preview: String,
+    pub author_name: String,
+    /// The idea itself, or the idea a flagged comment belongs to
+    pub idea_id: i32,
+    pub idea_title: String,
+    /// Flag count per reason, most common first
+    pub reasons: Vec<(FlagReason, i64)>,
+    /// Free text flaggers added
+    pub details: Vec<String>,
+    /// Hidden automatically until a moderator reviews it
+    pub auto_hidden: bool,
 }
 
 // ============================================================================
@@ -290,6 +1263,370 @@ fn role_name(role: i16) -> &'static str {
     }
 }
 
+/// Most recent audit log entries shown in the Audit tab
+#[cfg(feature = "ssr")]
+const AUDIT_LOG_PAGE_SIZE: i64 = 500;
+
+/// Write a moderator action to the audit log. A failed write is reported in
+/// the server log but does not fail the action, which has already happened.
+#[cfg(feature = "ssr")]
+pub(crate) async fn record_moderation(
+    actor: &UserSession,
+    action: ModerationAction,
+    target_type: &str,
+    target_id: i32,
+    before_value: Option<String>,
+    after_value: Option<String>,
+    reason: Option<String>,
+) {
+    if let Err(e) = ModerationLogEntry::record(
+        actor.id,
+        action,
+        target_type,
+        target_id,
+        before_value,
+        after_value,
+        reason,
+    )
+    .await
+    {
+        tracing::error!(
+            "Failed to record moderation action {} on {target_type} {target_id}: {e}",
+            action.as_str()
+        );
+    }
+}
+
+#[cfg(feature = "ssr")]
+async fn reload_word_filter() -> Result<(), ServerFnError> {
+    crate::profanity::reload()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to reload word filter: {}", e)))
+}
+
+/// Show an auto-hidden idea or held comment again once a moderator has
+/// reviewed it, returning whether it was hidden.
+#[cfg(feature = "ssr")]
+async fn release_auto_hide(target_type: &str, target_id: i32) -> Result<bool, ServerFnError> {
+    use crate::models::Comment;
+
+    let released = if target_type == "idea" {
+        Idea::release_auto_hide(target_id).await
+    } else {
+        Comment::release_hold(target_id).await
+    };
+    released.map_err(|e| ServerFnError::new(format!("Failed to restore {target_type}: {e}")))
+}
+
+/// Tell an author what a moderator did to their content.
+#[cfg(feature = "ssr")]
+pub(crate) async fn notify_author(user_id: i32, message: String, link: Option<String>) {
+    crate::notifier::notify([user_id], None, message, link).await;
+}
+
+/// Tell every moderator about something that needs their review.
+#[cfg(feature = "ssr")]
+pub(crate) async fn notify_moderators(actor_id: Option<i32>, message: String) {
+    let moderators = match User::get_moderator_ids().await {
+        Ok(moderators) => moderators,
+        Err(e) => {
+            tracing::error!("Failed to fetch moderators: {e}");
+            return;
+        }
+    };
+    crate::notifier::notify(
+        moderators,
+        actor_id,
+        message,
+        Some(paths::ADMIN.to_string()),
+    )
+    .await;
+}
+
+/// Act on a word filter rule that a new or edited post matched: flag it for
+/// the moderators with the rule, hide it too if the rule says to hold it, and
+/// record the match in the audit log against the author.
+#[cfg(feature = "ssr")]
+pub(crate) async fn apply_word_filter_match(
+    author: &UserSession,
+    target_type: &str,
+    target_id: i32,
+    found: &crate::models::FilterMatch,
+) {
+    use crate::models::{Comment, FilterSeverity};
+
+    let rule = found.describe();
+    if let Err(e) =
+        Flag::add_filter_flag(target_type, target_id, format!("Word filter: {rule}")).await
+    {
+        tracing::error!("Failed to flag {target_type} {target_id} for the word filter: {e}");
+    }
+
+    let held = found.severity == FilterSeverity::Hold;
+    if held {
+        let hidden = if target_type == "idea" {
+            Idea::auto_hide(target_id).await.map(|_| ())
+        } else {
+            Comment::hold(target_id).await.map(|_| ())
+        };
+        if let Err(e) = hidden {
+            tracing::error!("Failed to hold {target_type} {target_id}: {e}");
+        }
+    }
+
+    record_moderation(
+        author,
+        ModerationAction::WordFilterMatch,
+        target_type,
+        target_id,
+        None,
+        Some(found.severity.as_str().to_string()),
+        Some(rule.clone()),
+    )
+    .await;
+
+    let outcome = if held { "held" } else { "flagged" };
+    notify_moderators(
+        Some(author.id),
+        format!(
+            "A {target_type} by {} was {outcome} by the word filter: {rule}",
+            author.name
+        ),
+    )
+    .await;
+}
+
+/// Notify the author, voters and commenters of an idea about a change made by
+/// `actor_id`.
+#[cfg(feature = "ssr")]
+pub(crate) async fn notify_followers(idea_id: i32, actor_id: i32, message: String) {
+    notify_followers_except(idea_id, actor_id, None, message).await;
+}
+
+/// Like [`notify_followers`], leaving out `skipped`, who was told about the
+/// change another way.
+#[cfg(feature = "ssr")]
+pub(crate) async fn notify_followers_except(
+    idea_id: i32,
+    actor_id: i32,
+    skipped: Option<i32>,
+    message: String,
+) {
+    let mut followers = match Idea::get_follower_ids(idea_id).await {
+        Ok(followers) => followers,
+        Err(e) => {
+            tracing::error!("Failed to fetch followers of idea {idea_id}: {e}");
+            return;
+        }
+    };
+    followers.retain(|follower| Some(*follower) != skipped);
+    crate::notifier::notify(
+        followers,
+        Some(actor_id),
+        message,
+        Some(format!("/ideas/{}", idea_id)),
+    )
+    .await;
+}
+
+/// Tell an idea's followers about a comment they can now see, and the author
+/// of the comment it replies to that they got a reply.
+#[cfg(feature = "ssr")]
+pub(crate) async fn announce_comment(
+    commenter_id: i32,
+    commenter_name: &str,
+    idea: &Idea,
+    comment_id: i32,
+    replied_to: Option<i32>,
+) {
+    let replied_to = replied_to.filter(|author_id| *author_id != commenter_id);
+    if let Some(author_id) = replied_to {
+        crate::notifier::notify(
+            [author_id],
+            Some(commenter_id),
+            format!(
+                "{} replied to your comment on \"{}\"",
+                commenter_name, idea.title
+            ),
+            Some(format!("/ideas/{}#comment-{}", idea.id, comment_id)),
+        )
+        .await;
+    }
+    notify_followers_except(
+        idea.id,
+        commenter_id,
+        replied_to,
+        format!("{} commented on \"{}\"", commenter_name, idea.title),
+    )
+    .await;
+}
+
+/// Announce a held comment once a moderator releases it, as it would have
+/// been when posted. A reply is announced to the author of the comment it is
+/// threaded under.
+#[cfg(feature = "ssr")]
+async fn announce_released_comment(comment_id: i32) {
+    use crate::models::Comment;
+
+    let found = async {
+        let Some(comment) = Comment::get_by_id(comment_id).await? else {
+            return Ok(None);
+        };
+        let Some(idea) = Idea::get_by_id(comment.idea_id).await? else {
+            return Ok(None);
+        };
+        let Some(author) = User::get_by_id(comment.user_id).await? else {
+            return Ok(None);
+        };
+        let replied_to = match comment.parent_id {
+            Some(parent_id) => Comment::get_by_id(parent_id)
+                .await?
+                .filter(|parent| !parent.is_deleted && !parent.is_held)
+                .map(|parent| parent.user_id),
+            None => None,
+        };
+        Ok::<_, sqlx::Error>(Some((comment, idea, author, replied_to)))
+    };
+    match found.await {
+        Ok(Some((comment, idea, author, replied_to))) if !comment.is_deleted => {
+            announce_comment(author.id, &author.name, &idea, comment.id, replied_to).await;
+        }
+        Ok(_) => {}
+        Err(e) => tracing::error!("Failed to announce released comment {comment_id}: {e}"),
+    }
+}
+
+/// Whether a flagged item in `category_id` belongs in the queue of a
+/// moderator with `scope`.
+/// Items that are gone have no category and only show to unscoped
+/// moderators, who see them drop out of the queue as before.
+#[cfg(feature = "ssr")]
+fn flagged_item_in_scope(category_id: Option<i32>, scope: &[i32]) -> bool {
+    use crate::models::scope_covers;
+    match category_id {
+        Some(category_id) => scope_covers(scope, category_id),
+        None => scope.is_empty(),
+    }
+}
+
+/// The ideas a moderator with `scope` may act on
+#[cfg(feature = "ssr")]
+fn ideas_in_scope(mut ideas: Vec<IdeaWithAuthor>, scope: &[i32]) -> Vec<IdeaWithAuthor> {
+    use crate::models::scope_covers;
+    ideas.retain(|iwa| scope_covers(scope, iwa.idea.category_id));
+    ideas
+}
+
+#[cfg(feature = "ssr")]
+async fn fetch_idea_for_log(idea_id: i32) -> Result<Idea, ServerFnError> {
+    Idea::get_by_id_mod(idea_id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to get idea: {}", e)))?
+        .ok_or_else(|| ServerFnError::new("Idea not found"))
+}
+
+#[cfg(feature = "ssr")]
+async fn fetch_user_for_log(user_id: i32) -> Result<User, ServerFnError> {
+    User::get_by_id(user_id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to get user: {}", e)))?
+        .ok_or_else(|| ServerFnError::new("User not found"))
+}
+
+#[cfg(feature = "ssr")]
+async fn fetch_category_for_log(category_id: i32) -> Result<Category, ServerFnError> {
+    Category::get_by_id(category_id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to get category: {}", e)))?
+        .ok_or_else(|| ServerFnError::new("Category not found"))
+}
+
+#[cfg(feature = "ssr")]
+fn validate_category_description(description: &str) -> Result<String, ServerFnError> {
+    let description = description.trim();
+    if description.chars().count() > 300 {
+        return Err(ServerFnError::new(
+            "Category description cannot exceed 300 characters",
+        ));
+    }
+    Ok(description.to_string())
+}
+
+#[cfg(feature = "ssr")]
+fn validate_stage(name: &str, color: &str) -> Result<(String, StageColor), ServerFnError> {
+    use crate::models::MAX_STAGE_NAME_CHARS;
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    if name.is_empty() {
+        return Err(ServerFnError::new("Stage name cannot be empty"));
+    }
+    if name.chars().count() > MAX_STAGE_NAME_CHARS {
+        return Err(ServerFnError::new(format!(
+            "Stage name cannot exceed {MAX_STAGE_NAME_CHARS} characters"
+        )));
+    }
+    let color =
+        StageColor::from_name(color).ok_or_else(|| ServerFnError::new("Choose a badge color"))?;
+    Ok((name, color))
+}
+
+#[cfg(feature = "ssr")]
+fn stage_write_error(e: sqlx::Error) -> ServerFnError {
+    match e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => {
+            ServerFnError::new("Another stage already has that name")
+        }
+        e => ServerFnError::new(format!("Failed to save stage: {}", e)),
+    }
+}
+
+/// Refuse to close or delete the only open stage, which new ideas start in.
+#[cfg(feature = "ssr")]
+async fn ensure_open_stage_remains(stage_id: i32) -> Result<(), ServerFnError> {
+    use crate::models::is_last_open_stage;
+
+    let stages = Stage::get_all()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to get stages: {}", e)))?;
+    if is_last_open_stage(&stages, stage_id) {
+        return Err(ServerFnError::new(
+            "New ideas start in the first open stage, so at least one stage must stay open",
+        ));
+    }
+    Ok(())
+}
+
+/// Turn a clash on the unique slug into a message an admin can act on.
+#[cfg(feature = "ssr")]
+fn category_write_error(e: sqlx::Error) -> ServerFnError {
+    match e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => {
+            ServerFnError::new("Another category already has that name")
+        }
+        e => ServerFnError::new(format!("Failed to save category: {}", e)),
+    }
+}
+
+/// Count flags per reason, most common first, and collect their free text.
+/// Unknown stored reasons count as [`FlagReason::Other`].
+#[cfg(feature = "ssr")]
+fn summarize_flag_reasons<'a>(
+    flags: impl IntoIterator<Item = (&'a str, Option<&'a str>)>,
+) -> (Vec<(FlagReason, i64)>, Vec<String>) {
+    let mut counts: Vec<(FlagReason, i64)> = Vec::new();
+    let mut details = Vec::new();
+    for (reason, detail) in flags {
+        let reason = FlagReason::from_name(reason).unwrap_or(FlagReason::Other);
+        match counts.iter_mut().find(|(counted, _)| *counted == reason) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((reason, 1)),
+        }
+        details.extend(detail.map(str::to_string));
+    }
+    // Stable sort keeps first-flagged order among equal counts
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    (counts, details)
+}
+
 #[cfg(feature = "ssr")]
 fn csv_escape(value: &str) -> String {
     let trimmed = value.trim_start_matches([' ', '\t']);
@@ -319,26 +1656,15 @@ fn csv_download_headers(filename: &str) -> Result<HeaderMap, StatusCode> {
 }
 
 #[cfg(feature = "ssr")]
-async fn require_admin_cookie_jar(jar: &CookieJar) -> Result<(), StatusCode> {
-    let Some(cookie) = jar.get("user_session") else {
-        return Err(StatusCode::UNAUTHORIZED);
-    };
-
-    let session: UserSession = serde_json::from_str(cookie.value()).map_err(|e| {
-        tracing::warn!("Rejected admin CSV export due to invalid session cookie: {e}");
-        StatusCode::UNAUTHORIZED
-    })?;
-
-    // Verify the claimed identity against the database to prevent forged cookies
-    let db_user = User::get_by_id(session.id).await.map_err(|e| {
-        tracing::error!("DB lookup failed during admin export auth: {e}");
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
-
-    match db_user {
-        Some(user) if user.role >= 2 => Ok(()),
+fn require_admin_export(current_user: &Option<CurrentUser>) -> Result<(), StatusCode> {
+    // The session middleware has already re-read the user's role from the database.
+    match current_user {
+        Some(current_user) if current_user.user.is_admin() => Ok(()),
         Some(_) => Err(StatusCode::FORBIDDEN),
-        None => Err(StatusCode::UNAUTHORIZED),
+        None => {
+            tracing::warn!("Rejected admin CSV export without a valid session cookie");
+            Err(StatusCode::UNAUTHORIZED)
+        }
     }
 }
 
@@ -533,9 +1859,121 @@ async fn comments_csv_body() -> Result<Body, StatusCode> {
     })))
 }
 
+#[cfg(feature = "ssr")]
+const MODERATION_LOG_CSV_HEADER: &[u8] =
+    b"id,created_at,actor_id,actor_name,action,target_type,target_id,before_value,after_value,reason\n";
+
+#[cfg(feature = "ssr")]
+fn format_moderation_log_row(row: &sqlx::postgres::PgRow) -> Result<String, sqlx::Error> {
+    use sqlx::Row;
+
+    let id: i32 = row.try_get("id")?;
+    let created_at: chrono::DateTime<chrono::Utc> = row.try_get("created_at")?;
+    let actor_id: Option<i32> = row.try_get("actor_id")?;
+    let actor_name: Option<String> = row.try_get("actor_name")?;
+    let action: String = row.try_get("action")?;
+    let target_type: String = row.try_get("target_type")?;
+    let target_id: i32 = row.try_get("target_id")?;
+    let before_value: Option<String> = row.try_get("before_value")?;
+    let after_value: Option<String> = row.try_get("after_value")?;
+    let reason: Option<String> = row.try_get("reason")?;
+
+    Ok(format!(
+        "{},{},{},{},{},{},{},{},{},{}\n",
+        id,
+        csv_escape(&created_at.to_rfc3339()),
+        actor_id.map(|id| id.to_string()).unwrap_or_default(),
+        csv_escape(&actor_name.unwrap_or_default()),
+        csv_escape(&action),
+        csv_escape(&target_type),
+        target_id,
+        csv_escape(&before_value.unwrap_or_default()),
+        csv_escape(&after_value.unwrap_or_default()),
+        csv_escape(&reason.unwrap_or_default()),
+    ))
+}
+
+#[cfg(feature = "ssr")]
+async fn moderation_log_csv_body() -> Result<Body, StatusCode> {
+    let mut rows = sqlx::query(
+        r#"
+        SELECT
+            l.id,
+            l.created_at,
+            l.actor_id,
+            u.name AS actor_name,
+            l.action,
+            l.target_type,
+            l.target_id,
+            l.before_value,
+            l.after_value,
+            l.reason
+        FROM moderation_log l
+        LEFT JOIN users u ON l.actor_id = u.id
+        ORDER BY l.created_at DESC, l.id DESC
+        "#,
+    )
+    .fetch(crate::database::get_db());
+
+    // Fetch first row before committing to a 200 response, so DB errors
+    // can still surface as a proper error status code.
+    let first_row = rows.try_next().await.map_err(|e| {
+        tracing::error!("Moderation log CSV query failed on first row: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let first_line = first_row
+        .as_ref()
+        .map(format_moderation_log_row)
+        .transpose()
+        .map_err(|e| {
+            tracing::error!("Moderation log CSV row formatting failed: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let stream = try_stream! {
+        yield Bytes::from_static(MODERATION_LOG_CSV_HEADER);
+
+        if let Some(line) = first_line {
+            yield Bytes::from(line);
+        }
+
+        while let Some(row) = rows.try_next().await? {
+            yield Bytes::from(format_moderation_log_row(&row)?);
+        }
+    };
+    Ok(Body::from_stream(stream.map_err(|e: sqlx::Error| {
+        tracing::error!("Moderation log CSV stream failed: {e}");
+        std::io::Error::other(e)
+    })))
+}
+
 #[cfg(all(test, feature = "ssr"))]
 mod tests {
-    use super::{csv_escape, COMMENTS_CSV_HEADER, IDEAS_CSV_HEADER};
+    use super::{
+        csv_escape, summarize_flag_reasons, FlagReason, COMMENTS_CSV_HEADER, IDEAS_CSV_HEADER,
+        MODERATION_LOG_CSV_HEADER,
+    };
+
+    #[test]
+    fn flag_reasons_are_counted_most_common_first() {
+        let (reasons, details) = summarize_flag_reasons([
+            ("spam", None),
+            ("abusive", Some("insults the author")),
+            ("abusive", None),
+            ("bogus", Some("left over")),
+        ]);
+
+        assert_eq!(
+            reasons,
+            vec![
+                (FlagReason::Abusive, 2),
+                (FlagReason::Spam, 1),
+                (FlagReason::Other, 1)
+            ]
+        );
+        assert_eq!(details, vec!["insults the author", "left over"]);
+    }
 
     #[test]
     fn csv_escape_quotes_and_escapes_embedded_quotes() {
@@ -584,4 +2022,26 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn moderation_log_csv_header_has_expected_columns() {
+        let header =
+            std::str::from_utf8(MODERATION_LOG_CSV_HEADER).expect("header should be valid UTF-8");
+        let columns: Vec<&str> = header.trim_end().split(',').collect();
+        assert_eq!(
+            columns,
+            vec![
+                "id",
+                "created_at",
+                "actor_id",
+                "actor_name",
+                "action",
+                "target_type",
+                "target_id",
+                "before_value",
+                "after_value",
+                "reason"
+            ]
+        );
+    }
 }
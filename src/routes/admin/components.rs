@@ -4,25 +4,46 @@ use crate::auth::UserSession;
 
 use super::{get_admin_stats, role_name};
 
+mod audit;
+mod categories;
+mod duplicates;
 mod export;
 mod flags;
 mod moderation;
 mod overview;
+mod pending;
+mod stages;
+mod tags;
 mod users;
+mod word_filter;
 
+use audit::AuditTab;
+use categories::CategoriesTab;
+use duplicates::DuplicatesTab;
 use export::ExportTab;
 use flags::FlagsTab;
 use moderation::ModerationTab;
 use overview::OverviewTab;
+use pending::PendingTab;
+use stages::StagesTab;
+use tags::TagsTab;
 use users::UsersTab;
+use word_filter::WordFilterTab;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum ResolvedTab {
     Overview,
+    Pending,
     Flags,
+    Duplicates,
     Moderation,
     Export,
     Users,
+    Audit,
+    WordFilter,
+    Tags,
+    Categories,
+    Stages,
     Unknown,
 }
 
@@ -33,10 +54,17 @@ fn show_admin_management_tabs(user: &UserSession) -> bool {
 fn resolve_active_tab(active_tab: &str, is_admin: bool) -> ResolvedTab {
     match active_tab {
         "overview" => ResolvedTab::Overview,
+        "pending" => ResolvedTab::Pending,
         "flags" => ResolvedTab::Flags,
+        "duplicates" => ResolvedTab::Duplicates,
         "moderation" => ResolvedTab::Moderation,
         "export" if is_admin => ResolvedTab::Export,
         "users" if is_admin => ResolvedTab::Users,
+        "audit" if is_admin => ResolvedTab::Audit,
+        "words" if is_admin => ResolvedTab::WordFilter,
+        "tags" if is_admin => ResolvedTab::Tags,
+        "categories" if is_admin => ResolvedTab::Categories,
+        "stages" if is_admin => ResolvedTab::Stages,
         _ => ResolvedTab::Unknown,
     }
 }
@@ -63,11 +91,21 @@ pub(super) fn AdminDashboard(user: UserSession) -> impl IntoView {
                     class:active=move || active_tab.get() == "overview"
                     on:click=move |_| active_tab.set("overview")
                 >"Overview"</button>
+                <button
+                    class="btn btn-secondary admin-tab-btn"
+                    class:active=move || active_tab.get() == "pending"
+                    on:click=move |_| active_tab.set("pending")
+                >"Pending"</button>
                 <button
                     class="btn btn-secondary admin-tab-btn"
                     class:active=move || active_tab.get() == "flags"
                     on:click=move |_| active_tab.set("flags")
                 >"Flagged Content"</button>
+                <button
+                    class="btn btn-secondary admin-tab-btn"
+                    class:active=move || active_tab.get() == "duplicates"
+                    on:click=move |_| active_tab.set("duplicates")
+                >"Duplicates"</button>
                 <button
                     class="btn btn-secondary admin-tab-btn"
                     class:active=move || active_tab.get() == "moderation"
@@ -86,6 +124,31 @@ pub(super) fn AdminDashboard(user: UserSession) -> impl IntoView {
                                 class:active=move || active_tab.get() == "users"
                                 on:click=move |_| active_tab.set("users")
                             >"User Management"</button>
+                            <button
+                                class="btn btn-secondary admin-tab-btn"
+                                class:active=move || active_tab.get() == "audit"
+                                on:click=move |_| active_tab.set("audit")
+                            >"Audit"</button>
+                            <button
+                                class="btn btn-secondary admin-tab-btn"
+                                class:active=move || active_tab.get() == "words"
+                                on:click=move |_| active_tab.set("words")
+                            >"Word Filter"</button>
+                            <button
+                                class="btn btn-secondary admin-tab-btn"
+                                class:active=move || active_tab.get() == "tags"
+                                on:click=move |_| active_tab.set("tags")
+                            >"Tags"</button>
+                            <button
+                                class="btn btn-secondary admin-tab-btn"
+                                class:active=move || active_tab.get() == "categories"
+                                on:click=move |_| active_tab.set("categories")
+                            >"Categories"</button>
+                            <button
+                                class="btn btn-secondary admin-tab-btn"
+                                class:active=move || active_tab.get() == "stages"
+                                on:click=move |_| active_tab.set("stages")
+                            >"Stages"</button>
                         }
                             .into_any()
                     } else {
@@ -97,10 +160,17 @@ pub(super) fn AdminDashboard(user: UserSession) -> impl IntoView {
             <div class="admin-content">
                 {move || match resolve_active_tab(active_tab.get(), user_for_content.is_admin()) {
                     ResolvedTab::Overview => view! { <OverviewTab stats=stats /> }.into_any(),
+                    ResolvedTab::Pending => view! { <PendingTab /> }.into_any(),
                     ResolvedTab::Flags => view! { <FlagsTab /> }.into_any(),
+                    ResolvedTab::Duplicates => view! { <DuplicatesTab /> }.into_any(),
                     ResolvedTab::Moderation => view! { <ModerationTab /> }.into_any(),
                     ResolvedTab::Export => view! { <ExportTab /> }.into_any(),
                     ResolvedTab::Users => view! { <UsersTab /> }.into_any(),
+                    ResolvedTab::Audit => view! { <AuditTab /> }.into_any(),
+                    ResolvedTab::WordFilter => view! { <WordFilterTab /> }.into_any(),
+                    ResolvedTab::Tags => view! { <TagsTab /> }.into_any(),
+                    ResolvedTab::Categories => view! { <CategoriesTab /> }.into_any(),
+                    ResolvedTab::Stages => view! { <StagesTab /> }.into_any(),
                     ResolvedTab::Unknown => view! { <p>"Unknown tab"</p> }.into_any(),
                 }}
             </div>
@@ -135,6 +205,26 @@ mod tests {
             resolve_active_tab("users", admin.is_admin()),
             ResolvedTab::Users
         );
+        assert_eq!(
+            resolve_active_tab("audit", admin.is_admin()),
+            ResolvedTab::Audit
+        );
+        assert_eq!(
+            resolve_active_tab("words", admin.is_admin()),
+            ResolvedTab::WordFilter
+        );
+        assert_eq!(
+            resolve_active_tab("tags", admin.is_admin()),
+            ResolvedTab::Tags
+        );
+        assert_eq!(
+            resolve_active_tab("categories", admin.is_admin()),
+            ResolvedTab::Categories
+        );
+        assert_eq!(
+            resolve_active_tab("stages", admin.is_admin()),
+            ResolvedTab::Stages
+        );
     }
 
     #[test]
@@ -152,6 +242,26 @@ mod tests {
             resolve_active_tab("users", regular_user.is_admin()),
             ResolvedTab::Unknown
         );
+        assert_eq!(
+            resolve_active_tab("audit", moderator.is_admin()),
+            ResolvedTab::Unknown
+        );
+        assert_eq!(
+            resolve_active_tab("words", moderator.is_admin()),
+            ResolvedTab::Unknown
+        );
+        assert_eq!(
+            resolve_active_tab("tags", moderator.is_admin()),
+            ResolvedTab::Unknown
+        );
+        assert_eq!(
+            resolve_active_tab("categories", moderator.is_admin()),
+            ResolvedTab::Unknown
+        );
+        assert_eq!(
+            resolve_active_tab("stages", moderator.is_admin()),
+            ResolvedTab::Unknown
+        );
     }
 
     #[test]
@@ -162,10 +272,18 @@ mod tests {
             resolve_active_tab("overview", non_admin.is_admin()),
             ResolvedTab::Overview
         );
+        assert_eq!(
+            resolve_active_tab("pending", non_admin.is_admin()),
+            ResolvedTab::Pending
+        );
         assert_eq!(
             resolve_active_tab("flags", non_admin.is_admin()),
             ResolvedTab::Flags
         );
+        assert_eq!(
+            resolve_active_tab("duplicates", non_admin.is_admin()),
+            ResolvedTab::Duplicates
+        );
         assert_eq!(
             resolve_active_tab("moderation", non_admin.is_admin()),
             ResolvedTab::Moderation
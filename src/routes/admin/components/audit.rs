@@ -0,0 +1,133 @@
+use leptos::prelude::*;
+
+use crate::models::{ModerationAction, ModerationLogEntry, ModerationLogFilter};
+use crate::routes::view_helpers::format_relative_time;
+
+use super::super::get_moderation_log;
+
+const TARGET_TYPES: [(&str, &str); 7] = [
+    ("idea", "Ideas"),
+    ("comment", "Comments"),
+    ("user", "Users"),
+    ("word_filter", "Word filter"),
+    ("tag", "Tags"),
+    ("category", "Categories"),
+    ("stage", "Stages"),
+];
+
+fn action_label(action: &str) -> String {
+    ModerationAction::from_name(action)
+        .map(|action| action.label().to_string())
+        .unwrap_or_else(|| action.to_string())
+}
+
+fn non_empty(value: String) -> Option<String> {
+    (!value.trim().is_empty()).then_some(value)
+}
+
+#[component]
+pub(super) fn AuditTab() -> impl IntoView {
+    let action = RwSignal::new(String::new());
+    let target_type = RwSignal::new(String::new());
+    let actor = RwSignal::new(String::new());
+    let filter = Memo::new(move |_| ModerationLogFilter {
+        action: non_empty(action.get()),
+        target_type: non_empty(target_type.get()),
+        actor: non_empty(actor.get()),
+    });
+    let entries = Resource::new(move || filter.get(), get_moderation_log);
+
+    view! {
+        <div class="audit-tab">
+            <h2>"Audit Log"</h2>
+            <div class="audit-filters" role="search">
+                <label class="audit-filter">
+                    <span>"Action"</span>
+                    <select on:change=move |ev| action.set(event_target_value(&ev)) prop:value=move || action.get()>
+                        <option value="">"All actions"</option>
+                        {ModerationAction::ALL
+                            .into_iter()
+                            .map(|action| view! { <option value=action.as_str()>{action.label()}</option> })
+                            .collect_view()}
+                    </select>
+                </label>
+                <label class="audit-filter">
+                    <span>"Target"</span>
+                    <select on:change=move |ev| target_type.set(event_target_value(&ev)) prop:value=move || target_type.get()>
+                        <option value="">"All targets"</option>
+                        {TARGET_TYPES
+                            .into_iter()
+                            .map(|(value, label)| view! { <option value=value>{label}</option> })
+                            .collect_view()}
+                    </select>
+                </label>
+                <label class="audit-filter">
+                    <span>"Moderator"</span>
+                    <input
+                        type="search"
+                        placeholder="Name or email"
+                        on:change=move |ev| actor.set(event_target_value(&ev))
+                        prop:value=move || actor.get()
+                    />
+                </label>
+            </div>
+            <Suspense fallback=|| view! { <p>"Loading audit log…"</p> }>
+                {move || entries.get().map(|result| match result {
+                    Ok(entries) if entries.is_empty() => {
+                        view! { <p class="empty-state">"No moderation actions recorded"</p> }.into_any()
+                    }
+                    Ok(entries) => {
+                        view! {
+                            <table class="audit-table">
+                                <thead>
+                                    <tr>
+                                        <th>"When"</th>
+                                        <th>"Moderator"</th>
+                                        <th>"Action"</th>
+                                        <th>"Target"</th>
+                                        <th>"Before"</th>
+                                        <th>"After"</th>
+                                        <th>"Reason"</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    <For
+                                        each=move || entries.clone()
+                                        key=|entry| entry.id
+                                        children=move |entry: ModerationLogEntry| {
+                                            let exact = entry.created_at.format("%Y-%m-%d %H:%M UTC").to_string();
+                                            view! {
+                                                <tr>
+                                                    <td title=exact>{format_relative_time(&entry.created_at)}</td>
+                                                    <td>{entry.actor_name.unwrap_or_else(|| "Deleted user".to_string())}</td>
+                                                    <td>{action_label(&entry.action)}</td>
+                                                    <td>{format!("{} #{}", entry.target_type, entry.target_id)}</td>
+                                                    <td class="audit-value">{entry.before_value.unwrap_or_default()}</td>
+                                                    <td class="audit-value">{entry.after_value.unwrap_or_default()}</td>
+                                                    <td class="audit-value">{entry.reason.unwrap_or_default()}</td>
+                                                </tr>
+                                            }
+                                        }
+                                    />
+                                </tbody>
+                            </table>
+                        }
+                            .into_any()
+                    }
+                    Err(_) => view! { <p class="error">"Failed to load audit log"</p> }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::action_label;
+
+    #[test]
+    fn action_label_uses_known_labels_and_falls_back_to_raw_name() {
+        assert_eq!(action_label("delete_idea"), "Delete idea");
+        assert_eq!(action_label("legacy_action"), "legacy_action");
+    }
+}
@@ -0,0 +1,279 @@
+use leptos::prelude::*;
+
+use crate::models::{
+    CategoryModerator, CategoryWithCount, IdeaSearch, User, MAX_CATEGORY_NAME_CHARS,
+};
+use crate::routes::async_helpers::spawn_server_action_with_error;
+use crate::routes::paths;
+
+use super::super::{
+    assign_category_moderator, create_category, get_all_users_admin, get_categories_admin,
+    get_category_moderators, remove_category_moderator, set_category_archived, update_category,
+};
+
+#[component]
+pub(super) fn CategoriesTab() -> impl IntoView {
+    let categories = Resource::new(|| (), |_| async { get_categories_admin().await });
+    let moderators = Resource::new(|| (), |_| async { get_category_moderators().await });
+    let users = Resource::new(|| (), |_| async { get_all_users_admin().await });
+    let action_error = RwSignal::new(Option::<String>::None);
+
+    // The category being edited, or `None` while the form creates one
+    let editing_id = RwSignal::new(Option::<i32>::None);
+    let name = RwSignal::new(String::new());
+    let description = RwSignal::new(String::new());
+    let reset_form = move || {
+        editing_id.set(None);
+        name.set(String::new());
+        description.set(String::new());
+    };
+
+    let handle_save = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        action_error.set(None);
+        let on_saved = move |_| {
+            reset_form();
+            categories.refetch();
+        };
+        match editing_id.get() {
+            Some(category_id) => spawn_server_action_with_error(
+                update_category(category_id, name.get(), description.get()),
+                on_saved,
+                action_error,
+            ),
+            None => spawn_server_action_with_error(
+                create_category(name.get(), description.get()),
+                on_saved,
+                action_error,
+            ),
+        }
+    };
+
+    let handle_archive = move |category_id: i32, archived: bool| {
+        action_error.set(None);
+        spawn_server_action_with_error(
+            set_category_archived(category_id, archived),
+            move |_| categories.refetch(),
+            action_error,
+        );
+    };
+
+    let handle_assign = move |category_id: i32, user_id: i32| {
+        action_error.set(None);
+        spawn_server_action_with_error(
+            assign_category_moderator(category_id, user_id),
+            move |_| moderators.refetch(),
+            action_error,
+        );
+    };
+
+    let handle_remove = move |category_id: i32, user_id: i32| {
+        action_error.set(None);
+        spawn_server_action_with_error(
+            remove_category_moderator(category_id, user_id),
+            move |_| moderators.refetch(),
+            action_error,
+        );
+    };
+
+    view! {
+        <div class="categories-tab">
+            <h2>"Categories"</h2>
+            <p>
+                "Every idea is filed under one category. Archived categories keep their ideas "
+                "but take no new ones. Moderators assigned to categories can only change stages "
+                "and moderate ideas in those categories; moderators with none cover every category. "
+                "A moderator's last category cannot be removed, so demote them instead."
+            </p>
+
+            <form class="audit-filters" on:submit=handle_save>
+                <label class="audit-filter">
+                    <span>{move || if editing_id.get().is_some() { "Rename category" } else { "New category" }}</span>
+                    <input
+                        type="text"
+                        required
+                        maxlength=MAX_CATEGORY_NAME_CHARS
+                        on:input=move |ev| name.set(event_target_value(&ev))
+                        prop:value=move || name.get()
+                    />
+                </label>
+                <label class="audit-filter">
+                    <span>"Description"</span>
+                    <input
+                        type="text"
+                        maxlength=300
+                        on:input=move |ev| description.set(event_target_value(&ev))
+                        prop:value=move || description.get()
+                    />
+                </label>
+                <button type="submit" class="btn btn-primary">
+                    {move || if editing_id.get().is_some() { "Save changes" } else { "Add category" }}
+                </button>
+                <Show when=move || editing_id.get().is_some()>
+                    <button type="button" class="btn btn-secondary" on:click=move |_| reset_form()>
+                        "Cancel"
+                    </button>
+                </Show>
+            </form>
+            {move || action_error.get().map(|error| view! { <p class="error" role="alert">{error}</p> })}
+
+            <Suspense fallback=|| view! { <p>"Loading categories…"</p> }>
+                {move || categories.get().map(|result| match result {
+                    Ok(category_list) => {
+                        let assigned = moderators.get().and_then(Result::ok).unwrap_or_default();
+                        let staff: Vec<User> = users
+                            .get()
+                            .and_then(Result::ok)
+                            .unwrap_or_default()
+                            .into_iter()
+                            .filter(|user| user.role >= 1)
+                            .collect();
+                        view! {
+                            <table class="audit-table">
+                                <thead>
+                                    <tr>
+                                        <th>"Category"</th>
+                                        <th>"Ideas"</th>
+                                        <th>"Status"</th>
+                                        <th>"Moderators"</th>
+                                        <th><span class="sr-only">"Edit or archive"</span></th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {category_list
+                                        .into_iter()
+                                        .map(|found| {
+                                            let category_moderators: Vec<CategoryModerator> = assigned
+                                                .iter()
+                                                .filter(|moderator| moderator.category_id == found.category.id)
+                                                .cloned()
+                                                .collect();
+                                            let unassigned: Vec<User> = staff
+                                                .iter()
+                                                .filter(|user| {
+                                                    !category_moderators.iter().any(|moderator| moderator.user_id == user.id)
+                                                })
+                                                .cloned()
+                                                .collect();
+                                            category_row(
+                                                found,
+                                                category_moderators,
+                                                unassigned,
+                                                CategoryRowActions {
+                                                    edit: Callback::new(move |(category_id, current_name, current_description)| {
+                                                        editing_id.set(Some(category_id));
+                                                        name.set(current_name);
+                                                        description.set(current_description);
+                                                    }),
+                                                    archive: Callback::new(move |(category_id, archived)| handle_archive(category_id, archived)),
+                                                    assign: Callback::new(move |(category_id, user_id)| handle_assign(category_id, user_id)),
+                                                    remove: Callback::new(move |(category_id, user_id)| handle_remove(category_id, user_id)),
+                                                },
+                                            )
+                                        })
+                                        .collect_view()}
+                                </tbody>
+                            </table>
+                        }
+                            .into_any()
+                    }
+                    Err(_) => view! { <p class="error">"Failed to load categories"</p> }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+/// What the buttons in a category's row do
+#[derive(Clone, Copy)]
+struct CategoryRowActions {
+    /// Load the category (id, name, description) into the form
+    edit: Callback<(i32, String, String)>,
+    archive: Callback<(i32, bool)>,
+    assign: Callback<(i32, i32)>,
+    remove: Callback<(i32, i32)>,
+}
+
+fn category_row(
+    found: CategoryWithCount,
+    moderators: Vec<CategoryModerator>,
+    unassigned: Vec<User>,
+    actions: CategoryRowActions,
+) -> impl IntoView {
+    let category = found.category;
+    let category_id = category.id;
+    let is_archived = category.is_archived;
+    let board_link = format!("{}?{}={}", paths::HOME, IdeaSearch::CATEGORY, category.slug);
+    let current_name = category.name.clone();
+    let current_description = category.description.clone();
+
+    view! {
+        <tr>
+            <td>
+                <a href=board_link>{category.name}</a>
+                <div class="category-description">{category.description}</div>
+            </td>
+            <td>{found.idea_count}</td>
+            <td>{if is_archived { "Archived" } else { "Active" }}</td>
+            <td>
+                <ul class="tag-chips" aria-label="Assigned moderators">
+                    {moderators
+                        .into_iter()
+                        .map(|moderator| {
+                            let user_id = moderator.user_id;
+                            let label = format!("Remove {} from this category", moderator.user_name);
+                            view! {
+                                <li class="tag-chip">
+                                    {moderator.user_name}
+                                    <button
+                                        type="button"
+                                        class="tag-chip-remove"
+                                        aria-label=label
+                                        on:click=move |_| actions.remove.run((category_id, user_id))
+                                    >
+                                        "×"
+                                    </button>
+                                </li>
+                            }
+                        })
+                        .collect_view()}
+                </ul>
+                {(!unassigned.is_empty()).then(|| view! {
+                    <select
+                        aria-label="Assign a moderator"
+                        prop:value=""
+                        on:change=move |ev| {
+                            if let Ok(user_id) = event_target_value(&ev).parse::<i32>() {
+                                actions.assign.run((category_id, user_id));
+                            }
+                        }
+                    >
+                        <option value="">"Assign a moderator…"</option>
+                        {unassigned
+                            .into_iter()
+                            .map(|user| view! { <option value=user.id.to_string()>{user.name}</option> })
+                            .collect_view()}
+                    </select>
+                })}
+            </td>
+            <td>
+                <button
+                    type="button"
+                    class="btn btn-secondary"
+                    on:click=move |_| {
+                        actions.edit.run((category_id, current_name.clone(), current_description.clone()))
+                    }
+                >
+                    "Edit"
+                </button>
+                <button
+                    type="button"
+                    class="btn btn-secondary"
+                    on:click=move |_| actions.archive.run((category_id, !is_archived))
+                >
+                    {if is_archived { "Restore" } else { "Archive" }}
+                </button>
+            </td>
+        </tr>
+    }
+}
@@ -0,0 +1,146 @@
+use leptos::prelude::*;
+
+use crate::models::{DuplicatePair, SimilarIdea};
+use crate::routes::async_helpers::spawn_server_action_with_error;
+use crate::routes::view_helpers::confirm_action;
+
+use super::super::{get_possible_duplicates, merge_ideas_action};
+
+fn similarity_percent(similarity: f64) -> String {
+    format!("{:.0}% similar", similarity * 100.0)
+}
+
+#[component]
+fn DuplicateIdea(idea: SimilarIdea) -> impl IntoView {
+    view! {
+        <div class="idea-content">
+            <h3>
+                <a href=format!("/ideas/{}", idea.id)>{idea.title}</a>
+            </h3>
+            <span class="author">
+                "#" {idea.id} " · " {idea.stage} " · " {idea.vote_count} " votes"
+            </span>
+        </div>
+    }
+}
+
+#[component]
+pub(super) fn DuplicatesTab() -> impl IntoView {
+    let pairs = Resource::new(|| (), |_| async { get_possible_duplicates().await });
+    let merge_error = RwSignal::new(Option::<String>::None);
+
+    let handle_merge = move |target_id: i32, duplicate_id: i32| {
+        let question = format!(
+            "Merge idea #{} into idea #{}? Its votes and comments move across and it becomes a redirect.",
+            duplicate_id, target_id
+        );
+        if !confirm_action(&question) {
+            return;
+        }
+        merge_error.set(None);
+        spawn_server_action_with_error(
+            merge_ideas_action(target_id, duplicate_id),
+            move |_| pairs.refetch(),
+            merge_error,
+        );
+    };
+
+    // Ideas typed in by hand, for duplicates the similarity search misses
+    let manual_duplicate = RwSignal::new(String::new());
+    let manual_target = RwSignal::new(String::new());
+    let handle_manual_merge = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        match (
+            manual_target.get().trim().parse::<i32>(),
+            manual_duplicate.get().trim().parse::<i32>(),
+        ) {
+            (Ok(target_id), Ok(duplicate_id)) => handle_merge(target_id, duplicate_id),
+            _ => merge_error.set(Some("Enter the numbers of both ideas".to_string())),
+        }
+    };
+
+    view! {
+        <div class="moderation-tab">
+            <h2>"Possible Duplicates"</h2>
+            <form class="audit-filters" on:submit=handle_manual_merge>
+                <label class="audit-filter">
+                    <span>"Merge idea #"</span>
+                    <input
+                        type="number"
+                        min="1"
+                        required
+                        on:input=move |ev| manual_duplicate.set(event_target_value(&ev))
+                        prop:value=move || manual_duplicate.get()
+                    />
+                </label>
+                <label class="audit-filter">
+                    <span>"into idea #"</span>
+                    <input
+                        type="number"
+                        min="1"
+                        required
+                        on:input=move |ev| manual_target.set(event_target_value(&ev))
+                        prop:value=move || manual_target.get()
+                    />
+                </label>
+                <button type="submit" class="btn btn-primary">"Merge"</button>
+            </form>
+            {move || merge_error.get().map(|error| view! { <p class="error" role="alert">{error}</p> })}
+            <Suspense fallback=|| view! { <p>"Looking for duplicates…"</p> }>
+                {move || pairs.get().map(|result| match result {
+                    Ok(pairs_list) if pairs_list.is_empty() => {
+                        view! { <p class="empty-state">"No likely duplicates found"</p> }.into_any()
+                    }
+                    Ok(pairs_list) => {
+                        view! {
+                            <div class="off-topic-list">
+                                <For
+                                    each=move || pairs_list.clone()
+                                    key=|pair| (pair.original.id, pair.duplicate.id)
+                                    children=move |pair: DuplicatePair| {
+                                        let original_id = pair.original.id;
+                                        let duplicate_id = pair.duplicate.id;
+                                        view! {
+                                            <div class="off-topic-item callout callout-secondary duplicate-pair">
+                                                <DuplicateIdea idea=pair.original.clone() />
+                                                <span class="duplicate-similarity">
+                                                    {similarity_percent(pair.duplicate.similarity)}
+                                                </span>
+                                                <DuplicateIdea idea=pair.duplicate />
+                                                <div class="moderation-actions">
+                                                    <button
+                                                        type="button"
+                                                        class="btn btn-primary"
+                                                        on:click=move |_| handle_merge(original_id, duplicate_id)
+                                                    >{format!("Merge #{} into #{}", duplicate_id, original_id)}</button>
+                                                    <button
+                                                        type="button"
+                                                        class="btn btn-secondary"
+                                                        on:click=move |_| handle_merge(duplicate_id, original_id)
+                                                    >{format!("Merge #{} into #{}", original_id, duplicate_id)}</button>
+                                                </div>
+                                            </div>
+                                        }
+                                    }
+                                />
+                            </div>
+                        }
+                            .into_any()
+                    }
+                    Err(_) => view! { <p class="error">"Failed to load possible duplicates"</p> }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::similarity_percent;
+
+    #[test]
+    fn similarity_is_shown_as_a_whole_percentage() {
+        assert_eq!(similarity_percent(0.456), "46% similar");
+        assert_eq!(similarity_percent(1.0), "100% similar");
+    }
+}
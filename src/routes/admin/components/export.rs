@@ -149,13 +149,23 @@ pub(super) fn ExportTab() -> impl IntoView {
         );
     };
 
+    let handle_export_moderation_log = move |_| {
+        run_export(
+            "Audit log",
+            "/admin/export/moderation_log.csv",
+            "moderation_log_export.csv",
+            export_status,
+            is_exporting,
+        );
+    };
+
     view! {
         <div class="export-tab">
             <h2>"Data Export"</h2>
             <section class="admin-export-panel" aria-label="Data export controls">
                 <header class="admin-export-heading">
                     <h3>"Export CSV Files"</h3>
-                    <p>"Download complete snapshots of ideas and comments."</p>
+                    <p>"Download complete snapshots of ideas, comments and the moderation audit log."</p>
                 </header>
                 <div class="admin-export-actions">
                     <button
@@ -174,6 +184,14 @@ pub(super) fn ExportTab() -> impl IntoView {
                     >
                         "Export Comments CSV"
                     </button>
+                    <button
+                        type="button"
+                        class="btn btn-primary admin-export-btn"
+                        disabled=move || is_exporting.get()
+                        on:click=handle_export_moderation_log
+                    >
+                        "Export Audit Log CSV"
+                    </button>
                 </div>
                 <p
                     class="admin-export-status"
@@ -2,12 +2,31 @@ use leptos::prelude::*;
 
 use crate::routes::async_helpers::spawn_server_action_refetch_resource;
 use crate::routes::view_helpers::confirm_action;
+use crate::routes::{delete_comment_mod, ModerationReasonPrompt};
 
 use super::super::{
     clear_flags_action, delete_idea_action, get_flagged_content, mark_idea_off_topic_action,
     FlaggedItemDetail,
 };
 
+/// Moderator action waiting for a reason
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PendingAction {
+    MarkOffTopic,
+    DeleteIdea,
+    DeleteComment,
+}
+
+impl PendingAction {
+    fn confirm_label(self) -> &'static str {
+        match self {
+            Self::MarkOffTopic => "Mark Off-Topic",
+            Self::DeleteIdea => "Delete Idea",
+            Self::DeleteComment => "Delete Comment",
+        }
+    }
+}
+
 #[component]
 pub(super) fn FlagsTab() -> impl IntoView {
     let flagged_items = Resource::new(|| (), |_| async { get_flagged_content().await });
@@ -19,18 +38,29 @@ pub(super) fn FlagsTab() -> impl IntoView {
         );
     };
 
-    let handle_mark_off_topic = move |idea_id: i32| {
-        spawn_server_action_refetch_resource(
-            mark_idea_off_topic_action(idea_id, true),
-            flagged_items,
-        );
-    };
+    // Item and action whose reason is being asked for
+    let prompt = RwSignal::new(Option::<(String, i32, PendingAction)>::None);
 
-    let handle_delete = move |target_type: String, target_id: i32| {
-        if target_type != "idea" {
-            return;
+    let handle_confirm = move |target_id: i32, action: PendingAction, reason: String| {
+        prompt.set(None);
+        match action {
+            PendingAction::MarkOffTopic => spawn_server_action_refetch_resource(
+                mark_idea_off_topic_action(target_id, true, Some(reason)),
+                flagged_items,
+            ),
+            PendingAction::DeleteIdea => {
+                if confirm_action("Delete this idea? This cannot be undone.") {
+                    spawn_server_action_refetch_resource(
+                        delete_idea_action(target_id, reason),
+                        flagged_items,
+                    );
+                }
+            }
+            PendingAction::DeleteComment => spawn_server_action_refetch_resource(
+                delete_comment_mod(target_id, reason),
+                flagged_items,
+            ),
         }
-        spawn_server_action_refetch_resource(delete_idea_action(target_id), flagged_items);
     };
 
     view! {
@@ -46,54 +76,103 @@ pub(super) fn FlagsTab() -> impl IntoView {
                             <div class="flagged-items-list">
                                 <For
                                     each=move || flagged.clone()
-                                    key=|item| (item.target_type.clone(), item.target_id)
+                                    key=|item| (item.target_type.clone(), item.target_id, item.flag_count, item.auto_hidden)
                                     children=move |item: FlaggedItemDetail| {
                                         let target_type = item.target_type.clone();
-                                        let target_type_for_check = target_type.clone();
                                         let target_id = item.target_id;
-                                        let item_type_display = item.target_type.clone();
-                                        let content_preview = item.content_preview.clone();
-                                        let flag_count = item.flag_count;
+                                        let is_idea = item.target_type == "idea";
+                                        let auto_hidden = item.auto_hidden;
+                                        let prompt_key = target_type.clone();
+                                        let open_prompt = {
+                                            let target_type = target_type.clone();
+                                            move |action: PendingAction| {
+                                                prompt.set(Some((target_type.clone(), target_id, action)))
+                                            }
+                                        };
+                                        let open_prompt_delete = open_prompt.clone();
+                                        let context = if is_idea {
+                                            format!("Idea by {}", item.author_name)
+                                        } else {
+                                            format!("Comment by {} on", item.author_name)
+                                        };
                                         view! {
                                             <div class="flagged-item callout callout-secondary">
                                                 <div class="flagged-info">
-                                                    <span class="flag-badge">{flag_count}" flags"</span>
-                                                    <span class="content-type">{item_type_display}</span>
-                                                    <p class="content-preview">{content_preview}</p>
+                                                    <span class="flag-badge">{item.flag_count}" flags"</span>
+                                                    {item.auto_hidden.then(|| view! {
+                                                        <span class="auto-hidden-badge">"Auto-hidden"</span>
+                                                    })}
+                                                    <span class="content-type">{item.target_type.clone()}</span>
+                                                    <p class="flagged-context">
+                                                        {context}
+                                                        {(!is_idea).then(|| view! {
+                                                            " "
+                                                            <a href=format!("/ideas/{}", item.idea_id)>{item.idea_title.clone()}</a>
+                                                        })}
+                                                    </p>
+                                                    <p class="content-preview">{item.content_preview.clone()}</p>
+                                                    <ul class="flag-reasons">
+                                                        {item
+                                                            .reasons
+                                                            .iter()
+                                                            .map(|(reason, count)| view! {
+                                                                <li class="flag-reason">{format!("{} × {}", reason.label(), count)}</li>
+                                                            })
+                                                            .collect_view()}
+                                                    </ul>
+                                                    {(!item.details.is_empty()).then(|| view! {
+                                                        <ul class="flag-details">
+                                                            {item
+                                                                .details
+                                                                .iter()
+                                                                .map(|detail| view! { <li>{detail.clone()}</li> })
+                                                                .collect_view()}
+                                                        </ul>
+                                                    })}
                                                 </div>
                                                 <div class="flagged-actions">
                                                     <button
                                                         type="button"
                                                         class="btn btn-primary"
                                                         on:click=move |_| handle_clear_flags(target_type.clone(), target_id)
-                                                    >"Unflag"</button>
-                                                    {move || {
-                                                        let target_type_for_delete = target_type_for_check.clone();
-                                                        if target_type_for_check == "idea" {
-                                                            view! {
-                                                                <>
-                                                                    <button
-                                                                        type="button"
-                                                                        class="btn btn-warning"
-                                                                        on:click=move |_| handle_mark_off_topic(target_id)
-                                                                    >"Mark Off-Topic"</button>
-                                                                    <button
-                                                                        type="button"
-                                                                        class="btn btn-danger"
-                                                                        on:click=move |_| {
-                                                                            if confirm_action("Delete this idea? This cannot be undone.") {
-                                                                                handle_delete(target_type_for_delete.clone(), target_id);
-                                                                            }
-                                                                        }
-                                                                    >"Delete"</button>
-                                                                </>
-                                                            }
-                                                                .into_any()
-                                                        } else {
-                                                            ().into_any()
+                                                    >{if auto_hidden { "Restore" } else { "Unflag" }}</button>
+                                                    {if is_idea {
+                                                        view! {
+                                                            <button
+                                                                type="button"
+                                                                class="btn btn-warning"
+                                                                on:click=move |_| open_prompt(PendingAction::MarkOffTopic)
+                                                            >"Mark Off-Topic"</button>
+                                                            <button
+                                                                type="button"
+                                                                class="btn btn-danger"
+                                                                on:click=move |_| open_prompt_delete(PendingAction::DeleteIdea)
+                                                            >"Delete"</button>
+                                                        }
+                                                            .into_any()
+                                                    } else {
+                                                        view! {
+                                                            <button
+                                                                type="button"
+                                                                class="btn btn-danger"
+                                                                on:click=move |_| open_prompt_delete(PendingAction::DeleteComment)
+                                                            >"Delete Comment"</button>
                                                         }
+                                                            .into_any()
                                                     }}
                                                 </div>
+                                                {move || {
+                                                    prompt
+                                                        .get()
+                                                        .filter(|(kind, id, _)| *kind == prompt_key && *id == target_id)
+                                                        .map(|(_, _, action)| view! {
+                                                            <ModerationReasonPrompt
+                                                                confirm_label=action.confirm_label()
+                                                                on_confirm=Callback::new(move |reason| handle_confirm(target_id, action, reason))
+                                                                on_cancel=Callback::new(move |_| prompt.set(None))
+                                                            />
+                                                        })
+                                                }}
                                             </div>
                                         }
                                     }
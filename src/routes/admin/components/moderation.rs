@@ -3,6 +3,7 @@ use leptos::prelude::*;
 use crate::models::IdeaWithAuthor;
 use crate::routes::async_helpers::spawn_server_action_refetch_resource;
 use crate::routes::view_helpers::confirm_action;
+use crate::routes::ModerationReasonPrompt;
 
 use super::super::{delete_idea_action, get_off_topic_ideas, mark_idea_off_topic_action};
 
@@ -12,13 +13,17 @@ pub(super) fn ModerationTab() -> impl IntoView {
 
     let handle_restore = move |idea_id: i32| {
         spawn_server_action_refetch_resource(
-            mark_idea_off_topic_action(idea_id, false),
+            mark_idea_off_topic_action(idea_id, false, None),
             off_topic_ideas,
         );
     };
 
-    let handle_delete = move |idea_id: i32| {
-        spawn_server_action_refetch_resource(delete_idea_action(idea_id), off_topic_ideas);
+    // Idea whose deletion reason is being asked for
+    let delete_prompt = RwSignal::new(Option::<i32>::None);
+
+    let handle_delete = move |idea_id: i32, reason: String| {
+        delete_prompt.set(None);
+        spawn_server_action_refetch_resource(delete_idea_action(idea_id, reason), off_topic_ideas);
     };
 
     view! {
@@ -43,6 +48,9 @@ pub(super) fn ModerationTab() -> impl IntoView {
                                                     <h3>{iwa.idea.title.clone()}</h3>
                                                     <p>{iwa.idea.content.clone()}</p>
                                                     <span class="author">"By: " {iwa.author_name}</span>
+                                                    {iwa.idea.moderation_reason.map(|reason| view! {
+                                                        <p class="moderation-reason">"Reason: " {reason}</p>
+                                                    })}
                                                 </div>
                                                 <div class="moderation-actions">
                                                     <button
@@ -53,13 +61,20 @@ pub(super) fn ModerationTab() -> impl IntoView {
                                                     <button
                                                         type="button"
                                                         class="btn btn-danger"
-                                                        on:click=move |_| {
-                                                            if confirm_action("Permanently delete this idea? This cannot be undone.") {
-                                                                handle_delete(idea_id);
-                                                            }
-                                                        }
+                                                        on:click=move |_| delete_prompt.set(Some(idea_id))
                                                     >"Delete Permanently"</button>
                                                 </div>
+                                                <Show when=move || delete_prompt.get() == Some(idea_id)>
+                                                    <ModerationReasonPrompt
+                                                        confirm_label="Delete Permanently"
+                                                        on_confirm=Callback::new(move |reason| {
+                                                            if confirm_action("Permanently delete this idea? This cannot be undone.") {
+                                                                handle_delete(idea_id, reason);
+                                                            }
+                                                        })
+                                                        on_cancel=Callback::new(move |_| delete_prompt.set(None))
+                                                    />
+                                                </Show>
                                             </div>
                                         }
                                     }
@@ -0,0 +1,87 @@
+use leptos::prelude::*;
+
+use crate::models::IdeaWithAuthor;
+use crate::routes::async_helpers::spawn_server_action_refetch_resource;
+use crate::routes::view_helpers::format_relative_time;
+use crate::routes::ModerationReasonPrompt;
+
+use super::super::{approve_idea_action, get_pending_ideas, reject_idea_action};
+
+#[component]
+pub(super) fn PendingTab() -> impl IntoView {
+    let pending_ideas = Resource::new(|| (), |_| async { get_pending_ideas().await });
+
+    let handle_approve = move |idea_id: i32| {
+        spawn_server_action_refetch_resource(approve_idea_action(idea_id), pending_ideas);
+    };
+
+    // Idea whose rejection reason is being asked for
+    let reject_prompt = RwSignal::new(Option::<i32>::None);
+
+    let handle_reject = move |idea_id: i32, reason: String| {
+        reject_prompt.set(None);
+        spawn_server_action_refetch_resource(reject_idea_action(idea_id, reason), pending_ideas);
+    };
+
+    view! {
+        <div class="moderation-tab">
+            <h2>"Ideas Awaiting Approval"</h2>
+            <Suspense fallback=|| view! { <p>"Loading pending ideas…"</p> }>
+                {move || pending_ideas.get().map(|ideas| match ideas {
+                    Ok(ideas_list) if ideas_list.is_empty() => {
+                        view! { <p class="empty-state">"No ideas are waiting for approval"</p> }.into_any()
+                    }
+                    Ok(ideas_list) => {
+                        view! {
+                            <div class="off-topic-list">
+                                <For
+                                    each=move || ideas_list.clone()
+                                    key=|iwa| (iwa.idea.id, iwa.idea.edited_at)
+                                    children=move |iwa: IdeaWithAuthor| {
+                                        let idea_id = iwa.idea.id;
+                                        view! {
+                                            <div class="off-topic-item callout callout-secondary">
+                                                <div class="idea-content">
+                                                    <h3>{iwa.idea.title.clone()}</h3>
+                                                    <p>{iwa.idea.content.clone()}</p>
+                                                    <span class="author">
+                                                        "By: " {iwa.author_name} " · "
+                                                        {format_relative_time(&iwa.idea.created_at)}
+                                                    </span>
+                                                </div>
+                                                <div class="moderation-actions">
+                                                    <button
+                                                        type="button"
+                                                        class="btn btn-primary"
+                                                        on:click=move |_| handle_approve(idea_id)
+                                                    >"Approve"</button>
+                                                    <a class="btn btn-secondary" href=format!("/ideas/{}", idea_id)>
+                                                        "Edit"
+                                                    </a>
+                                                    <button
+                                                        type="button"
+                                                        class="btn btn-danger"
+                                                        on:click=move |_| reject_prompt.set(Some(idea_id))
+                                                    >"Reject"</button>
+                                                </div>
+                                                <Show when=move || reject_prompt.get() == Some(idea_id)>
+                                                    <ModerationReasonPrompt
+                                                        confirm_label="Reject"
+                                                        on_confirm=Callback::new(move |reason| handle_reject(idea_id, reason))
+                                                        on_cancel=Callback::new(move |_| reject_prompt.set(None))
+                                                    />
+                                                </Show>
+                                            </div>
+                                        }
+                                    }
+                                />
+                            </div>
+                        }
+                            .into_any()
+                    }
+                    Err(_) => view! { <p class="error">"Failed to load pending ideas"</p> }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
@@ -0,0 +1,265 @@
+use leptos::prelude::*;
+
+use crate::models::{Stage, StageColor, MAX_STAGE_NAME_CHARS};
+use crate::routes::async_helpers::spawn_server_action_with_error;
+use crate::routes::ideas::list_stages;
+use crate::routes::view_helpers::{confirm_action, stage_badge_color};
+
+use super::super::{create_stage, delete_stage, edit_stage, move_stage, set_stage_transitions};
+
+#[component]
+pub(super) fn StagesTab() -> impl IntoView {
+    let stages = Resource::new(|| (), |_| async { list_stages().await });
+    let action_error = RwSignal::new(Option::<String>::None);
+
+    // The stage being edited, or `None` while the form adds one
+    let editing_id = RwSignal::new(Option::<i32>::None);
+    let name = RwSignal::new(String::new());
+    let color = RwSignal::new(StageColor::Gray.as_str().to_string());
+    let is_terminal = RwSignal::new(false);
+    let reset_form = move || {
+        editing_id.set(None);
+        name.set(String::new());
+        color.set(StageColor::Gray.as_str().to_string());
+        is_terminal.set(false);
+    };
+
+    let handle_save = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        action_error.set(None);
+        let on_saved = move |_| {
+            reset_form();
+            stages.refetch();
+        };
+        match editing_id.get() {
+            Some(stage_id) => spawn_server_action_with_error(
+                edit_stage(stage_id, name.get(), color.get(), is_terminal.get()),
+                on_saved,
+                action_error,
+            ),
+            None => spawn_server_action_with_error(
+                create_stage(name.get(), color.get(), is_terminal.get()),
+                on_saved,
+                action_error,
+            ),
+        }
+    };
+
+    let actions = StageRowActions {
+        edit: Callback::new(move |stage: Stage| {
+            editing_id.set(Some(stage.id));
+            name.set(stage.name);
+            color.set(stage.color);
+            is_terminal.set(stage.is_terminal);
+        }),
+        reorder: Callback::new(move |(stage_id, earlier)| {
+            action_error.set(None);
+            spawn_server_action_with_error(
+                move_stage(stage_id, earlier),
+                move |_| stages.refetch(),
+                action_error,
+            );
+        }),
+        set_transitions: Callback::new(move |(stage_id, to_stage_ids)| {
+            action_error.set(None);
+            spawn_server_action_with_error(
+                set_stage_transitions(stage_id, to_stage_ids),
+                move |_| stages.refetch(),
+                action_error,
+            );
+        }),
+        delete: Callback::new(move |stage_id| {
+            if !confirm_action("Delete this stage? Only stages no idea is in can be deleted.") {
+                return;
+            }
+            action_error.set(None);
+            spawn_server_action_with_error(
+                delete_stage(stage_id),
+                move |_| stages.refetch(),
+                action_error,
+            );
+        }),
+    };
+
+    view! {
+        <div class="stages-tab">
+            <h2>"Stages"</h2>
+            <p>
+                "Ideas start in the first stage that does not close them, and moderators can "
+                "only move an idea to the stages ticked for its current stage. Closing stages "
+                "mark an idea as finished, whether it shipped or was declined."
+            </p>
+
+            <form class="audit-filters" on:submit=handle_save>
+                <label class="audit-filter">
+                    <span>{move || if editing_id.get().is_some() { "Rename stage" } else { "New stage" }}</span>
+                    <input
+                        type="text"
+                        required
+                        maxlength=MAX_STAGE_NAME_CHARS
+                        on:input=move |ev| name.set(event_target_value(&ev))
+                        prop:value=move || name.get()
+                    />
+                </label>
+                <label class="audit-filter">
+                    <span>"Badge color"</span>
+                    <select on:change=move |ev| color.set(event_target_value(&ev)) prop:value=move || color.get()>
+                        {StageColor::ALL
+                            .into_iter()
+                            .map(|option| view! { <option value=option.as_str()>{option.label()}</option> })
+                            .collect_view()}
+                    </select>
+                </label>
+                <label class="audit-filter">
+                    <span>"Closes the idea"</span>
+                    <input
+                        type="checkbox"
+                        on:change=move |ev| is_terminal.set(event_target_checked(&ev))
+                        prop:checked=move || is_terminal.get()
+                    />
+                </label>
+                <button type="submit" class="btn btn-primary">
+                    {move || if editing_id.get().is_some() { "Save changes" } else { "Add stage" }}
+                </button>
+                <Show when=move || editing_id.get().is_some()>
+                    <button type="button" class="btn btn-secondary" on:click=move |_| reset_form()>
+                        "Cancel"
+                    </button>
+                </Show>
+            </form>
+            {move || action_error.get().map(|error| view! { <p class="error" role="alert">{error}</p> })}
+
+            <Suspense fallback=|| view! { <p>"Loading stages…"</p> }>
+                {move || stages.get().map(|result| match result {
+                    Ok(stage_list) => {
+                        let last = stage_list.len().saturating_sub(1);
+                        view! {
+                            <table class="audit-table">
+                                <thead>
+                                    <tr>
+                                        <th>"Stage"</th>
+                                        <th>"Order"</th>
+                                        <th>"Closes"</th>
+                                        <th>"Can move to"</th>
+                                        <th><span class="sr-only">"Edit or delete"</span></th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {stage_list
+                                        .iter()
+                                        .enumerate()
+                                        .map(|(index, stage)| stage_row(stage.clone(), &stage_list, index == 0, index == last, actions))
+                                        .collect_view()}
+                                </tbody>
+                            </table>
+                        }
+                            .into_any()
+                    }
+                    Err(_) => view! { <p class="error">"Failed to load stages"</p> }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
+
+/// What the controls in a stage's row do
+#[derive(Clone, Copy)]
+struct StageRowActions {
+    /// Load the stage into the form
+    edit: Callback<Stage>,
+    /// Move a stage one place; true for earlier
+    reorder: Callback<(i32, bool)>,
+    set_transitions: Callback<(i32, Vec<i32>)>,
+    delete: Callback<i32>,
+}
+
+fn stage_row(
+    stage: Stage,
+    all: &[Stage],
+    is_first: bool,
+    is_last: bool,
+    actions: StageRowActions,
+) -> impl IntoView {
+    let stage_id = stage.id;
+    let allowed = stage.next_stage_ids.clone();
+    let targets: Vec<(i32, String, bool)> = all
+        .iter()
+        .filter(|to| to.id != stage_id)
+        .map(|to| (to.id, to.name.clone(), stage.allows(to)))
+        .collect();
+    let badge_class = format!("stage-badge stage-{}", stage_badge_color(&stage.color));
+    let for_edit = stage.clone();
+
+    view! {
+        <tr>
+            <td><span class=badge_class>{stage.name.clone()}</span></td>
+            <td>
+                <button
+                    type="button"
+                    class="btn btn-secondary"
+                    aria-label=format!("Move {} earlier", stage.name)
+                    disabled=is_first
+                    on:click=move |_| actions.reorder.run((stage_id, true))
+                >
+                    "↑"
+                </button>
+                <button
+                    type="button"
+                    class="btn btn-secondary"
+                    aria-label=format!("Move {} later", stage.name)
+                    disabled=is_last
+                    on:click=move |_| actions.reorder.run((stage_id, false))
+                >
+                    "↓"
+                </button>
+            </td>
+            <td>{if stage.is_terminal { "Yes" } else { "No" }}</td>
+            <td>
+                <div class="stage-transitions">
+                    {targets
+                        .into_iter()
+                        .map(|(to_id, to_name, checked)| {
+                            let allowed = allowed.clone();
+                            view! {
+                                <label class="stage-transition">
+                                    <input
+                                        type="checkbox"
+                                        prop:checked=checked
+                                        on:change=move |ev| {
+                                            let mut next: Vec<i32> = allowed
+                                                .iter()
+                                                .copied()
+                                                .filter(|id| *id != to_id)
+                                                .collect();
+                                            if event_target_checked(&ev) {
+                                                next.push(to_id);
+                                            }
+                                            actions.set_transitions.run((stage_id, next));
+                                        }
+                                    />
+                                    {to_name}
+                                </label>
+                            }
+                        })
+                        .collect_view()}
+                </div>
+            </td>
+            <td>
+                <button
+                    type="button"
+                    class="btn btn-secondary"
+                    on:click=move |_| actions.edit.run(for_edit.clone())
+                >
+                    "Edit"
+                </button>
+                <button
+                    type="button"
+                    class="btn btn-secondary"
+                    on:click=move |_| actions.delete.run(stage_id)
+                >
+                    "Delete"
+                </button>
+            </td>
+        </tr>
+    }
+}
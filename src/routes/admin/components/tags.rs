@@ -0,0 +1,187 @@
+use leptos::prelude::*;
+
+use crate::models::TagWithCount;
+use crate::routes::async_helpers::spawn_server_action_with_error;
+use crate::routes::paths;
+use crate::routes::view_helpers::confirm_action;
+
+use super::super::{get_tags, merge_tags, rename_tag, set_tag_retired};
+
+/// Options for a select listing every tag
+fn tag_options(tags: Vec<TagWithCount>) -> impl IntoView {
+    tags.into_iter()
+        .map(|found| {
+            view! {
+                <option value=found.tag.id.to_string()>
+                    {format!("{} ({})", found.tag.name, found.idea_count)}
+                </option>
+            }
+        })
+        .collect_view()
+}
+
+#[component]
+pub(super) fn TagsTab() -> impl IntoView {
+    let tags = Resource::new(|| (), |_| async { get_tags().await });
+    let loaded = move || tags.get().and_then(Result::ok).unwrap_or_default();
+    let action_error = RwSignal::new(Option::<String>::None);
+
+    let rename_id = RwSignal::new(String::new());
+    let new_name = RwSignal::new(String::new());
+    let handle_rename = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        action_error.set(None);
+        let Ok(tag_id) = rename_id.get().parse::<i32>() else {
+            action_error.set(Some("Choose a tag to rename".to_string()));
+            return;
+        };
+        spawn_server_action_with_error(
+            rename_tag(tag_id, new_name.get()),
+            move |_| {
+                new_name.set(String::new());
+                tags.refetch();
+            },
+            action_error,
+        );
+    };
+
+    let source_id = RwSignal::new(String::new());
+    let target_id = RwSignal::new(String::new());
+    let handle_merge = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        action_error.set(None);
+        let (Ok(source), Ok(target)) = (
+            source_id.get().parse::<i32>(),
+            target_id.get().parse::<i32>(),
+        ) else {
+            action_error.set(Some("Choose both tags to merge".to_string()));
+            return;
+        };
+        if !confirm_action(
+            "Merge these tags? The first tag is deleted and its ideas get the second.",
+        ) {
+            return;
+        }
+        spawn_server_action_with_error(
+            merge_tags(source, target),
+            move |_| {
+                source_id.set(String::new());
+                tags.refetch();
+            },
+            action_error,
+        );
+    };
+
+    let handle_retire = move |tag_id: i32, retired: bool| {
+        action_error.set(None);
+        spawn_server_action_with_error(
+            set_tag_retired(tag_id, retired),
+            move |_| tags.refetch(),
+            action_error,
+        );
+    };
+
+    view! {
+        <div class="tags-tab">
+            <h2>"Tags"</h2>
+            <p>
+                "Retired tags stay on the ideas that have them but cannot be added to more. "
+                "Merging moves every idea from the first tag to the second and deletes the first."
+            </p>
+
+            <form class="audit-filters" on:submit=handle_rename>
+                <label class="audit-filter">
+                    <span>"Rename tag"</span>
+                    <select on:change=move |ev| rename_id.set(event_target_value(&ev)) prop:value=move || rename_id.get()>
+                        <option value="">"Choose a tag"</option>
+                        {move || tag_options(loaded())}
+                    </select>
+                </label>
+                <label class="audit-filter">
+                    <span>"New name"</span>
+                    <input
+                        type="text"
+                        required
+                        maxlength=crate::models::MAX_TAG_CHARS
+                        on:input=move |ev| new_name.set(event_target_value(&ev))
+                        prop:value=move || new_name.get()
+                    />
+                </label>
+                <button type="submit" class="btn btn-primary">"Rename"</button>
+            </form>
+
+            <form class="audit-filters" on:submit=handle_merge>
+                <label class="audit-filter">
+                    <span>"Merge tag"</span>
+                    <select on:change=move |ev| source_id.set(event_target_value(&ev)) prop:value=move || source_id.get()>
+                        <option value="">"Choose a tag"</option>
+                        {move || tag_options(loaded())}
+                    </select>
+                </label>
+                <label class="audit-filter">
+                    <span>"into"</span>
+                    <select on:change=move |ev| target_id.set(event_target_value(&ev)) prop:value=move || target_id.get()>
+                        <option value="">"Choose a tag"</option>
+                        {move || tag_options(loaded())}
+                    </select>
+                </label>
+                <button type="submit" class="btn btn-primary">"Merge"</button>
+            </form>
+            {move || action_error.get().map(|error| view! { <p class="error" role="alert">{error}</p> })}
+
+            <Suspense fallback=|| view! { <p>"Loading tags…"</p> }>
+                {move || tags.get().map(|result| match result {
+                    Ok(tags_list) if tags_list.is_empty() => {
+                        view! { <p class="empty-state">"No ideas have been tagged yet"</p> }.into_any()
+                    }
+                    Ok(tags_list) => {
+                        view! {
+                            <table class="audit-table">
+                                <thead>
+                                    <tr>
+                                        <th>"Tag"</th>
+                                        <th>"Slug"</th>
+                                        <th>"Ideas"</th>
+                                        <th>"Status"</th>
+                                        <th><span class="sr-only">"Retire or restore"</span></th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    <For
+                                        each=move || tags_list.clone()
+                                        key=|found| (found.tag.id, found.tag.name.clone(), found.tag.is_retired, found.idea_count)
+                                        children=move |found: TagWithCount| {
+                                            let tag_id = found.tag.id;
+                                            let is_retired = found.tag.is_retired;
+                                            view! {
+                                                <tr>
+                                                    <td>
+                                                        <a href=format!("{}/{}", paths::TAGS, found.tag.slug)>{found.tag.name}</a>
+                                                    </td>
+                                                    <td><code>{found.tag.slug}</code></td>
+                                                    <td>{found.idea_count}</td>
+                                                    <td>{if is_retired { "Retired" } else { "Active" }}</td>
+                                                    <td>
+                                                        <button
+                                                            type="button"
+                                                            class="btn btn-secondary"
+                                                            on:click=move |_| handle_retire(tag_id, !is_retired)
+                                                        >
+                                                            {if is_retired { "Restore" } else { "Retire" }}
+                                                        </button>
+                                                    </td>
+                                                </tr>
+                                            }
+                                        }
+                                    />
+                                </tbody>
+                            </table>
+                        }
+                            .into_any()
+                    }
+                    Err(_) => view! { <p class="error">"Failed to load tags"</p> }.into_any(),
+                })}
+            </Suspense>
+        </div>
+    }
+}
@@ -0,0 +1,178 @@
+use leptos::prelude::*;
+
+use crate::models::{FilterMatch, FilterSeverity, MatchMode, WordFilter};
+use crate::routes::async_helpers::{
+    spawn_server_action_refetch_resource, spawn_server_action_with_error,
+};
+use crate::routes::view_helpers::{confirm_action, format_relative_time};
+
+use super::super::{add_word_filter, get_word_filters, remove_word_filter, test_word_filter};
+
+fn mode_label(match_mode: &str) -> String {
+    MatchMode::from_name(match_mode)
+        .map(|mode| mode.label().to_string())
+        .unwrap_or_else(|| match_mode.to_string())
+}
+
+fn severity_label(severity: &str) -> String {
+    FilterSeverity::from_name(severity)
+        .map(|severity| severity.label().to_string())
+        .unwrap_or_else(|| severity.to_string())
+}
+
+#[component]
+pub(super) fn WordFilterTab() -> impl IntoView {
+    let filters = Resource::new(|| (), |_| async { get_word_filters().await });
+
+    let term = RwSignal::new(String::new());
+    let match_mode = RwSignal::new(MatchMode::WholeWord.as_str().to_string());
+    let severity = RwSignal::new(FilterSeverity::Block.as_str().to_string());
+    let add_error = RwSignal::new(Option::<String>::None);
+
+    let handle_add = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        add_error.set(None);
+        spawn_server_action_with_error(
+            add_word_filter(term.get(), match_mode.get(), severity.get()),
+            move |_| {
+                term.set(String::new());
+                filters.refetch();
+            },
+            add_error,
+        );
+    };
+
+    let handle_remove = move |filter_id: i32| {
+        if confirm_action("Remove this term from the word filter?") {
+            spawn_server_action_refetch_resource(remove_word_filter(filter_id), filters);
+        }
+    };
+
+    let sample = RwSignal::new(String::new());
+    let test_result = RwSignal::new(Option::<Option<FilterMatch>>::None);
+    let test_error = RwSignal::new(Option::<String>::None);
+
+    let handle_test = move |_| {
+        test_error.set(None);
+        spawn_server_action_with_error(
+            test_word_filter(sample.get()),
+            move |found| test_result.set(Some(found)),
+            test_error,
+        );
+    };
+
+    view! {
+        <div class="word-filter-tab">
+            <h2>"Word Filter"</h2>
+            <p>
+                "Blocked terms reject the post. Held posts stay hidden until a moderator reviews them; "
+                "flagged posts are published and added to the flags queue."
+            </p>
+
+            <form class="audit-filters" on:submit=handle_add>
+                <label class="audit-filter">
+                    <span>"Term"</span>
+                    <input
+                        type="text"
+                        required
+                        maxlength="200"
+                        on:input=move |ev| term.set(event_target_value(&ev))
+                        prop:value=move || term.get()
+                    />
+                </label>
+                <label class="audit-filter">
+                    <span>"Match"</span>
+                    <select on:change=move |ev| match_mode.set(event_target_value(&ev)) prop:value=move || match_mode.get()>
+                        {MatchMode::ALL
+                            .into_iter()
+                            .map(|mode| view! { <option value=mode.as_str()>{mode.label()}</option> })
+                            .collect_view()}
+                    </select>
+                </label>
+                <label class="audit-filter">
+                    <span>"Action"</span>
+                    <select on:change=move |ev| severity.set(event_target_value(&ev)) prop:value=move || severity.get()>
+                        {FilterSeverity::ALL
+                            .into_iter()
+                            .map(|severity| view! { <option value=severity.as_str()>{severity.label()}</option> })
+                            .collect_view()}
+                    </select>
+                </label>
+                <button type="submit" class="btn btn-primary">"Add term"</button>
+            </form>
+            {move || add_error.get().map(|error| view! { <p class="error" role="alert">{error}</p> })}
+
+            <Suspense fallback=|| view! { <p>"Loading word filter…"</p> }>
+                {move || filters.get().map(|result| match result {
+                    Ok(filters_list) if filters_list.is_empty() => {
+                        view! { <p class="empty-state">"The word filter is empty"</p> }.into_any()
+                    }
+                    Ok(filters_list) => {
+                        view! {
+                            <table class="audit-table">
+                                <thead>
+                                    <tr>
+                                        <th>"Term"</th>
+                                        <th>"Match"</th>
+                                        <th>"Action"</th>
+                                        <th>"Added"</th>
+                                        <th><span class="sr-only">"Remove"</span></th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    <For
+                                        each=move || filters_list.clone()
+                                        key=|filter| filter.id
+                                        children=move |filter: WordFilter| {
+                                            let filter_id = filter.id;
+                                            view! {
+                                                <tr>
+                                                    <td><code>{filter.term}</code></td>
+                                                    <td>{mode_label(&filter.match_mode)}</td>
+                                                    <td>{severity_label(&filter.severity)}</td>
+                                                    <td>{format_relative_time(&filter.created_at)}</td>
+                                                    <td>
+                                                        <button
+                                                            type="button"
+                                                            class="btn btn-danger"
+                                                            on:click=move |_| handle_remove(filter_id)
+                                                        >"Remove"</button>
+                                                    </td>
+                                                </tr>
+                                            }
+                                        }
+                                    />
+                                </tbody>
+                            </table>
+                        }
+                            .into_any()
+                    }
+                    Err(_) => view! { <p class="error">"Failed to load word filter"</p> }.into_any(),
+                })}
+            </Suspense>
+
+            <h3>"Test the filter"</h3>
+            <div class="audit-filters">
+                <label class="audit-filter">
+                    <span>"Sample text"</span>
+                    <textarea
+                        rows="3"
+                        on:input=move |ev| sample.set(event_target_value(&ev))
+                        prop:value=move || sample.get()
+                    ></textarea>
+                </label>
+                <button type="button" class="btn btn-secondary" on:click=handle_test>"Test"</button>
+            </div>
+            {move || test_error.get().map(|error| view! { <p class="error" role="alert">{error}</p> })}
+            {move || test_result.get().map(|found| match found {
+                Some(found) => view! {
+                    <p role="status">
+                        "Matches " {found.describe()} " · " {found.severity.label()}
+                    </p>
+                }
+                    .into_any(),
+                None => view! { <p role="status">"No filter rule matches this text"</p> }.into_any(),
+            })}
+        </div>
+    }
+}
@@ -0,0 +1,93 @@
+/// One line of a line-by-line comparison between two texts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Unchanged(String),
+    Added(String),
+    Removed(String),
+}
+
+/// Compare two texts line by line, keeping the longest run of shared lines.
+pub fn line_diff(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // common[i][j] = length of the longest common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(DiffLine::Unchanged(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            lines.push(DiffLine::Removed(old[i].to_string()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new[j].to_string()));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|l| DiffLine::Removed(l.to_string())));
+    lines.extend(new[j..].iter().map(|l| DiffLine::Added(l.to_string())));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_texts_are_unchanged() {
+        assert_eq!(
+            line_diff("a\nb", "a\nb"),
+            vec![
+                DiffLine::Unchanged("a".into()),
+                DiffLine::Unchanged("b".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn changed_line_is_removed_then_added() {
+        assert_eq!(
+            line_diff("a\nb\nc", "a\nB\nc"),
+            vec![
+                DiffLine::Unchanged("a".into()),
+                DiffLine::Removed("b".into()),
+                DiffLine::Added("B".into()),
+                DiffLine::Unchanged("c".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn inserted_and_deleted_lines_keep_shared_lines() {
+        assert_eq!(
+            line_diff("a\nb\nc", "x\na\nc\nd"),
+            vec![
+                DiffLine::Added("x".into()),
+                DiffLine::Unchanged("a".into()),
+                DiffLine::Removed("b".into()),
+                DiffLine::Unchanged("c".into()),
+                DiffLine::Added("d".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_side_is_all_added_or_removed() {
+        assert_eq!(line_diff("", "a"), vec![DiffLine::Added("a".into())]);
+        assert_eq!(line_diff("a", ""), vec![DiffLine::Removed("a".into())]);
+    }
+}
@@ -0,0 +1,76 @@
+use leptos::prelude::*;
+
+use crate::models::FlagReason;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Inline prompt asking a user why they are flagging an idea or comment.
+/// Moderators see the category and any details in the Flags queue.
+#[component]
+pub fn FlagReasonPrompt(
+    on_confirm: Callback<(FlagReason, Option<String>)>,
+    on_cancel: Callback<()>,
+) -> impl IntoView {
+    let selected = RwSignal::new(String::new());
+    let details = RwSignal::new(String::new());
+    let error = RwSignal::new(Option::<String>::None);
+
+    let submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let Some(reason) = FlagReason::from_name(&selected.get()) else {
+            error.set(Some("Please choose a reason.".to_string()));
+            return;
+        };
+        let details = Some(details.get().trim().to_string()).filter(|text| !text.is_empty());
+        if reason == FlagReason::Other && details.is_none() {
+            error.set(Some("Please describe the problem.".to_string()));
+            return;
+        }
+        error.set(None);
+        on_confirm.run((reason, details));
+    };
+
+    view! {
+        <form class="flag-reason-prompt" on:submit=submit>
+            <Show when=move || error.get().is_some()>
+                <div class="dialog-alert dialog-alert-error" role="alert" aria-live="polite">
+                    {move || error.get().unwrap_or_default()}
+                </div>
+            </Show>
+            <label class="moderation-reason-field">
+                <span>"Why are you flagging this?"</span>
+                <select
+                    on:change=move |ev| selected.set(event_target_value(&ev))
+                    prop:value=move || selected.get()
+                >
+                    <option value="">"Choose a reason…"</option>
+                    {FlagReason::ALL
+                        .into_iter()
+                        .map(|reason| view! { <option value=reason.as_str()>{reason.label()}</option> })
+                        .collect_view()}
+                </select>
+            </label>
+            <textarea
+                class="dialog-textarea"
+                aria-label="Details"
+                placeholder=move || {
+                    if selected.get() == FlagReason::Other.as_str() {
+                        "Describe the problem"
+                    } else {
+                        "Details for the moderators (optional)"
+                    }
+                }
+                maxlength=500
+                bind:value=details
+            />
+            <div class="dialog-footer">
+                <button type="button" class="btn-cancel" on:click=move |_| on_cancel.run(())>
+                    "Cancel"
+                </button>
+                <button type="submit" class="btn btn-danger">"Flag"</button>
+            </div>
+        </form>
+    }
+}
@@ -1,5 +1,16 @@
 use crate::auth::{get_user, use_auth_refresh};
-use crate::models::{Comment, CommentWithAuthor, Idea};
+use crate::models::{
+    Comment, CommentRevision, CommentSort, CommentWithAuthor, EditWindow, Idea, IdeaRevision,
+    StageChange,
+};
+#[cfg(feature = "ssr")]
+use crate::models::{FilterSeverity, ModerationAction};
+#[cfg(feature = "ssr")]
+use crate::profanity;
+#[cfg(feature = "ssr")]
+use crate::routes::admin::{
+    announce_comment, apply_word_filter_match, notify_author, record_moderation,
+};
 #[cfg(feature = "ssr")]
 use crate::routes::error_helpers::server_fn_error_with_log;
 use crate::routes::ideas::check_user_votes;
@@ -7,10 +18,11 @@ use crate::routes::paths;
 #[cfg(feature = "ssr")]
 use crate::routes::validation_helpers::{
     validate_comment_content, validate_idea_tags, validate_idea_title_and_content,
+    validate_moderation_reason,
 };
 use leptos::prelude::*;
 use leptos_meta::Title;
-use leptos_router::components::A;
+use leptos_router::components::{Redirect, A};
 use leptos_router::hooks::use_params_map;
 
 mod components;
@@ -18,24 +30,64 @@ use components::IdeaDetailLoaded;
 
 #[server]
 pub async fn get_idea(id: i32) -> Result<Idea, ServerFnError> {
-    Idea::get_by_id(id)
+    let fetch_error = |e| {
+        let context = format!("Failed to fetch idea {id}");
+        server_fn_error_with_log(&context, e, "Idea not found")
+    };
+    if let Some(idea) = Idea::get_by_id(id).await.map_err(fetch_error)? {
+        return Ok(idea);
+    }
+
+    // Hidden ideas stay visible to their author, who sees the moderator's
+    // reason, and to moderators. Merged ideas are open to everyone so old
+    // links can follow the redirect.
+    let viewer = get_user().await?;
+    Idea::get_by_id_mod(id)
         .await
-        .map_err(|e| {
-            let context = format!("Failed to fetch idea {id}");
-            server_fn_error_with_log(&context, e, "Idea not found")
-        })?
+        .map_err(fetch_error)?
+        .filter(|idea| {
+            idea.merged_into.is_some()
+                || viewer
+                    .as_ref()
+                    .is_some_and(|user| user.is_moderator() || user.id == idea.user_id)
+        })
         .ok_or_else(|| ServerFnError::new("Idea not found"))
 }
 
+/// An idea's stage changes and official responses, oldest first, for anyone
+/// who can see the idea.
 #[server]
-pub async fn get_comments(idea_id: i32) -> Result<Vec<CommentWithAuthor>, ServerFnError> {
-    Comment::get_by_idea_id(idea_id, false).await.map_err(|e| {
-        server_fn_error_with_log("Failed to fetch comments", e, "Failed to fetch comments")
+pub async fn get_stage_timeline(idea_id: i32) -> Result<Vec<StageChange>, ServerFnError> {
+    get_idea(idea_id).await?;
+    StageChange::list_for_idea(idea_id).await.map_err(|e| {
+        server_fn_error_with_log(
+            "Failed to fetch stage timeline",
+            e,
+            "Failed to fetch stage timeline",
+        )
     })
 }
 
 #[server]
-pub async fn create_comment(idea_id: i32, content: String) -> Result<Comment, ServerFnError> {
+pub async fn get_comments(
+    idea_id: i32,
+    sort: CommentSort,
+) -> Result<Vec<CommentWithAuthor>, ServerFnError> {
+    Comment::get_by_idea_id(idea_id, false, sort)
+        .await
+        .map_err(|e| {
+            server_fn_error_with_log("Failed to fetch comments", e, "Failed to fetch comments")
+        })
+}
+
+/// Comment on an idea, or reply to one of its comments. Replies to a reply
+/// join the top-level comment's thread, so threads stay one level deep.
+#[server]
+pub async fn create_comment(
+    idea_id: i32,
+    content: String,
+    parent_id: Option<i32>,
+) -> Result<Comment, ServerFnError> {
     use crate::auth::require_auth;
     let user = require_auth().await?;
 
@@ -49,11 +101,349 @@ pub async fn create_comment(idea_id: i32, content: String) -> Result<Comment, Se
         return Err(ServerFnError::new("Comments are locked on this idea"));
     }
 
-    validate_comment_content(&content)?;
-    Comment::create(user.id, idea_id, content.trim().to_string())
+    let parent = match parent_id {
+        Some(parent_id) => Some(
+            Comment::get_by_id(parent_id)
+                .await
+                .map_err(|e| {
+                    server_fn_error_with_log(
+                        "Failed to fetch comment",
+                        e,
+                        "Failed to fetch comment",
+                    )
+                })?
+                .filter(|parent| parent.idea_id == idea_id && !parent.is_held)
+                .ok_or_else(|| ServerFnError::new("The comment you replied to is gone"))?,
+        ),
+        None => None,
+    };
+
+    let filter_match = validate_comment_content(&content, &profanity::active())?;
+    let thread_id = parent
+        .as_ref()
+        .map(|parent| parent.parent_id.unwrap_or(parent.id));
+    let mut comment = Comment::create(user.id, idea_id, content.trim().to_string(), thread_id)
         .await
         .map_err(|e| {
             server_fn_error_with_log("Failed to create comment", e, "Failed to create comment")
+        })?;
+    if let Some(found) = &filter_match {
+        apply_word_filter_match(&user, "comment", comment.id, found).await;
+        comment.is_held = found.severity == FilterSeverity::Hold;
+    }
+
+    // Followers hear about a held comment only if a moderator releases it
+    if !comment.is_held {
+        let replied_to = parent.map(|parent| parent.user_id);
+        announce_comment(user.id, &user.name, &idea, comment.id, replied_to).await;
+    }
+
+    Ok(comment)
+}
+
+/// Flag a comment, or remove the current user's flag if there is one. A new
+/// flag needs a `reason` (see `FlagReason`).
+#[server]
+pub async fn toggle_comment_flag_server(
+    comment_id: i32,
+    reason: Option<String>,
+    details: Option<String>,
+) -> Result<bool, ServerFnError> {
+    use crate::auth::require_auth;
+    use crate::routes::ideas::toggle_flag;
+    let user = require_auth().await?;
+
+    toggle_flag(user.id, "comment", comment_id, reason, details).await
+}
+
+/// Mark a comment as helpful, or take the mark back. Returns whether the
+/// current user now marks it helpful.
+#[server]
+pub async fn toggle_comment_vote(comment_id: i32) -> Result<bool, ServerFnError> {
+    use crate::auth::require_auth;
+    use crate::models::CommentVote;
+    let user = require_auth().await?;
+
+    let comment = Comment::get_by_id(comment_id)
+        .await
+        .map_err(|e| {
+            server_fn_error_with_log("Failed to fetch comment", e, "Failed to fetch comment")
+        })?
+        .filter(|comment| !comment.is_held)
+        .ok_or_else(|| ServerFnError::new("Comment not found"))?;
+    if comment.user_id == user.id {
+        return Err(ServerFnError::new(
+            "You cannot mark your own comment as helpful",
+        ));
+    }
+
+    CommentVote::toggle(user.id, comment_id).await.map_err(|e| {
+        server_fn_error_with_log(
+            "Failed to toggle comment vote",
+            e,
+            "Failed to toggle comment vote",
+        )
+    })
+}
+
+/// Ids of the comments on an idea the current user marked helpful; empty
+/// when signed out.
+#[server]
+pub async fn get_my_comment_votes(idea_id: i32) -> Result<Vec<i32>, ServerFnError> {
+    use crate::models::CommentVote;
+    let Some(user) = get_user().await? else {
+        return Ok(Vec::new());
+    };
+
+    CommentVote::get_voted_comment_ids(user.id, idea_id)
+        .await
+        .map_err(|e| {
+            server_fn_error_with_log(
+                "Failed to fetch comment votes",
+                e,
+                "Failed to fetch comment votes",
+            )
+        })
+}
+
+/// Ids of the comments on an idea the current user has flagged; empty when
+/// signed out.
+#[server]
+pub async fn get_my_comment_flags(idea_id: i32) -> Result<Vec<i32>, ServerFnError> {
+    use crate::models::Flag;
+    let Some(user) = get_user().await? else {
+        return Ok(Vec::new());
+    };
+
+    Flag::get_user_flagged_comment_ids(user.id, idea_id)
+        .await
+        .map_err(|e| {
+            server_fn_error_with_log(
+                "Failed to fetch comment flags",
+                e,
+                "Failed to fetch comment flags",
+            )
+        })
+}
+
+#[server]
+pub async fn get_edit_window() -> Result<EditWindow, ServerFnError> {
+    Ok(EditWindow::from_env())
+}
+
+#[cfg(feature = "ssr")]
+fn edit_window_closed(target_type: &str) -> ServerFnError {
+    ServerFnError::new(format!(
+        "This {target_type} can no longer be changed because the edit window has closed"
+    ))
+}
+
+/// Load an idea the current user wrote, provided they may still change it.
+/// The change itself checks the window again, in case someone votes or
+/// comments in between.
+#[cfg(feature = "ssr")]
+async fn require_editable_own_idea(idea_id: i32, user_id: i32) -> Result<Idea, ServerFnError> {
+    let idea = Idea::get_by_id_mod(idea_id)
+        .await
+        .map_err(|e| server_fn_error_with_log("Failed to fetch idea", e, "Failed to fetch idea"))?
+        .ok_or_else(|| ServerFnError::new("Idea not found"))?;
+
+    if idea.user_id != user_id {
+        return Err(ServerFnError::new("You can only change your own ideas"));
+    }
+
+    let has_engagement = Idea::has_engagement(idea_id).await.map_err(|e| {
+        server_fn_error_with_log("Failed to check idea activity", e, "Failed to fetch idea")
+    })?;
+    if !EditWindow::from_env().is_open(idea.created_at, chrono::Utc::now(), has_engagement) {
+        return Err(edit_window_closed("idea"));
+    }
+
+    Ok(idea)
+}
+
+/// Load a comment the current user wrote, provided they may still change it.
+/// As with ideas, the change itself checks the window again.
+#[cfg(feature = "ssr")]
+async fn require_editable_own_comment(
+    comment_id: i32,
+    user_id: i32,
+) -> Result<Comment, ServerFnError> {
+    let comment = Comment::get_by_id(comment_id)
+        .await
+        .map_err(|e| {
+            server_fn_error_with_log("Failed to fetch comment", e, "Failed to fetch comment")
+        })?
+        .ok_or_else(|| ServerFnError::new("Comment not found"))?;
+
+    if comment.user_id != user_id {
+        return Err(ServerFnError::new("You can only change your own comments"));
+    }
+
+    let has_engagement = Comment::has_engagement(comment_id).await.map_err(|e| {
+        server_fn_error_with_log(
+            "Failed to check comment activity",
+            e,
+            "Failed to fetch comment",
+        )
+    })?;
+    if !EditWindow::from_env().is_open(comment.created_at, chrono::Utc::now(), has_engagement) {
+        return Err(edit_window_closed("comment"));
+    }
+
+    Ok(comment)
+}
+
+#[server]
+pub async fn update_own_idea(
+    idea_id: i32,
+    title: String,
+    content: String,
+) -> Result<(), ServerFnError> {
+    use crate::auth::require_auth;
+    let user = require_auth().await?;
+
+    require_editable_own_idea(idea_id, user.id).await?;
+    let filter_match = validate_idea_title_and_content(&title, &content, &profanity::active())?;
+
+    let updated = Idea::update_content(
+        idea_id,
+        user.id,
+        EditWindow::from_env(),
+        title.trim().to_string(),
+        content.trim().to_string(),
+    )
+    .await
+    .map_err(|e| server_fn_error_with_log("Failed to update idea", e, "Failed to update idea"))?;
+
+    if !updated {
+        return Err(edit_window_closed("idea"));
+    }
+    if let Some(found) = &filter_match {
+        apply_word_filter_match(&user, "idea", idea_id, found).await;
+    }
+
+    Ok(())
+}
+
+#[server]
+pub async fn withdraw_own_idea(idea_id: i32) -> Result<(), ServerFnError> {
+    use crate::auth::require_auth;
+    let user = require_auth().await?;
+
+    require_editable_own_idea(idea_id, user.id).await?;
+
+    let deleted = Idea::delete_own(idea_id, user.id, EditWindow::from_env())
+        .await
+        .map_err(|e| {
+            server_fn_error_with_log("Failed to withdraw idea", e, "Failed to withdraw idea")
+        })?;
+
+    if !deleted {
+        return Err(edit_window_closed("idea"));
+    }
+
+    Ok(())
+}
+
+#[server]
+pub async fn update_own_comment(comment_id: i32, content: String) -> Result<(), ServerFnError> {
+    use crate::auth::require_auth;
+    let user = require_auth().await?;
+
+    require_editable_own_comment(comment_id, user.id).await?;
+    let filter_match = validate_comment_content(&content, &profanity::active())?;
+
+    let updated = Comment::update_content(
+        comment_id,
+        user.id,
+        EditWindow::from_env(),
+        content.trim().to_string(),
+    )
+    .await
+    .map_err(|e| {
+        server_fn_error_with_log("Failed to update comment", e, "Failed to update comment")
+    })?;
+
+    if !updated {
+        return Err(edit_window_closed("comment"));
+    }
+    if let Some(found) = &filter_match {
+        apply_word_filter_match(&user, "comment", comment_id, found).await;
+    }
+
+    Ok(())
+}
+
+#[server]
+pub async fn withdraw_own_comment(comment_id: i32) -> Result<(), ServerFnError> {
+    use crate::auth::require_auth;
+    let user = require_auth().await?;
+
+    require_editable_own_comment(comment_id, user.id).await?;
+
+    let deleted = Comment::soft_delete_own(comment_id, user.id, EditWindow::from_env())
+        .await
+        .map_err(|e| {
+            server_fn_error_with_log(
+                "Failed to withdraw comment",
+                e,
+                "Failed to withdraw comment",
+            )
+        })?;
+
+    if !deleted {
+        return Err(edit_window_closed("comment"));
+    }
+
+    Ok(())
+}
+
+#[server]
+pub async fn get_idea_revisions(idea_id: i32) -> Result<Vec<IdeaRevision>, ServerFnError> {
+    use crate::auth::require_auth;
+    let user = require_auth().await?;
+
+    let idea = Idea::get_by_id_mod(idea_id)
+        .await
+        .map_err(|e| server_fn_error_with_log("Failed to fetch idea", e, "Failed to fetch idea"))?
+        .ok_or_else(|| ServerFnError::new("Idea not found"))?;
+    if idea.user_id != user.id && !user.is_moderator() {
+        return Err(ServerFnError::new(
+            "Only the author and moderators can view this history",
+        ));
+    }
+
+    IdeaRevision::get_by_idea_id(idea_id).await.map_err(|e| {
+        server_fn_error_with_log("Failed to fetch idea history", e, "Failed to fetch history")
+    })
+}
+
+#[server]
+pub async fn get_comment_revisions(comment_id: i32) -> Result<Vec<CommentRevision>, ServerFnError> {
+    use crate::auth::require_auth;
+    let user = require_auth().await?;
+
+    let comment = Comment::get_by_id(comment_id)
+        .await
+        .map_err(|e| {
+            server_fn_error_with_log("Failed to fetch comment", e, "Failed to fetch comment")
+        })?
+        .ok_or_else(|| ServerFnError::new("Comment not found"))?;
+    if comment.user_id != user.id && !user.is_moderator() {
+        return Err(ServerFnError::new(
+            "Only the author and moderators can view this history",
+        ));
+    }
+
+    CommentRevision::get_by_comment_id(comment_id)
+        .await
+        .map_err(|e| {
+            server_fn_error_with_log(
+                "Failed to fetch comment history",
+                e,
+                "Failed to fetch history",
+            )
         })
 }
 
@@ -63,18 +453,28 @@ pub async fn update_idea_content_mod(
     title: String,
     content: String,
     tags: String,
+    category_id: i32,
 ) -> Result<(), ServerFnError> {
-    use crate::auth::require_moderator;
-    require_moderator().await?;
+    use crate::auth::{require_category_moderator, require_idea_moderator};
+    use crate::routes::ideas::{reject_retired_tags, validate_category};
+    let user = require_idea_moderator(idea_id).await?;
+    // Moving an idea needs the right to moderate where it goes, too
+    require_category_moderator(category_id).await?;
+
+    let tags = validate_idea_tags(&tags)?;
+    // A moderator's own wording is not held or flagged, only blocked
+    validate_idea_title_and_content(&title, &content, &profanity::active())?;
 
-    validate_idea_tags(&tags)?;
-    validate_idea_title_and_content(&title, &content)?;
+    reject_retired_tags(&tags, Some(idea_id)).await?;
+    validate_category(category_id, Some(idea_id)).await?;
 
     let updated = Idea::update_content_mod(
         idea_id,
+        user.id,
         title.trim().to_string(),
         content.trim().to_string(),
-        tags.trim().to_string(),
+        &tags,
+        category_id,
     )
     .await
     .map_err(|e| server_fn_error_with_log("Failed to update idea", e, "Failed to update idea"))?;
@@ -88,12 +488,13 @@ pub async fn update_idea_content_mod(
 
 #[server]
 pub async fn update_comment_mod(comment_id: i32, content: String) -> Result<(), ServerFnError> {
-    use crate::auth::require_moderator;
-    require_moderator().await?;
+    use crate::auth::require_comment_moderator;
+    let user = require_comment_moderator(comment_id).await?;
 
-    validate_comment_content(&content)?;
+    // A moderator's own wording is not held or flagged, only blocked
+    validate_comment_content(&content, &profanity::active())?;
 
-    let updated = Comment::update_content_mod(comment_id, content.trim().to_string())
+    let updated = Comment::update_content_mod(comment_id, user.id, content.trim().to_string())
         .await
         .map_err(|e| {
             server_fn_error_with_log("Failed to update comment", e, "Failed to update comment")
@@ -107,19 +508,56 @@ pub async fn update_comment_mod(comment_id: i32, content: String) -> Result<(),
 }
 
 #[server]
-pub async fn delete_comment_mod(comment_id: i32) -> Result<(), ServerFnError> {
-    use crate::auth::require_moderator;
-    require_moderator().await?;
+pub async fn delete_comment_mod(comment_id: i32, reason: String) -> Result<(), ServerFnError> {
+    use crate::auth::require_comment_moderator;
+    let user = require_comment_moderator(comment_id).await?;
+    let reason = validate_moderation_reason(&reason)?;
 
-    Comment::soft_delete(comment_id).await.map_err(|e| {
-        server_fn_error_with_log("Failed to delete comment", e, "Failed to delete comment")
-    })
+    let comment = Comment::get_by_id(comment_id)
+        .await
+        .map_err(|e| {
+            server_fn_error_with_log("Failed to fetch comment", e, "Failed to fetch comment")
+        })?
+        .ok_or_else(|| ServerFnError::new("Comment not found"))?;
+
+    Comment::soft_delete(comment_id, reason.clone())
+        .await
+        .map_err(|e| {
+            server_fn_error_with_log("Failed to delete comment", e, "Failed to delete comment")
+        })?;
+
+    // A deleted comment no longer needs review
+    crate::models::Flag::clear_flags("comment", comment_id)
+        .await
+        .map_err(|e| {
+            server_fn_error_with_log("Failed to clear flags", e, "Failed to delete comment")
+        })?;
+
+    record_moderation(
+        &user,
+        ModerationAction::DeleteComment,
+        "comment",
+        comment_id,
+        Some(comment.content),
+        None,
+        Some(reason.clone()),
+    )
+    .await;
+
+    notify_author(
+        comment.user_id,
+        format!("Your comment was removed by a moderator: {}", reason),
+        Some(format!("/ideas/{}", comment.idea_id)),
+    )
+    .await;
+
+    Ok(())
 }
 
 #[server]
 pub async fn toggle_comment_pin(comment_id: i32) -> Result<bool, ServerFnError> {
-    use crate::auth::require_moderator;
-    require_moderator().await?;
+    use crate::auth::require_comment_moderator;
+    require_comment_moderator(comment_id).await?;
 
     Comment::toggle_pin(comment_id).await.map_err(|e| {
         server_fn_error_with_log(
@@ -132,12 +570,25 @@ pub async fn toggle_comment_pin(comment_id: i32) -> Result<bool, ServerFnError>
 
 #[server]
 pub async fn toggle_idea_comments(idea_id: i32) -> Result<bool, ServerFnError> {
-    use crate::auth::require_moderator;
-    require_moderator().await?;
+    use crate::auth::require_idea_moderator;
+    let user = require_idea_moderator(idea_id).await?;
 
-    Idea::toggle_comments(idea_id).await.map_err(|e| {
+    let enabled = Idea::toggle_comments(idea_id).await.map_err(|e| {
         server_fn_error_with_log("Failed to toggle comments", e, "Failed to toggle comments")
-    })
+    })?;
+
+    record_moderation(
+        &user,
+        ModerationAction::ToggleComments,
+        "idea",
+        idea_id,
+        Some((!enabled).to_string()),
+        Some(enabled.to_string()),
+        None,
+    )
+    .await;
+
+    Ok(enabled)
 }
 
 /// Individual idea detail page with comments
@@ -153,12 +604,17 @@ pub fn IdeaDetailPage() -> impl IntoView {
     };
 
     let idea_resource = Resource::new(idea_id, |id| async move { get_idea(id).await });
-    let comments_resource = Resource::new(idea_id, |id| async move { get_comments(id).await });
+    let comment_sort = RwSignal::new(CommentSort::default());
+    let comments_resource = Resource::new(
+        move || (idea_id(), comment_sort.get()),
+        |(id, sort)| async move { get_comments(id, sort).await },
+    );
     let auth_refresh = use_auth_refresh();
     let user_resource = Resource::new(
         move || auth_refresh.get(),
         move |_| async move { get_user().await },
     );
+    let edit_window = Resource::new(|| (), |_| async { get_edit_window().await });
     let has_voted = RwSignal::new(false);
 
     // Load user's vote status for this idea
@@ -182,12 +638,27 @@ pub fn IdeaDetailPage() -> impl IntoView {
                     {move || {
                         idea_resource.get().map(|result| {
                             match result {
+                                Ok(Idea { merged_into: Some(target_id), .. }) => {
+                                    let target = format!("/ideas/{}", target_id);
+                                    view! {
+                                        <Redirect path=target.clone()/>
+                                        <div class="empty-state">
+                                            <p>
+                                                "This idea was merged into "
+                                                <A href=target>{format!("idea #{}", target_id)}</A>
+                                                "."
+                                            </p>
+                                        </div>
+                                    }.into_any()
+                                }
                                 Ok(idea) => view! {
                                     <IdeaDetailLoaded
                                         idea=idea
                                         idea_resource=idea_resource
                                         comments_resource=comments_resource
+                                        comment_sort=comment_sort
                                         user_resource=user_resource
+                                        edit_window=edit_window
                                         has_voted=has_voted
                                     />
                                 }.into_any(),
@@ -2,10 +2,12 @@ use leptos::prelude::*;
 use leptos_meta::Title;
 
 use crate::auth::UserSession;
-use crate::models::{CommentWithAuthor, Idea};
+use crate::models::{CommentSort, CommentWithAuthor, EditWindow, Idea};
 
 mod card;
 mod comments;
+mod history;
+mod timeline;
 
 use card::IdeaDetailCard;
 use comments::CommentsSection;
@@ -15,7 +17,9 @@ pub(super) fn IdeaDetailLoaded(
     idea: Idea,
     idea_resource: Resource<Result<Idea, ServerFnError>>,
     comments_resource: Resource<Result<Vec<CommentWithAuthor>, ServerFnError>>,
+    comment_sort: RwSignal<CommentSort>,
     user_resource: Resource<Result<Option<UserSession>, ServerFnError>>,
+    edit_window: Resource<Result<EditWindow, ServerFnError>>,
     has_voted: RwSignal<bool>,
 ) -> impl IntoView {
     let page_title = if idea.title.is_empty() {
@@ -31,14 +35,18 @@ pub(super) fn IdeaDetailLoaded(
         <IdeaDetailCard
             idea=idea
             idea_resource=idea_resource
+            comments_resource=comments_resource
             user_resource=user_resource
+            edit_window=edit_window
             has_voted=has_voted
         />
         <CommentsSection
             idea_id=idea_id
             idea_comments_enabled=idea_comments_enabled
             comments_resource=comments_resource
+            comment_sort=comment_sort
             user_resource=user_resource
+            edit_window=edit_window
         />
     }
 }
@@ -1,29 +1,57 @@
 use leptos::prelude::*;
+use leptos_router::components::A;
+use leptos_router::hooks::use_navigate;
 
 use crate::auth::UserSession;
-use crate::models::Idea;
+use crate::models::{
+    find_stage, next_stages, slugify, CommentWithAuthor, EditWindow, FlagReason, Idea, IdeaSearch,
+    MAX_RESPONSE_CHARS,
+};
 use crate::routes::async_helpers::{
     spawn_server_action, spawn_server_action_ok, spawn_server_action_refetch_resource,
     spawn_server_action_with_error,
 };
-use crate::routes::ideas::{check_idea_flag_server, toggle_idea_flag_server, toggle_vote};
-use crate::routes::view_helpers::{format_relative_time, stage_badge_color};
+use crate::routes::ideas::{
+    check_idea_flag_server, list_categories, list_stages, toggle_idea_flag_server, toggle_vote,
+};
+use crate::routes::paths;
+use crate::routes::view_helpers::{
+    confirm_action, edited_marker, format_relative_time, idea_visibility_notice, stage_badge_color,
+};
+use crate::routes::FlagReasonPrompt;
 
-use super::super::{toggle_idea_comments, update_idea_content_mod};
+use super::super::{
+    get_stage_timeline, toggle_idea_comments, update_idea_content_mod, update_own_idea,
+    withdraw_own_idea,
+};
+use super::history::IdeaHistoryPanel;
+use super::timeline::StageTimeline;
 
 #[component]
 pub(super) fn IdeaDetailCard(
     idea: Idea,
     idea_resource: Resource<Result<Idea, ServerFnError>>,
+    comments_resource: Resource<Result<Vec<CommentWithAuthor>, ServerFnError>>,
     user_resource: Resource<Result<Option<UserSession>, ServerFnError>>,
+    edit_window: Resource<Result<EditWindow, ServerFnError>>,
     has_voted: RwSignal<bool>,
 ) -> impl IntoView {
     let flagged = RwSignal::new(false);
+    let asking_flag_reason = RwSignal::new(false);
     let stage_updating = RwSignal::new(false);
     let idea_editing = RwSignal::new(false);
+    // Authors edit through their own server function, without tags
+    let editing_as_author = RwSignal::new(false);
     let idea_edit_error = RwSignal::new(Option::<String>::None);
+    let navigate = use_navigate();
 
     let idea_id_val = idea.id;
+    let idea_author_id = idea.user_id;
+    let idea_created_at = idea.created_at;
+    let edited = idea.edited_at.as_ref().map(edited_marker);
+    let hidden_notice = idea_visibility_notice(&idea);
+    let has_history = idea.edited_at.is_some();
+    let show_history = RwSignal::new(false);
     let idea_pinned = idea.is_pinned();
     let idea_comments_enabled = idea.comments_enabled;
     let idea_vote_count = idea.vote_count;
@@ -31,15 +59,41 @@ pub(super) fn IdeaDetailCard(
     let idea_content = idea.content.clone();
     let relative_time = format_relative_time(&idea.created_at);
     let stage = idea.stage.clone();
-    let stage_color = stage_badge_color(&stage).to_string();
+    let stages = Resource::new(|| (), |_| async { list_stages().await });
+    let stage_error = RwSignal::new(Option::<String>::None);
+    // Stage picked in the select, waiting for an optional official response
+    let pending_stage = RwSignal::new(Option::<String>::None);
+    let stage_response = RwSignal::new(String::new());
+    let timeline = Resource::new(move || idea_id_val, get_stage_timeline);
     let tags_str = idea.tags.clone();
     let edit_title = RwSignal::new(idea_title.clone());
     let edit_content = RwSignal::new(idea_content.clone());
     let edit_tags = RwSignal::new(tags_str.clone());
+    let idea_category_id = idea.category_id;
+    let edit_category = RwSignal::new(idea_category_id.to_string());
+    let categories = Resource::new(|| (), |_| async { list_categories().await });
     let idea_title_value = StoredValue::new(idea_title.clone());
     let idea_content_value = StoredValue::new(idea_content.clone());
     let tags_str_value = StoredValue::new(tags_str.clone());
 
+    // Authors may change their idea until the window passes or someone engages
+    let author_window_open = move || {
+        let has_comments = comments_resource
+            .get()
+            .and_then(|result| result.ok())
+            .is_some_and(|comments| !comments.is_empty());
+        edit_window
+            .get()
+            .and_then(|result| result.ok())
+            .is_some_and(|window| {
+                window.is_open(
+                    idea_created_at,
+                    chrono::Utc::now(),
+                    idea_vote_count > 0 || has_comments,
+                )
+            })
+    };
+
     Effect::new(move |_| match user_resource.get() {
         Some(Ok(Some(_))) => {
             let id = idea_id_val;
@@ -82,6 +136,12 @@ pub(super) fn IdeaDetailCard(
                     </Suspense>
                 </div>
                 <div class="detail-idea-content">
+                    {hidden_notice.map(|notice| view! { <p class="moderation-notice" role="status">{notice}</p> })}
+                    <Show when=move || idea_edit_error.get().is_some()>
+                        <div class="dialog-alert dialog-alert-error" role="alert" aria-live="polite" aria-atomic="true">
+                            {move || idea_edit_error.get().unwrap_or_default()}
+                        </div>
+                    </Show>
                     <Show
                         when=move || idea_editing.get()
                         fallback=move || {
@@ -100,23 +160,28 @@ pub(super) fn IdeaDetailCard(
                                 let title_value = edit_title.get();
                                 let content_value = edit_content.get();
                                 let tags_value = edit_tags.get();
+                                let category_value = edit_category.get().parse::<i32>().unwrap_or(idea_category_id);
                                 idea_edit_error.set(None);
                                 let id = idea_id_val;
-                                spawn_server_action_with_error(
-                                    update_idea_content_mod(id, title_value, content_value, tags_value),
-                                    move |_| {
-                                        idea_resource.refetch();
-                                        idea_editing.set(false);
-                                    },
-                                    idea_edit_error,
-                                );
+                                let on_saved = move |_| {
+                                    idea_resource.refetch();
+                                    idea_editing.set(false);
+                                };
+                                if editing_as_author.get() {
+                                    spawn_server_action_with_error(
+                                        update_own_idea(id, title_value, content_value),
+                                        on_saved,
+                                        idea_edit_error,
+                                    );
+                                } else {
+                                    spawn_server_action_with_error(
+                                        update_idea_content_mod(id, title_value, content_value, tags_value, category_value),
+                                        on_saved,
+                                        idea_edit_error,
+                                    );
+                                }
                             }
                         >
-                            <Show when=move || idea_edit_error.get().is_some()>
-                                <div class="dialog-alert dialog-alert-error" role="alert" aria-live="polite" aria-atomic="true">
-                                    {move || idea_edit_error.get().unwrap_or_default()}
-                                </div>
-                            </Show>
                             <div class="form-group">
                                 <label class="form-label" for="idea-edit-title">"Title"</label>
                                 <input
@@ -136,23 +201,63 @@ pub(super) fn IdeaDetailCard(
                                     bind:value=edit_content
                                 />
                             </div>
-                            <div class="form-group">
-                                <label class="form-label" for="idea-edit-tags">"Tags (comma-separated)"</label>
-                                <input
-                                    id="idea-edit-tags"
-                                    class="dialog-input"
-                                    type="text"
-                                    maxlength=200
-                                    placeholder="e.g., security, performance, ui"
-                                    bind:value=edit_tags
-                                />
-                            </div>
+                            <Show when=move || !editing_as_author.get()>
+                                <div class="form-group">
+                                    <label class="form-label" for="idea-edit-tags">"Tags (comma-separated)"</label>
+                                    <input
+                                        id="idea-edit-tags"
+                                        class="dialog-input"
+                                        type="text"
+                                        maxlength=200
+                                        placeholder="e.g., security, performance, ui"
+                                        bind:value=edit_tags
+                                    />
+                                </div>
+                                <div class="form-group">
+                                    <label class="form-label" for="idea-edit-category">"Category"</label>
+                                    <Suspense fallback=|| ()>
+                                        {move || categories.get().and_then(Result::ok).map(|categories| view! {
+                                            <select
+                                                id="idea-edit-category"
+                                                class="dialog-input"
+                                                prop:value=move || edit_category.get()
+                                                on:change=move |ev| edit_category.set(event_target_value(&ev))
+                                            >
+                                                {categories
+                                                    .into_iter()
+                                                    // Archived categories only keep the ideas already in them
+                                                    .filter(|category| !category.is_archived || category.id == idea_category_id)
+                                                    .map(|category| view! {
+                                                        <option value=category.id.to_string()>{category.name}</option>
+                                                    })
+                                                    .collect_view()}
+                                            </select>
+                                        })}
+                                    </Suspense>
+                                </div>
+                            </Show>
                             <div class="dialog-footer">
                                 <button type="submit" class="submit-btn btn btn-primary">"Save"</button>
                             </div>
                         </form>
                     </Show>
                     <Show when=move || !idea_editing.get()>
+                        <Suspense fallback=|| ()>
+                            {move || {
+                                categories
+                                    .get()
+                                    .and_then(Result::ok)
+                                    .and_then(|categories| categories.into_iter().find(|category| category.id == idea_category_id))
+                                    .map(|category| view! {
+                                        <A
+                                            href=format!("{}?{}={}", paths::HOME, IdeaSearch::CATEGORY, category.slug)
+                                            attr:class="category-badge"
+                                        >
+                                            {category.name}
+                                        </A>
+                                    })
+                            }}
+                        </Suspense>
                         {move || {
                             let tag_list: Vec<String> = tags_str_value
                                 .get_value()
@@ -170,7 +275,11 @@ pub(super) fn IdeaDetailCard(
                                             each=move || tag_list.clone()
                                             key=|t| t.clone()
                                             children=move |tag: String| {
-                                                view! { <span class="detail-tag">{tag}</span> }
+                                                view! {
+                                                    <A href=format!("{}/{}", paths::TAGS, slugify(&tag)) attr:class="detail-tag">
+                                                        {tag}
+                                                    </A>
+                                                }
                                             }
                                         />
                                     </div>
@@ -182,85 +291,108 @@ pub(super) fn IdeaDetailCard(
                     <div class="detail-meta-row">
                         <div class="detail-meta-info">
                             <Suspense fallback=|| ()>
-                                {move || user_resource.get().map(|ur| match ur {
+                                {move || user_resource.get().map(|ur| {
+                                    let stages = stages.get().and_then(Result::ok).unwrap_or_default();
+                                    let stage_color_class = stage_badge_color(
+                                        find_stage(&stages, &stage).map_or("", |found| found.color.as_str()),
+                                    );
+                                    match ur {
                                     Ok(Some(user)) if user.is_moderator() => {
                                         let current_stage = stage.clone();
                                         let current_stage_for_value = current_stage.clone();
-                                        let stage_color_class = stage_color.clone();
+                                        let options: Vec<String> = std::iter::once(stage.clone())
+                                            .chain(next_stages(&stages, &stage).into_iter().map(|next| next.name.clone()))
+                                            .collect();
                                         view! {
                                             <label class="sr-only" for="idea-stage">"Stage"</label>
                                             <select
                                                 id="idea-stage"
                                                 class=format!("stage-select stage-badge stage-{}", stage_color_class)
-                                                prop:value=move || current_stage_for_value.clone()
+                                                prop:value=move || pending_stage.get().unwrap_or_else(|| current_stage_for_value.clone())
                                                 disabled=move || stage_updating.get()
                                                 on:change=move |ev| {
                                                     let new_stage = event_target_value(&ev);
-                                                    if new_stage == current_stage {
-                                                        return;
-                                                    }
-                                                    stage_updating.set(true);
-                                                    let id = idea_id_val;
-                                                    spawn_server_action(
-                                                        crate::routes::admin::update_idea_stage_action(
-                                                            id,
-                                                            new_stage,
-                                                        ),
-                                                        move |_| {
-                                                            idea_resource.refetch();
-                                                            stage_updating.set(false);
-                                                        },
-                                                        move |_| {
-                                                            stage_updating.set(false);
-                                                        },
-                                                    );
+                                                    stage_error.set(None);
+                                                    pending_stage.set((new_stage != current_stage).then_some(new_stage));
                                                 }
                                             >
-                                                <option value="Ideate">"Ideate"</option>
-                                                <option value="Review">"Review"</option>
-                                                <option value="In Progress">"In Progress"</option>
-                                                <option value="Completed">"Completed"</option>
+                                                {options
+                                                    .into_iter()
+                                                    .map(|name| view! { <option value=name.clone()>{name.clone()}</option> })
+                                                    .collect_view()}
                                             </select>
                                         }
                                             .into_any()
                                     }
                                     _ => {
-                                        let stage_color_class = stage_color.clone();
                                         view! {
                                             <span class=format!("stage-badge stage-{}", stage_color_class)>{stage.clone()}</span>
                                         }
                                             .into_any()
                                     }
+                                    }
                                 })}
                             </Suspense>
                             <span class="detail-time">
                                 {format!("submitted {}", relative_time)}
                             </span>
+                            {edited.map(|(label, title)| view! { <span class="edited-marker" title=title>{label}</span> })}
                         </div>
                         <Suspense fallback=|| ()>
                             {move || user_resource.get().map(|ur| match ur {
                                 Ok(Some(user)) => {
                                     let is_mod = user.is_moderator();
+                                    let is_author = user.id == idea_author_id;
+                                    let can_author_change = move || is_author && author_window_open();
+                                    let navigate = navigate.clone();
                                     view! {
                                         <div class="detail-card-actions">
-                                            <Show when=move || is_mod>
+                                            <Show when=move || is_mod || can_author_change()>
                                                 <button
                                                     type="button"
                                                     class="btn-edit btn btn-secondary"
                                                     on:click=move |_| {
                                                         idea_edit_error.set(None);
+                                                        editing_as_author.set(!is_mod);
                                                         idea_editing.set(!idea_editing.get());
                                                     }
                                                 >
                                                     {move || if idea_editing.get() { "Cancel Edit" } else { "Edit" }}
                                                 </button>
                                             </Show>
+                                            <Show when=can_author_change>
+                                                <button
+                                                    type="button"
+                                                    class="btn-delete btn btn-danger"
+                                                    on:click={
+                                                        let navigate = navigate.clone();
+                                                        move |_| {
+                                                            if !confirm_action("Withdraw this idea? This cannot be undone.") {
+                                                                return;
+                                                            }
+                                                            idea_edit_error.set(None);
+                                                            let navigate = navigate.clone();
+                                                            spawn_server_action_with_error(
+                                                                withdraw_own_idea(idea_id_val),
+                                                                move |_| navigate(paths::HOME, Default::default()),
+                                                                idea_edit_error,
+                                                            );
+                                                        }
+                                                    }
+                                                >
+                                                    "Withdraw"
+                                                </button>
+                                            </Show>
                                             <button
                                                 type="button"
                                                 class="btn-flag btn btn-secondary"
                                                 on:click=move |_| {
+                                                    if !flagged.get() {
+                                                        asking_flag_reason.set(true);
+                                                        return;
+                                                    }
                                                     let id = idea_id_val;
-                                                    spawn_server_action_ok(toggle_idea_flag_server(id), move |is_flagged| {
+                                                    spawn_server_action_ok(toggle_idea_flag_server(id, None, None), move |is_flagged| {
                                                         flagged.set(is_flagged);
                                                         idea_resource.refetch();
                                                     });
@@ -268,6 +400,16 @@ pub(super) fn IdeaDetailCard(
                                             >
                                                 {move || if flagged.get() { "Unflag" } else { "Flag" }}
                                             </button>
+                                            <Show when=move || has_history && (is_mod || is_author)>
+                                                <button
+                                                    type="button"
+                                                    class="btn-history btn btn-secondary"
+                                                    aria-expanded=move || show_history.get().to_string()
+                                                    on:click=move |_| show_history.update(|open| *open = !*open)
+                                                >
+                                                    {move || if show_history.get() { "Hide History" } else { "View History" }}
+                                                </button>
+                                            </Show>
                                             <Show when=move || is_mod>
                                                 <button
                                                     type="button"
@@ -304,8 +446,93 @@ pub(super) fn IdeaDetailCard(
                             })}
                         </Suspense>
                     </div>
+                    {move || {
+                        asking_flag_reason.get().then(|| {
+                            view! {
+                                <FlagReasonPrompt
+                                    on_confirm=Callback::new(move |(reason, details): (FlagReason, Option<String>)| {
+                                        asking_flag_reason.set(false);
+                                        spawn_server_action_ok(
+                                            toggle_idea_flag_server(idea_id_val, Some(reason.as_str().to_string()), details),
+                                            move |is_flagged| flagged.set(is_flagged),
+                                        );
+                                    })
+                                    on_cancel=Callback::new(move |_| asking_flag_reason.set(false))
+                                />
+                            }
+                            .into_any()
+                        })
+                    }}
+                    {move || pending_stage.get().map(|to_stage| {
+                        let label = format!("Official response for the move to {to_stage} (optional, Markdown)");
+                        view! {
+                            <form
+                                class="stage-response-form"
+                                on:submit=move |ev| {
+                                    ev.prevent_default();
+                                    stage_updating.set(true);
+                                    stage_error.set(None);
+                                    let response = Some(stage_response.get()).filter(|text| !text.trim().is_empty());
+                                    spawn_server_action(
+                                        crate::routes::admin::update_idea_stage_action(
+                                            idea_id_val,
+                                            to_stage.clone(),
+                                            response,
+                                        ),
+                                        move |_| {
+                                            pending_stage.set(None);
+                                            stage_response.set(String::new());
+                                            stage_updating.set(false);
+                                            timeline.refetch();
+                                            idea_resource.refetch();
+                                        },
+                                        move |e| {
+                                            stage_error.set(Some(e.to_string()));
+                                            stage_updating.set(false);
+                                        },
+                                    );
+                                }
+                            >
+                                <label class="form-label" for="stage-response">{label}</label>
+                                <textarea
+                                    id="stage-response"
+                                    class="dialog-textarea"
+                                    maxlength=MAX_RESPONSE_CHARS
+                                    placeholder="Explain what happens next for this idea"
+                                    bind:value=stage_response
+                                />
+                                {move || stage_error.get().map(|error| view! { <p class="error" role="alert">{error}</p> })}
+                                <div class="dialog-footer">
+                                    <button
+                                        type="button"
+                                        class="btn btn-secondary"
+                                        on:click=move |_| {
+                                            pending_stage.set(None);
+                                            stage_error.set(None);
+                                        }
+                                    >
+                                        "Cancel"
+                                    </button>
+                                    <button type="submit" class="btn btn-primary" disabled=move || stage_updating.get()>
+                                        "Move idea"
+                                    </button>
+                                </div>
+                            </form>
+                        }
+                    })}
+                    <StageTimeline timeline=timeline stages=stages/>
+                    <Show when=move || show_history.get()>
+                        <IdeaHistoryPanel
+                            idea_id=idea_id_val
+                            title=idea_title_value.get_value()
+                            content=idea_content_value.get_value()
+                            tags=tags_str_value.get_value()
+                        />
+                    </Show>
                 </div>
             </div>
         </article>
     }
+    // Erased so the page's SSR future stays within the default stack in debug builds
+    .into_any()
 }
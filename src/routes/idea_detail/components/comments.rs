@@ -2,24 +2,65 @@ use leptos::ev::SubmitEvent;
 use leptos::prelude::*;
 
 use crate::auth::UserSession;
-use crate::models::CommentWithAuthor;
+use crate::models::{
+    thread_comments, CommentSort, CommentThread, CommentWithAuthor, EditWindow, FlagReason,
+};
 use crate::routes::async_helpers::{
-    spawn_server_action_refetch_resource, spawn_server_action_with_error,
+    spawn_server_action, spawn_server_action_refetch_resource, spawn_server_action_with_error,
+};
+use crate::routes::view_helpers::{confirm_action, edited_marker, format_relative_time};
+use crate::routes::FlagReasonPrompt;
+use crate::routes::ModerationReasonPrompt;
+
+use super::super::{
+    create_comment, delete_comment_mod, get_my_comment_flags, get_my_comment_votes,
+    toggle_comment_flag_server, toggle_comment_pin, toggle_comment_vote, update_comment_mod,
+    update_own_comment, withdraw_own_comment,
 };
-use crate::routes::view_helpers::{confirm_action, format_relative_time};
+use super::history::CommentHistoryPanel;
 
-use super::super::{create_comment, delete_comment_mod, toggle_comment_pin, update_comment_mod};
+/// Replies shown under a comment before the rest are collapsed
+const REPLIES_SHOWN: usize = 3;
 
 #[component]
 pub(super) fn CommentsSection(
     idea_id: i32,
     idea_comments_enabled: bool,
     comments_resource: Resource<Result<Vec<CommentWithAuthor>, ServerFnError>>,
+    comment_sort: RwSignal<CommentSort>,
     user_resource: Resource<Result<Option<UserSession>, ServerFnError>>,
+    edit_window: Resource<Result<EditWindow, ServerFnError>>,
 ) -> impl IntoView {
+    let my_flags = Resource::new(
+        move || user_resource.get().is_some(),
+        move |_| get_my_comment_flags(idea_id),
+    );
+    let my_votes = Resource::new(
+        move || user_resource.get().is_some(),
+        move |_| get_my_comment_votes(idea_id),
+    );
+
     view! {
         <div class="comments-section">
-            <h2 class="comments-heading">"Comments"</h2>
+            <div class="comments-header">
+                <h2 class="comments-heading">"Comments"</h2>
+                <div class="sort-tabs" role="group" aria-label="Sort comments">
+                    {CommentSort::ALL
+                        .into_iter()
+                        .map(|sort| view! {
+                            <button
+                                type="button"
+                                class="sort-tab btn btn-secondary"
+                                class:active=move || comment_sort.get() == sort
+                                aria-pressed=move || (comment_sort.get() == sort).to_string()
+                                on:click=move |_| comment_sort.set(sort)
+                            >
+                                {sort.label()}
+                            </button>
+                        })
+                        .collect_view()}
+                </div>
+            </div>
 
             <Show
                 when=move || idea_comments_enabled && matches!(user_resource.get(), Some(Ok(Some(_))))
@@ -54,17 +95,23 @@ pub(super) fn CommentsSection(
                                 .into_any()
                         }
                         Ok(comments) => {
+                            let threads = thread_comments(comments);
                             view! {
                                 <div class="comment-list">
                                     <For
-                                        each=move || comments.clone()
-                                        key=|cwa| cwa.comment.id
-                                        children=move |cwa: CommentWithAuthor| {
+                                        each=move || threads.clone()
+                                        key=|thread| thread.comment.comment.id
+                                        children=move |thread: CommentThread| {
                                             view! {
-                                                <CommentItem
-                                                    cwa=cwa
+                                                <CommentThreadItem
+                                                    thread=thread
+                                                    idea_id=idea_id
+                                                    can_reply=idea_comments_enabled
                                                     comments_resource=comments_resource
                                                     user_resource=user_resource
+                                                    edit_window=edit_window
+                                                    my_flags=my_flags
+                                                    my_votes=my_votes
                                                 />
                                             }
                                         }
@@ -84,23 +131,180 @@ pub(super) fn CommentsSection(
     }
 }
 
+/// A top-level comment and its replies, the later ones collapsed.
+#[component]
+fn CommentThreadItem(
+    thread: CommentThread,
+    idea_id: i32,
+    can_reply: bool,
+    comments_resource: Resource<Result<Vec<CommentWithAuthor>, ServerFnError>>,
+    user_resource: Resource<Result<Option<UserSession>, ServerFnError>>,
+    edit_window: Resource<Result<EditWindow, ServerFnError>>,
+    my_flags: Resource<Result<Vec<i32>, ServerFnError>>,
+    my_votes: Resource<Result<Vec<i32>, ServerFnError>>,
+) -> impl IntoView {
+    let expanded = RwSignal::new(false);
+    let on_replied = Callback::new(move |_| expanded.set(true));
+    let hidden_count = thread.replies.len().saturating_sub(REPLIES_SHOWN);
+    let item = move |cwa: CommentWithAuthor| {
+        if cwa.is_placeholder() {
+            view! { <DeletedComment comment_id=cwa.comment.id/> }.into_any()
+        } else {
+            view! {
+                <CommentItem
+                    cwa=cwa
+                    idea_id=idea_id
+                    can_reply=can_reply
+                    on_replied=on_replied
+                    comments_resource=comments_resource
+                    user_resource=user_resource
+                    edit_window=edit_window
+                    my_flags=my_flags
+                    my_votes=my_votes
+                />
+            }
+            .into_any()
+        }
+    };
+    let (shown, collapsed): (Vec<_>, Vec<_>) = thread
+        .replies
+        .into_iter()
+        .enumerate()
+        .partition(|(index, _)| *index < REPLIES_SHOWN);
+    let collapsed = StoredValue::new(collapsed);
+
+    view! {
+        <div class="comment-thread">
+            {item(thread.comment)}
+            {(!shown.is_empty()).then(|| view! {
+                <div class="comment-replies">
+                    {shown.into_iter().map(|(_, reply)| item(reply)).collect_view()}
+                    <Show
+                        when=move || expanded.get()
+                        fallback=move || {
+                            (hidden_count > 0).then(|| view! {
+                                <button
+                                    type="button"
+                                    class="btn-show-replies btn btn-secondary"
+                                    on:click=move |_| expanded.set(true)
+                                >
+                                    {if hidden_count == 1 {
+                                        "Show 1 more reply".to_string()
+                                    } else {
+                                        format!("Show {} more replies", hidden_count)
+                                    }}
+                                </button>
+                            })
+                        }
+                    >
+                        {collapsed.get_value().into_iter().map(|(_, reply)| item(reply)).collect_view()}
+                    </Show>
+                </div>
+            })}
+        </div>
+    }
+    .into_any()
+}
+
+/// Where a removed comment was, kept so its replies still make sense.
+#[component]
+fn DeletedComment(comment_id: i32) -> impl IntoView {
+    view! {
+        <div class="comment-item comment-deleted" id=format!("comment-{}", comment_id)>
+            <p class="comment-text">"[deleted]"</p>
+        </div>
+    }
+}
+
 #[component]
 fn CommentItem(
     cwa: CommentWithAuthor,
+    idea_id: i32,
+    /// Whether the idea takes new comments
+    can_reply: bool,
+    /// Called after the current user replies here
+    on_replied: Callback<()>,
     comments_resource: Resource<Result<Vec<CommentWithAuthor>, ServerFnError>>,
     user_resource: Resource<Result<Option<UserSession>, ServerFnError>>,
+    edit_window: Resource<Result<EditWindow, ServerFnError>>,
+    /// Comments on this idea the current user has flagged
+    my_flags: Resource<Result<Vec<i32>, ServerFnError>>,
+    /// Comments on this idea the current user marked helpful
+    my_votes: Resource<Result<Vec<i32>, ServerFnError>>,
 ) -> impl IntoView {
     let time = format_relative_time(&cwa.comment.created_at);
+    let edited = cwa.comment.edited_at.as_ref().map(edited_marker);
     let comment_id = cwa.comment.id;
+    let comment_author_id = cwa.comment.user_id;
+    let comment_created_at = cwa.comment.created_at;
+    let editing_as_author = RwSignal::new(false);
+    let helpful_count = RwSignal::new(cwa.comment.helpful_count);
+    // Authors may change their comment until the window passes or someone
+    // replies or marks it helpful
+    let author_window_open = move || {
+        let has_replies = comments_resource
+            .get()
+            .and_then(|result| result.ok())
+            .is_some_and(|comments| {
+                comments
+                    .iter()
+                    .any(|cwa| cwa.comment.parent_id == Some(comment_id) && !cwa.is_placeholder())
+            });
+        edit_window
+            .get()
+            .and_then(|result| result.ok())
+            .is_some_and(|window| {
+                window.is_open(
+                    comment_created_at,
+                    chrono::Utc::now(),
+                    helpful_count.get() > 0 || has_replies,
+                )
+            })
+    };
     let comment_is_pinned = cwa.comment.is_pinned;
     let is_editing = RwSignal::new(false);
     let edit_content = RwSignal::new(cwa.comment.content.clone());
     let edit_error = RwSignal::new(Option::<String>::None);
     let comment_content_value = StoredValue::new(cwa.comment.content.clone());
     let author_name = cwa.author_name;
+    let has_history = cwa.comment.edited_at.is_some();
+    let show_history = RwSignal::new(false);
+    let asking_delete_reason = RwSignal::new(false);
+    let asking_flag_reason = RwSignal::new(false);
+    let replying = RwSignal::new(false);
+    let is_voting = RwSignal::new(false);
+    let vote_error = RwSignal::new(Option::<String>::None);
+    let voted = move || {
+        my_votes
+            .get()
+            .and_then(|result| result.ok())
+            .is_some_and(|ids| ids.contains(&comment_id))
+    };
+    let handle_helpful = move |_| {
+        is_voting.set(true);
+        vote_error.set(None);
+        spawn_server_action(
+            toggle_comment_vote(comment_id),
+            move |now_voted| {
+                helpful_count.update(|count| *count += if now_voted { 1 } else { -1 });
+                my_votes.refetch();
+                is_voting.set(false);
+            },
+            move |e| {
+                vote_error.set(Some(e.to_string()));
+                is_voting.set(false);
+            },
+        );
+    };
+    let flagged = move || {
+        my_flags
+            .get()
+            .and_then(|result| result.ok())
+            .is_some_and(|ids| ids.contains(&comment_id))
+    };
 
     view! {
-        <div class="comment-item" class:pinned=comment_is_pinned>
+        <div class="comment-item" class:pinned=comment_is_pinned id=format!("comment-{}", comment_id)>
             <Show when=move || comment_is_pinned>
                 <span class="pinned-badge">"Pinned"</span>
             </Show>
@@ -116,14 +320,23 @@ fn CommentItem(
                         ev.prevent_default();
                         let content_value = edit_content.get();
                         edit_error.set(None);
-                        spawn_server_action_with_error(
-                            update_comment_mod(comment_id, content_value),
-                            move |_| {
-                                comments_resource.refetch();
-                                is_editing.set(false);
-                            },
-                            edit_error,
-                        );
+                        let on_saved = move |_| {
+                            comments_resource.refetch();
+                            is_editing.set(false);
+                        };
+                        if editing_as_author.get() {
+                            spawn_server_action_with_error(
+                                update_own_comment(comment_id, content_value),
+                                on_saved,
+                                edit_error,
+                            );
+                        } else {
+                            spawn_server_action_with_error(
+                                update_comment_mod(comment_id, content_value),
+                                on_saved,
+                                edit_error,
+                            );
+                        }
                     }
                 >
                     <Show when=move || edit_error.get().is_some()>
@@ -155,8 +368,45 @@ fn CommentItem(
                 </form>
             </Show>
             <div class="comment-meta">
+                <Suspense fallback=|| ()>
+                    {move || user_resource.get().map(|ur| {
+                        let can_vote = matches!(&ur, Ok(Some(user)) if user.id != comment_author_id);
+                        view! {
+                            <button
+                                type="button"
+                                class="btn-helpful btn btn-secondary"
+                                class:voted=voted
+                                aria-pressed=move || voted().to_string()
+                                disabled=move || !can_vote || is_voting.get()
+                                title=if can_vote { "Mark as helpful" } else { "Helpful votes" }
+                                on:click=handle_helpful
+                            >
+                                {move || format!("▲ Helpful {}", helpful_count.get())}
+                            </button>
+                        }
+                    })}
+                </Suspense>
                 <span class="comment-author">{author_name}</span>
                 <span class="comment-time">{time}</span>
+                {edited.map(|(label, title)| view! { <span class="edited-marker" title=title>{label}</span> })}
+                <Suspense fallback=|| ()>
+                    {move || user_resource.get().map(|ur| match ur {
+                        Ok(Some(user)) if has_history && (user.is_moderator() || user.id == comment_author_id) => {
+                            view! {
+                                <button
+                                    type="button"
+                                    class="btn-history btn btn-secondary"
+                                    aria-expanded=move || show_history.get().to_string()
+                                    on:click=move |_| show_history.update(|open| *open = !*open)
+                                >
+                                    {move || if show_history.get() { "Hide History" } else { "History" }}
+                                </button>
+                            }
+                                .into_any()
+                        }
+                        _ => ().into_any(),
+                    })}
+                </Suspense>
                 <Suspense fallback=|| ()>
                     {move || user_resource.get().map(|ur| match ur {
                         Ok(Some(user)) if user.is_moderator() => {
@@ -179,6 +429,32 @@ fn CommentItem(
                                         class="btn-edit btn btn-secondary"
                                         on:click=move |_| {
                                             edit_error.set(None);
+                                            editing_as_author.set(false);
+                                            is_editing.set(true);
+                                        }
+                                    >
+                                        "Edit"
+                                    </button>
+                                    <button
+                                        type="button"
+                                        class="btn-delete btn btn-danger"
+                                        on:click=move |_| asking_delete_reason.set(true)
+                                    >
+                                        "Delete"
+                                    </button>
+                                </Show>
+                            }
+                                .into_any()
+                        }
+                        Ok(Some(user)) if user.id == comment_author_id => {
+                            view! {
+                                <Show when=move || !is_editing.get() && author_window_open()>
+                                    <button
+                                        type="button"
+                                        class="btn-edit btn btn-secondary"
+                                        on:click=move |_| {
+                                            edit_error.set(None);
+                                            editing_as_author.set(true);
                                             is_editing.set(true);
                                         }
                                     >
@@ -188,15 +464,63 @@ fn CommentItem(
                                         type="button"
                                         class="btn-delete btn btn-danger"
                                         on:click=move |_| {
-                                            if confirm_action("Delete this comment? This cannot be undone.") {
+                                            if confirm_action("Withdraw this comment?") {
                                                 spawn_server_action_refetch_resource(
-                                                    delete_comment_mod(comment_id),
+                                                    withdraw_own_comment(comment_id),
                                                     comments_resource,
                                                 );
                                             }
                                         }
                                     >
-                                        "Delete"
+                                        "Withdraw"
+                                    </button>
+                                </Show>
+                            }
+                                .into_any()
+                        }
+                        _ => ().into_any(),
+                    })}
+                </Suspense>
+                <Suspense fallback=|| ()>
+                    {move || user_resource.get().map(|ur| match ur {
+                        Ok(Some(user)) if user.id != comment_author_id => {
+                            view! {
+                                <Show when=move || !is_editing.get()>
+                                    <button
+                                        type="button"
+                                        class="btn-flag btn btn-secondary"
+                                        on:click=move |_| {
+                                            if flagged() {
+                                                spawn_server_action_refetch_resource(
+                                                    toggle_comment_flag_server(comment_id, None, None),
+                                                    my_flags,
+                                                );
+                                            } else {
+                                                asking_flag_reason.set(true);
+                                            }
+                                        }
+                                    >
+                                        {move || if flagged() { "Unflag" } else { "Flag" }}
+                                    </button>
+                                </Show>
+                            }
+                                .into_any()
+                        }
+                        _ => ().into_any(),
+                    })}
+                </Suspense>
+                <Suspense fallback=|| ()>
+                    {move || user_resource.get().map(|ur| match ur {
+                        Ok(Some(_)) if can_reply => {
+                            view! {
+                                <Show when=move || !is_editing.get()>
+                                    <button
+                                        type="button"
+                                        class="btn-reply btn btn-secondary"
+                                        aria-expanded=move || replying.get().to_string()
+                                        on:click=move |_| replying.update(|open| *open = !*open)
+                                    >
+                                        "Reply"
                                     </button>
                                 </Show>
                             }
@@ -206,20 +530,77 @@ fn CommentItem(
                     })}
                 </Suspense>
             </div>
+            {move || vote_error.get().map(|error| view! { <p class="error" role="alert">{error}</p> })}
+            <Show when=move || replying.get()>
+                <div class="comment-reply-form">
+                    <CommentForm
+                        idea_id=idea_id
+                        comments_resource=comments_resource
+                        parent_id=comment_id
+                        on_posted=Callback::new(move |_| {
+                            replying.set(false);
+                            on_replied.run(());
+                        })
+                    />
+                </div>
+            </Show>
+            <Show when=move || asking_flag_reason.get()>
+                <FlagReasonPrompt
+                    on_confirm=Callback::new(move |(reason, details): (FlagReason, Option<String>)| {
+                        asking_flag_reason.set(false);
+                        spawn_server_action_refetch_resource(
+                            toggle_comment_flag_server(comment_id, Some(reason.as_str().to_string()), details),
+                            my_flags,
+                        );
+                    })
+                    on_cancel=Callback::new(move |_| asking_flag_reason.set(false))
+                />
+            </Show>
+            <Show when=move || asking_delete_reason.get()>
+                <ModerationReasonPrompt
+                    confirm_label="Delete Comment"
+                    on_confirm=Callback::new(move |reason| {
+                        asking_delete_reason.set(false);
+                        spawn_server_action_refetch_resource(
+                            delete_comment_mod(comment_id, reason),
+                            comments_resource,
+                        );
+                    })
+                    on_cancel=Callback::new(move |_| asking_delete_reason.set(false))
+                />
+            </Show>
+            <Show when=move || show_history.get()>
+                <CommentHistoryPanel comment_id=comment_id content=comment_content_value.get_value()/>
+            </Show>
         </div>
     }
+    // Erased so the page's SSR future stays within the default stack in debug builds
+    .into_any()
 }
 
 #[component]
 fn CommentForm(
     idea_id: i32,
     comments_resource: Resource<Result<Vec<CommentWithAuthor>, ServerFnError>>,
+    /// The comment being replied to
+    #[prop(optional)]
+    parent_id: Option<i32>,
+    /// Called after the comment is posted
+    #[prop(optional)]
+    on_posted: Option<Callback<()>>,
 ) -> impl IntoView {
     let content = RwSignal::new(String::new());
     let max_chars: usize = 500;
     let char_count = move || content.get().len();
     let is_warning = move || char_count() >= (max_chars as f64 * 0.9) as usize;
     let is_error = move || char_count() >= max_chars;
+    let error = RwSignal::new(Option::<String>::None);
+    // Set when the word filter held the comment for review
+    let held = RwSignal::new(false);
+    let textarea_id = match parent_id {
+        Some(parent_id) => format!("reply-content-{}", parent_id),
+        None => "comment-content".to_string(),
+    };
 
     let handle_submit = move |ev: SubmitEvent| {
         ev.prevent_default();
@@ -227,22 +608,47 @@ fn CommentForm(
         if content_value.trim().is_empty() || content_value.len() > max_chars {
             return;
         }
-        let content_clone = content_value.clone();
-        spawn_server_action_refetch_resource(
-            create_comment(idea_id, content_clone),
-            comments_resource,
+        error.set(None);
+        held.set(false);
+        spawn_server_action_with_error(
+            create_comment(idea_id, content_value, parent_id),
+            move |comment| {
+                held.set(comment.is_held);
+                content.set(String::new());
+                comments_resource.refetch();
+                // A held reply keeps its form open to show the notice
+                if let Some(on_posted) = on_posted.filter(|_| !comment.is_held) {
+                    on_posted.run(());
+                }
+            },
+            error,
         );
-        content.set(String::new());
     };
 
     view! {
         <form on:submit=handle_submit>
+            <Show when=move || error.get().is_some()>
+                <div class="dialog-alert dialog-alert-error" role="alert" aria-live="polite">
+                    {move || error.get().unwrap_or_default()}
+                </div>
+            </Show>
+            <Show when=move || held.get()>
+                <p class="moderation-notice" role="status">
+                    "Your comment will appear once a moderator has reviewed it."
+                </p>
+            </Show>
             <div class="form-group">
-                <label class="form-label" for="comment-content">"Your Comment"</label>
+                <label class="form-label" for=textarea_id.clone()>
+                    {if parent_id.is_some() { "Your Reply" } else { "Your Comment" }}
+                </label>
                 <textarea
-                    id="comment-content"
+                    id=textarea_id
                     class="dialog-textarea"
-                    placeholder="Add a comment (max 500 characters)…"
+                    placeholder=if parent_id.is_some() {
+                        "Write a reply (max 500 characters)…"
+                    } else {
+                        "Add a comment (max 500 characters)…"
+                    }
                     maxlength=max_chars
                     bind:value=content
                 />
@@ -256,7 +662,7 @@ fn CommentForm(
                     class="submit-btn btn btn-primary"
                     disabled=move || content.get().trim().is_empty()
                 >
-                    "Post Comment"
+                    {if parent_id.is_some() { "Post Reply" } else { "Post Comment" }}
                 </button>
             </div>
         </form>
@@ -0,0 +1,139 @@
+use leptos::prelude::*;
+
+use crate::routes::diff_helpers::{line_diff, DiffLine};
+use crate::routes::view_helpers::format_relative_time;
+
+use super::super::{get_comment_revisions, get_idea_revisions};
+
+/// Every edit made to an idea, newest first, as line diffs against the following version.
+#[component]
+pub(super) fn IdeaHistoryPanel(
+    idea_id: i32,
+    title: String,
+    content: String,
+    tags: String,
+) -> impl IntoView {
+    let revisions = Resource::new(move || idea_id, get_idea_revisions);
+    let current = StoredValue::new((title, content, tags));
+
+    view! {
+        <section class="history-panel" aria-label="Edit history">
+            <h3 class="history-title">"Edit history"</h3>
+            <Suspense fallback=move || view! { <p class="loading">"Loading history…"</p> }>
+                {move || revisions.get().map(|result| match result {
+                    Ok(revisions) if revisions.is_empty() => {
+                        view! { <p class="history-empty">"No earlier versions."</p> }.into_any()
+                    }
+                    Ok(revisions) => {
+                        let mut afters: Vec<(String, String, String)> = revisions
+                            .iter()
+                            .skip(1)
+                            .map(|r| (r.title.clone(), r.content.clone(), r.tags.clone()))
+                            .collect();
+                        afters.push(current.get_value());
+                        revisions
+                            .into_iter()
+                            .zip(afters)
+                            .rev()
+                            .map(|(before, (title, content, tags))| {
+                                view! {
+                                    <HistoryEntry editor_name=before.editor_name edited_at=before.created_at>
+                                        <TextDiff label="Title" old=before.title new=title/>
+                                        <TextDiff label="Description" old=before.content new=content/>
+                                        <TextDiff label="Tags" old=before.tags new=tags/>
+                                    </HistoryEntry>
+                                }
+                            })
+                            .collect_view()
+                            .into_any()
+                    }
+                    Err(e) => view! { <p class="error">{format!("Error loading history: {}", e)}</p> }.into_any(),
+                })}
+            </Suspense>
+        </section>
+    }
+}
+
+/// Every edit made to a comment, newest first, as line diffs against the following version.
+#[component]
+pub(super) fn CommentHistoryPanel(comment_id: i32, content: String) -> impl IntoView {
+    let revisions = Resource::new(move || comment_id, get_comment_revisions);
+    let current = StoredValue::new(content);
+
+    view! {
+        <section class="history-panel" aria-label="Comment edit history">
+            <Suspense fallback=move || view! { <p class="loading">"Loading history…"</p> }>
+                {move || revisions.get().map(|result| match result {
+                    Ok(revisions) if revisions.is_empty() => {
+                        view! { <p class="history-empty">"No earlier versions."</p> }.into_any()
+                    }
+                    Ok(revisions) => {
+                        let mut afters: Vec<String> =
+                            revisions.iter().skip(1).map(|r| r.content.clone()).collect();
+                        afters.push(current.get_value());
+                        revisions
+                            .into_iter()
+                            .zip(afters)
+                            .rev()
+                            .map(|(before, content)| {
+                                view! {
+                                    <HistoryEntry editor_name=before.editor_name edited_at=before.created_at>
+                                        <TextDiff label="Comment" old=before.content new=content/>
+                                    </HistoryEntry>
+                                }
+                            })
+                            .collect_view()
+                            .into_any()
+                    }
+                    Err(e) => view! { <p class="error">{format!("Error loading history: {}", e)}</p> }.into_any(),
+                })}
+            </Suspense>
+        </section>
+    }
+}
+
+#[component]
+fn HistoryEntry(
+    editor_name: Option<String>,
+    edited_at: chrono::DateTime<chrono::Utc>,
+    children: Children,
+) -> impl IntoView {
+    let editor = editor_name.unwrap_or_else(|| "Deleted user".to_string());
+    let when = format_relative_time(&edited_at);
+    let exact = edited_at.format("%Y-%m-%d %H:%M UTC").to_string();
+
+    view! {
+        <div class="history-entry">
+            <p class="history-meta">
+                {format!("Edited by {} ", editor)}
+                <span title=exact>{when}</span>
+            </p>
+            {children()}
+        </div>
+    }
+}
+
+/// Line diff of one field; renders nothing when the field did not change.
+#[component]
+fn TextDiff(label: &'static str, old: String, new: String) -> impl IntoView {
+    (old != new).then(|| {
+        let lines = line_diff(&old, &new)
+            .into_iter()
+            .map(|line| {
+                let (class, marker, text) = match line {
+                    DiffLine::Unchanged(text) => ("diff-line", ' ', text),
+                    DiffLine::Added(text) => ("diff-line diff-added", '+', text),
+                    DiffLine::Removed(text) => ("diff-line diff-removed", '-', text),
+                };
+                view! { <span class=class>{format!("{} {}\n", marker, text)}</span> }
+            })
+            .collect_view();
+
+        view! {
+            <div class="history-field">
+                <span class="history-field-label">{label}</span>
+                <pre class="history-diff">{lines}</pre>
+            </div>
+        }
+    })
+}
@@ -0,0 +1,69 @@
+use leptos::prelude::*;
+
+use crate::models::{find_stage, Stage, StageChange};
+use crate::routes::view_helpers::{format_relative_time, stage_badge_color};
+
+/// An idea's moves between stages, newest first, with any official responses
+/// staff wrote along the way. Renders nothing until the idea has moved.
+#[component]
+pub(super) fn StageTimeline(
+    timeline: Resource<Result<Vec<StageChange>, ServerFnError>>,
+    stages: Resource<Result<Vec<Stage>, ServerFnError>>,
+) -> impl IntoView {
+    view! {
+        <Transition fallback=|| ()>
+            {move || timeline.get().map(|result| match result {
+                Ok(changes) if changes.is_empty() => ().into_any(),
+                Ok(changes) => {
+                    let stages = stages.get().and_then(Result::ok).unwrap_or_default();
+                    view! {
+                        <section class="stage-timeline" aria-label="Stage timeline">
+                            <h3 class="stage-timeline-title">"Stage timeline"</h3>
+                            <ol class="stage-timeline-list">
+                                {changes
+                                    .into_iter()
+                                    .rev()
+                                    .map(|change| timeline_entry(change, &stages))
+                                    .collect_view()}
+                            </ol>
+                        </section>
+                    }
+                        .into_any()
+                }
+                Err(e) => view! { <p class="error">{format!("Error loading stage timeline: {}", e)}</p> }.into_any(),
+            })}
+        </Transition>
+    }
+}
+
+fn timeline_entry(change: StageChange, stages: &[Stage]) -> impl IntoView {
+    let badge = |name: &str| {
+        let color = find_stage(stages, name).map_or("", |found| found.color.as_str());
+        format!("stage-badge stage-{}", stage_badge_color(color))
+    };
+    let from_class = badge(&change.from_stage);
+    let to_class = badge(&change.to_stage);
+    let actor = change
+        .actor_name
+        .unwrap_or_else(|| "Former moderator".to_string());
+    let when = format_relative_time(&change.created_at);
+    let exact = change.created_at.format("%Y-%m-%d %H:%M UTC").to_string();
+
+    view! {
+        <li class="stage-timeline-entry">
+            <p class="stage-timeline-meta">
+                <span class=from_class>{change.from_stage}</span>
+                <span class="stage-timeline-arrow" aria-label="moved to">" → "</span>
+                <span class=to_class>{change.to_stage}</span>
+                {format!(" by {} ", actor)}
+                <span title=exact>{when}</span>
+            </p>
+            {change.response.map(|response| view! {
+                <div class="official-response">
+                    <p class="official-response-label">"Official response"</p>
+                    <div class="official-response-body" inner_html=crate::markdown::render(&response)></div>
+                </div>
+            })}
+        </li>
+    }
+}
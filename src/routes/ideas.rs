@@ -1,11 +1,12 @@
-use crate::models::{Idea, IdeaWithAuthor};
+use crate::models::{
+    Category, Idea, IdeaCursor, IdeaPage, IdeaSearch, SimilarIdea, Stage, Tag, TagWithCount,
+};
 #[cfg(feature = "ssr")]
 use crate::routes::error_helpers::server_fn_error_with_log;
 #[cfg(feature = "ssr")]
-use crate::routes::validation_helpers::validate_idea_title_and_content;
+use crate::routes::validation_helpers::{validate_idea_tags, validate_idea_title_and_content};
 use leptos::prelude::*;
-use std::cmp::Ordering;
-use std::collections::HashMap;
+use leptos_router::hooks::use_params_map;
 
 mod components;
 use components::IdeasBoard;
@@ -19,29 +20,230 @@ pub async fn create_idea_auth(
     title: String,
     content: String,
     tags: String,
+    category_id: i32,
 ) -> Result<Idea, ServerFnError> {
     use crate::auth::require_auth;
+    use crate::models::{PreModerationPolicy, User};
+    use crate::profanity;
+    use crate::routes::admin::{apply_word_filter_match, notify_moderators};
     let user = require_auth().await?;
 
-    validate_idea_title_and_content(&title, &content)?;
+    let filter_match = validate_idea_title_and_content(&title, &content, &profanity::active())?;
+    let tags = validate_idea_tags(&tags)?;
+    reject_retired_tags(&tags, None).await?;
+    validate_category(category_id, None).await?;
 
-    Idea::create(
+    let create_error =
+        |e| server_fn_error_with_log("Failed to create idea", e, "Failed to create idea");
+    let pending = match User::get_author_trust(user.id)
+        .await
+        .map_err(create_error)?
+    {
+        Some(author) => {
+            PreModerationPolicy::from_env().requires_approval(&author, chrono::Utc::now())
+        }
+        None => return Err(ServerFnError::new("Failed to create idea")),
+    };
+    // New ideas start in the first open stage of the workflow
+    let stage = Stage::first_open()
+        .await
+        .map_err(create_error)?
+        .ok_or_else(|| {
+            ServerFnError::new("New ideas cannot be posted until an admin opens a workflow stage")
+        })?;
+
+    let idea = Idea::create(
         user.id,
         title.trim().to_string(),
         content.trim().to_string(),
-        tags.trim().to_string(),
+        &tags,
+        category_id,
+        &stage,
+        pending,
     )
     .await
-    .map_err(|e| server_fn_error_with_log("Failed to create idea", e, "Failed to create idea"))
+    .map_err(create_error)?;
+
+    if let Some(found) = &filter_match {
+        apply_word_filter_match(&user, "idea", idea.id, found).await;
+    }
+    if pending {
+        notify_moderators(
+            Some(user.id),
+            format!("New idea \"{}\" is waiting for approval", idea.title),
+        )
+        .await;
+    }
+
+    Ok(idea)
 }
 
+/// Ideas per page of the board
+#[cfg(feature = "ssr")]
+const IDEAS_PAGE_SIZE: i64 = 20;
+
+/// A page of public ideas for the board, filtered and ranked by `search`.
+/// Pass the previous page's `next` cursor as `after` to continue.
 #[server]
-pub async fn get_ideas_with_authors() -> Result<Vec<IdeaWithAuthor>, ServerFnError> {
-    Idea::get_all()
+pub async fn list_ideas(
+    search: IdeaSearch,
+    after: Option<IdeaCursor>,
+) -> Result<IdeaPage, ServerFnError> {
+    use crate::auth::get_user;
+
+    let viewer_id = if search.voted_by_me {
+        get_user().await?.map(|user| user.id)
+    } else {
+        None
+    };
+    search
+        .run(viewer_id, after.as_ref(), IDEAS_PAGE_SIZE)
         .await
         .map_err(|e| server_fn_error_with_log("Failed to fetch ideas", e, "Failed to fetch ideas"))
 }
 
+/// Drafts with a shorter title are not checked for duplicates
+pub(super) const MIN_SIMILAR_TITLE_CHARS: usize = 5;
+/// How many similar ideas the submission dialog lists
+#[cfg(feature = "ssr")]
+const SIMILAR_IDEAS_LIMIT: i64 = 5;
+/// Longest draft title and body compared, in characters; a draft can never
+/// be longer than this and still be posted
+#[cfg(feature = "ssr")]
+const MAX_SIMILAR_TITLE_CHARS: usize = 100;
+#[cfg(feature = "ssr")]
+const MAX_SIMILAR_BODY_CHARS: usize = 1000;
+
+/// Published ideas that look like the draft being written, so the author can
+/// vote for an existing one instead of posting a duplicate.
+#[server]
+pub async fn find_similar_ideas(
+    title: String,
+    content: String,
+    tags: String,
+) -> Result<Vec<SimilarIdea>, ServerFnError> {
+    use crate::auth::require_auth;
+
+    require_auth().await?;
+    let title: String = title.trim().chars().take(MAX_SIMILAR_TITLE_CHARS).collect();
+    if title.chars().count() < MIN_SIMILAR_TITLE_CHARS {
+        return Ok(Vec::new());
+    }
+    let body: String = format!("{} {}", content.trim(), tags.trim())
+        .chars()
+        .take(MAX_SIMILAR_BODY_CHARS)
+        .collect();
+    Idea::find_similar(&title, &body, SIMILAR_IDEAS_LIMIT)
+        .await
+        .map_err(|e| {
+            server_fn_error_with_log(
+                "Failed to find similar ideas",
+                e,
+                "Failed to find similar ideas",
+            )
+        })
+}
+
+/// How many tags autocomplete offers
+#[cfg(feature = "ssr")]
+const TAG_SUGGESTIONS_LIMIT: i64 = 8;
+
+/// Tags in use whose names start like `prefix`, for autocomplete.
+#[server]
+pub async fn suggest_tags(prefix: String) -> Result<Vec<TagWithCount>, ServerFnError> {
+    use crate::models::slugify;
+
+    if slugify(&prefix).is_empty() {
+        return Ok(Vec::new());
+    }
+    Tag::suggest(&prefix, TAG_SUGGESTIONS_LIMIT)
+        .await
+        .map_err(|e| {
+            server_fn_error_with_log("Failed to suggest tags", e, "Failed to suggest tags")
+        })
+}
+
+/// The tag a `/tags/:slug` page lists ideas for.
+#[server]
+pub async fn get_tag(slug: String) -> Result<Option<Tag>, ServerFnError> {
+    Tag::get_by_slug(&slug)
+        .await
+        .map_err(|e| server_fn_error_with_log("Failed to fetch tag", e, "Failed to fetch tag"))
+}
+
+/// Every workflow stage with its transitions, for filters, badges and the
+/// moderator's stage picker.
+#[server]
+pub async fn list_stages() -> Result<Vec<Stage>, ServerFnError> {
+    Stage::get_all().await.map_err(|e| {
+        server_fn_error_with_log("Failed to fetch stages", e, "Failed to fetch stages")
+    })
+}
+
+/// Every category, for the board's filters and labels. Archived ones are
+/// included so their ideas can still be labelled.
+#[server]
+pub async fn list_categories() -> Result<Vec<Category>, ServerFnError> {
+    Category::get_all().await.map_err(|e| {
+        server_fn_error_with_log(
+            "Failed to fetch categories",
+            e,
+            "Failed to fetch categories",
+        )
+    })
+}
+
+/// Check that ideas can be filed under `category_id`: it must exist and not
+/// be archived, unless the idea being edited is already in it.
+#[cfg(feature = "ssr")]
+pub(crate) async fn validate_category(
+    category_id: i32,
+    idea_id: Option<i32>,
+) -> Result<(), ServerFnError> {
+    let fetch_error =
+        |e| server_fn_error_with_log("Failed to check category", e, "Failed to check category");
+    let category = Category::get_by_id(category_id)
+        .await
+        .map_err(fetch_error)?
+        .ok_or_else(|| ServerFnError::new("Choose a category for your idea"))?;
+    if !category.is_archived {
+        return Ok(());
+    }
+    let current = match idea_id {
+        Some(idea_id) => Category::of_idea(idea_id).await.map_err(fetch_error)?,
+        None => None,
+    };
+    if current == Some(category_id) {
+        Ok(())
+    } else {
+        Err(ServerFnError::new(format!(
+            "The {} category is no longer taking new ideas",
+            category.name
+        )))
+    }
+}
+
+/// Refuse retired tags, unless the idea being edited already has them.
+#[cfg(feature = "ssr")]
+pub(crate) async fn reject_retired_tags(
+    tags: &[String],
+    idea_id: Option<i32>,
+) -> Result<(), ServerFnError> {
+    let retired = Tag::retired_among(tags, idea_id)
+        .await
+        .map_err(|e| server_fn_error_with_log("Failed to check tags", e, "Failed to check tags"))?;
+    match retired.as_slice() {
+        [] => Ok(()),
+        [name] => Err(ServerFnError::new(format!(
+            "The tag \"{name}\" has been retired"
+        ))),
+        names => Err(ServerFnError::new(format!(
+            "These tags have been retired: {}",
+            names.join(", ")
+        ))),
+    }
+}
+
 #[server]
 pub async fn toggle_vote(idea_id: i32) -> Result<bool, ServerFnError> {
     use crate::auth::require_auth;
@@ -53,6 +255,19 @@ pub async fn toggle_vote(idea_id: i32) -> Result<bool, ServerFnError> {
         .map_err(|e| server_fn_error_with_log("Failed to toggle vote", e, "Failed to toggle vote"))
 }
 
+/// Vote for an idea without toggling, for "vote for this instead". Returns
+/// whether a new vote was added; `false` means the user had already voted.
+#[server]
+pub async fn add_vote(idea_id: i32) -> Result<bool, ServerFnError> {
+    use crate::auth::require_auth;
+    use crate::models::Vote;
+
+    let user = require_auth().await?;
+    Vote::add(user.id, idea_id)
+        .await
+        .map_err(|e| server_fn_error_with_log("Failed to add vote", e, "Failed to add vote"))
+}
+
 #[server]
 pub async fn check_user_votes() -> Result<Vec<i32>, ServerFnError> {
     use crate::auth::require_auth;
@@ -75,21 +290,6 @@ pub async fn get_idea_statistics() -> Result<(i64, i64), ServerFnError> {
     })
 }
 
-#[server]
-pub async fn get_comment_counts() -> Result<HashMap<i32, i64>, ServerFnError> {
-    use crate::models::Comment;
-    Comment::count_all_grouped()
-        .await
-        .map(|counts| counts.into_iter().collect())
-        .map_err(|e| {
-            server_fn_error_with_log(
-                "Failed to fetch comment counts",
-                e,
-                "Failed to fetch comment counts",
-            )
-        })
-}
-
 #[server]
 pub async fn check_idea_flag_server(idea_id: i32) -> Result<bool, ServerFnError> {
     use crate::auth::require_auth;
@@ -101,48 +301,129 @@ pub async fn check_idea_flag_server(idea_id: i32) -> Result<bool, ServerFnError>
         .map_err(|e| ServerFnError::new(format!("Failed to check idea flag: {}", e)))
 }
 
+/// Flag an idea, or remove the current user's flag if there is one. A new
+/// flag needs a `reason` (see `FlagReason`).
 #[server]
-pub async fn toggle_idea_flag_server(idea_id: i32) -> Result<bool, ServerFnError> {
+pub async fn toggle_idea_flag_server(
+    idea_id: i32,
+    reason: Option<String>,
+    details: Option<String>,
+) -> Result<bool, ServerFnError> {
     use crate::auth::require_auth;
-    use crate::models::Flag;
 
     let user = require_auth().await?;
-    Flag::toggle_user_flag(user.id, "idea", idea_id)
+    toggle_flag(user.id, "idea", idea_id, reason, details).await
+}
+
+/// Shared by the idea and comment flag server functions: removes the user's
+/// flag if present, otherwise validates the reason and adds one. Returns the
+/// resulting flagged state.
+#[cfg(feature = "ssr")]
+pub(crate) async fn toggle_flag(
+    user_id: i32,
+    target_type: &str,
+    target_id: i32,
+    reason: Option<String>,
+    details: Option<String>,
+) -> Result<bool, ServerFnError> {
+    use crate::models::Flag;
+    use crate::routes::validation_helpers::validate_flag_reason;
+
+    let removed = Flag::remove_user_flag(user_id, target_type, target_id)
         .await
-        .map_err(|e| ServerFnError::new(format!("Failed to toggle idea flag: {}", e)))
+        .map_err(|e| ServerFnError::new(format!("Failed to toggle {target_type} flag: {e}")))?;
+    if removed {
+        return Ok(false);
+    }
+
+    let (reason, details) = validate_flag_reason(reason.as_deref(), details.as_deref())?;
+    let visible = flag_target_visible(target_type, target_id)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to toggle {target_type} flag: {e}")))?;
+    if !visible {
+        return Err(ServerFnError::new(format!(
+            "This {target_type} is not available to flag"
+        )));
+    }
+    Flag::add_user_flag(user_id, target_type, target_id, reason, details)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to toggle {target_type} flag: {e}")))?;
+
+    if let Err(e) = auto_hide_if_flagged(target_type, target_id).await {
+        tracing::error!("Failed to check auto-hide for {target_type} {target_id}: {e}");
+    }
+    Ok(true)
+}
+
+/// Whether the idea or comment exists and can be seen on the board, so
+/// flags are only raised against content users were actually shown.
+#[cfg(feature = "ssr")]
+async fn flag_target_visible(target_type: &str, target_id: i32) -> Result<bool, sqlx::Error> {
+    use crate::models::Comment;
+
+    let idea_id = if target_type == "idea" {
+        target_id
+    } else {
+        match Comment::get_by_id(target_id).await? {
+            Some(comment) if !comment.is_held => comment.idea_id,
+            _ => return Ok(false),
+        }
+    };
+    Ok(Idea::get_by_id(idea_id).await?.is_some())
+}
+
+/// Hide an item pending review once its weighted flags cross the
+/// [`AutoHidePolicy`](crate::models::AutoHidePolicy), and tell the moderators.
+#[cfg(feature = "ssr")]
+async fn auto_hide_if_flagged(target_type: &str, target_id: i32) -> Result<(), sqlx::Error> {
+    use crate::models::{flag_weight, AutoHidePolicy, Comment, Flag, Idea, Vote};
+
+    let weight: f64 = Flag::get_flagger_dismissals(target_type, target_id)
+        .await?
+        .into_iter()
+        .map(flag_weight)
+        .sum();
+    let recent_voters = if target_type == "idea" {
+        let since = AutoHidePolicy::recent_votes_since(chrono::Utc::now());
+        Vote::count_recent_voters(target_id, since).await?
+    } else {
+        0
+    };
+    if !AutoHidePolicy::from_env().should_hide(weight, recent_voters) {
+        return Ok(());
+    }
+
+    let message = if target_type == "idea" {
+        match Idea::auto_hide(target_id).await? {
+            Some(title) => {
+                format!("The idea \"{title}\" was hidden after repeated flags and needs review")
+            }
+            None => return Ok(()),
+        }
+    } else if Comment::hold(target_id).await? {
+        "A comment was held after repeated flags and needs review".to_string()
+    } else {
+        return Ok(());
+    };
+
+    crate::routes::admin::notify_moderators(None, message).await;
+    Ok(())
 }
 
 // ============================================================================
 // COMPONENTS
 // ============================================================================
 
-#[derive(Clone, Copy, PartialEq)]
-pub(super) enum SortMode {
-    Popular,
-    Recent,
-}
-
 /// Main Idea Board page
 #[component]
 pub fn IdeasPage() -> impl IntoView {
     view! { <IdeasBoard/> }
 }
 
-fn compare_pinned_first(a: &IdeaWithAuthor, b: &IdeaWithAuthor) -> Ordering {
-    // Keep pinned ideas ahead of unpinned ideas in every sort mode.
-    b.idea
-        .is_pinned()
-        .cmp(&a.idea.is_pinned())
-        .then_with(|| b.idea.pinned_at.cmp(&a.idea.pinned_at))
-}
-
-pub(super) fn sort_ideas(ideas: &mut [IdeaWithAuthor], mode: SortMode) {
-    ideas.sort_by(|a, b| match mode {
-        SortMode::Popular => compare_pinned_first(a, b)
-            .then_with(|| b.idea.vote_count.cmp(&a.idea.vote_count))
-            .then_with(|| b.idea.created_at.cmp(&a.idea.created_at)),
-        SortMode::Recent => {
-            compare_pinned_first(a, b).then_with(|| b.idea.created_at.cmp(&a.idea.created_at))
-        }
-    });
+/// The board limited to one tag, at `/tags/:slug`
+#[component]
+pub fn TagPage() -> impl IntoView {
+    let params = use_params_map();
+    let slug = Signal::derive(move || params.read().get("slug").unwrap_or_default());
+    view! { <IdeasBoard tag=slug/> }
 }
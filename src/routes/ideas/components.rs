@@ -1,5 +1,6 @@
 mod board;
 mod card;
 mod submission;
+mod tag_input;
 
 pub(super) use board::IdeasBoard;
@@ -1,30 +1,126 @@
 use leptos::prelude::*;
 use leptos_meta::Title;
 use leptos_router::components::A;
+use leptos_router::hooks::{use_navigate, use_query_map};
+use leptos_router::params::ParamsMap;
+use leptos_router::NavigateOptions;
 
 use crate::auth::get_user;
-use crate::models::IdeaWithAuthor;
+use crate::models::{
+    Category, IdeaCursor, IdeaPage, IdeaSearch, IdeaSearchResult, IdeaSort, Stage,
+};
+use crate::routes::async_helpers::spawn_server_action;
+use crate::routes::paths;
+use crate::routes::view_helpers::is_user_logged_in;
 
 use super::super::{
-    check_user_votes, get_comment_counts, get_idea_statistics, get_ideas_with_authors, sort_ideas,
-    SortMode,
+    check_user_votes, get_idea_statistics, get_tag, list_categories, list_ideas, list_stages,
 };
 use super::card::IdeaCard;
 use super::submission::IdeaSubmissionDialog;
 
 #[component]
-pub fn IdeasBoard() -> impl IntoView {
+pub fn IdeasBoard(
+    /// Slug of the tag whose page this is; the search is limited to it
+    #[prop(optional, into)]
+    tag: Option<Signal<String>>,
+) -> impl IntoView {
     let auth_refresh = expect_context::<crate::auth::AuthRefresh>().0;
     let user_resource = Resource::new(
         move || auth_refresh.get(),
         move |_| async move { get_user().await },
     );
-    let ideas_resource = Resource::new(|| (), |_| async { get_ideas_with_authors().await });
+    let query = use_query_map();
+    let search = Memo::new(move |_| {
+        let query = query.read();
+        let mut search = IdeaSearch::from_query(|key| query.get(key));
+        if let Some(tag) = tag {
+            search.tag = Some(tag.get());
+        }
+        search
+    });
+    let tag_resource = Resource::new(
+        move || tag.map(|tag| tag.get()),
+        |slug| async move {
+            match slug {
+                Some(slug) => get_tag(slug).await,
+                None => Ok(None),
+            }
+        },
+    );
+    // The first page is rendered on the server; later pages are added below
+    // it with "Load more"
+    let ideas_resource = Resource::new(
+        move || search.get(),
+        |search| async move { list_ideas(search, None).await },
+    );
+    let more_ideas = RwSignal::new(Vec::<IdeaSearchResult>::new());
+    // Where the page after `more_ideas` starts, once one has been loaded
+    let more_next = RwSignal::new(Option::<Option<IdeaCursor>>::None);
+    let is_loading_more = RwSignal::new(false);
+    let load_more_failed = RwSignal::new(false);
+    Effect::watch(
+        move || search.get(),
+        move |_, _, _| {
+            more_ideas.set(Vec::new());
+            more_next.set(None);
+            load_more_failed.set(false);
+        },
+        false,
+    );
+    let next_cursor = move || match more_next.get() {
+        Some(next) => next,
+        None => ideas_resource
+            .get()
+            .and_then(Result::ok)
+            .and_then(|page| page.next),
+    };
+    let handle_load_more = move |_| {
+        let Some(after) = next_cursor() else {
+            return;
+        };
+        is_loading_more.set(true);
+        load_more_failed.set(false);
+        spawn_server_action(
+            list_ideas(search.get_untracked(), Some(after)),
+            move |page: IdeaPage| {
+                more_ideas.update(|ideas| ideas.extend(page.ideas));
+                more_next.set(Some(page.next));
+                is_loading_more.set(false);
+            },
+            move |_| {
+                load_more_failed.set(true);
+                is_loading_more.set(false);
+            },
+        );
+    };
     let stats_resource = Resource::new(|| (), |_| async { get_idea_statistics().await });
-    let comment_counts_resource = Resource::new(|| (), |_| async { get_comment_counts().await });
+    let categories = Resource::new(|| (), |_| async { list_categories().await });
+    let stages = Resource::new(|| (), |_| async { list_stages().await });
     let voted_ideas = RwSignal::new(Vec::<i32>::new());
-    let sort_mode = RwSignal::new(SortMode::Popular);
-    let search_query = RwSignal::new(String::new());
+
+    // Searches live in the URL so they can be shared and bookmarked
+    let navigate = use_navigate();
+    let apply_search = Callback::new(move |next: IdeaSearch| {
+        let page_tag = tag.map(|tag| tag.get_untracked());
+        navigate(
+            &search_url(&next, page_tag.as_deref()),
+            NavigateOptions::default(),
+        );
+    });
+    let update_search = move |change: &dyn Fn(&mut IdeaSearch)| {
+        let mut next = search.get_untracked();
+        change(&mut next);
+        apply_search.run(next);
+    };
+    // Without search text there is nothing to rank by relevance
+    let active_sort = move || {
+        let search = search.get();
+        match search.sort {
+            IdeaSort::Relevance if search.text.is_empty() => IdeaSort::Popular,
+            sort => sort,
+        }
+    };
 
     // Load user's voted ideas
     Effect::new(move |_| {
@@ -77,71 +173,111 @@ pub fn IdeasBoard() -> impl IntoView {
             <div class="container page">
                 <div class="spark-layout">
                     <div class="main-column">
+                        {tag.map(|_| view! {
+                            <Suspense fallback=|| ()>
+                                {move || tag_resource.get().map(|found| match found {
+                                    Ok(Some(found)) => view! {
+                                        <Title text=format!("Ideas tagged {} - UAB IT Idea Board", found.name)/>
+                                        <div class="tag-page-header">
+                                            <h2>"Ideas tagged " <span class="detail-tag">{found.name}</span></h2>
+                                            {found.is_retired.then(|| view! {
+                                                <p class="tag-page-note">"This tag has been retired and cannot be added to new ideas."</p>
+                                            })}
+                                        </div>
+                                    }.into_any(),
+                                    Ok(None) => view! {
+                                        <div class="tag-page-header">
+                                            <h2>"Tag not found"</h2>
+                                            <A href=paths::HOME>"Back to all ideas"</A>
+                                        </div>
+                                    }.into_any(),
+                                    Err(_) => ().into_any(),
+                                })}
+                            </Suspense>
+                        })}
                         <div class="sort-tabs">
-                            <button
-                                class="sort-tab btn btn-secondary"
-                                class:active=move || sort_mode.get() == SortMode::Popular
-                                on:click=move |_| sort_mode.set(SortMode::Popular)
-                            >
-                                "Popular"
-                            </button>
-                            <button
-                                class="sort-tab btn btn-secondary"
-                                class:active=move || sort_mode.get() == SortMode::Recent
-                                on:click=move |_| sort_mode.set(SortMode::Recent)
-                            >
-                                "Recent"
-                            </button>
+                            {IdeaSort::ALL
+                                .into_iter()
+                                .map(|sort| view! {
+                                    <button
+                                        class="sort-tab btn btn-secondary"
+                                        class:active=move || active_sort() == sort
+                                        style:display=move || {
+                                            if sort == IdeaSort::Relevance && search.get().text.is_empty() { "none" } else { "" }
+                                        }
+                                        on:click=move |_| update_search(&|next| next.sort = sort)
+                                    >
+                                        {sort.label()}
+                                    </button>
+                                })
+                                .collect_view()}
                         </div>
 
                         <Suspense fallback=move || view! { <p class="loading">"Loading ideas…"</p> }>
                             {move || {
-                                ideas_resource.get().map(|ideas| {
-                                    match ideas {
-                                        Ok(mut ideas_list) => {
-                                            sort_ideas(&mut ideas_list, sort_mode.get());
-                                            let query = search_query.get().to_lowercase();
-                                            if !query.is_empty() {
-                                                ideas_list.retain(|iwa| {
-                                                    iwa.idea.title.to_lowercase().contains(&query)
-                                                        || iwa.idea.content.to_lowercase().contains(&query)
-                                                        || iwa.idea.tags.to_lowercase().contains(&query)
-                                                        || iwa.author_name.to_lowercase().contains(&query)
-                                                });
-                                            }
-                                            if ideas_list.is_empty() {
-                                                view! {
-                                                    <div class="empty-state">
-                                                        <p>{move || if search_query.get().is_empty() { "No ideas yet. Be the first to submit one!" } else { "No ideas match your search." }}</p>
-                                                    </div>
-                                                }.into_any()
-                                            } else {
-                                                let ranked: Vec<(usize, IdeaWithAuthor)> = ideas_list
+                                ideas_resource.get().map(|page| {
+                                    match page {
+                                        Ok(page) if page.ideas.is_empty() => view! {
+                                            <div class="empty-state">
+                                                <p>{move || if search.get().is_filtered() { "No ideas match your search." } else { "No ideas yet. Be the first to submit one!" }}</p>
+                                            </div>
+                                        }.into_any(),
+                                        Ok(page) => {
+                                            let first_page = page.ideas;
+                                            let ranked = move || {
+                                                let mut ideas = first_page.clone();
+                                                // Votes since the first page loaded can move an
+                                                // idea onto a later page too
+                                                for result in more_ideas.get() {
+                                                    if !ideas.iter().any(|shown| shown.idea.idea.id == result.idea.idea.id) {
+                                                        ideas.push(result);
+                                                    }
+                                                }
+                                                ideas
                                                     .into_iter()
                                                     .enumerate()
-                                                    .map(|(i, idea)| (i + 1, idea))
-                                                    .collect();
-                                                view! {
-                                                    <div class="spark-list">
-                                                        <For
-                                                            each=move || ranked.clone()
-                                                            key=|(_, iwa)| iwa.idea.id
-                                                            children=move |(rank, iwa): (usize, IdeaWithAuthor)| {
-                                                                view! {
-                                                                    <IdeaCard
-                                                                        idea_with_author=iwa
-                                                                        rank=rank
-                                                                        user_resource=user_resource
-                                                                        voted_ideas=voted_ideas
-                                                                        comment_counts_resource=comment_counts_resource
-                                                                        ideas_resource=ideas_resource
-                                                                    />
-                                                                }
+                                                    .map(|(i, result)| (i + 1, result))
+                                                    .collect::<Vec<_>>()
+                                            };
+                                            view! {
+                                                <div class="spark-list">
+                                                    <For
+                                                        each=ranked
+                                                        key=|(rank, result)| (*rank, result.idea.idea.id)
+                                                        children=move |(rank, result): (usize, IdeaSearchResult)| {
+                                                            view! {
+                                                                <IdeaCard
+                                                                    idea_with_author=result.idea
+                                                                    snippet=result.snippet
+                                                                    comment_count=result.comment_count
+                                                                    category_name=result.category_name
+                                                                    stage_color=result.stage_color
+                                                                    latest_response=result.latest_response
+                                                                    rank=rank
+                                                                    user_resource=user_resource
+                                                                    voted_ideas=voted_ideas
+                                                                    ideas_resource=ideas_resource
+                                                                />
                                                             }
-                                                        />
+                                                        }
+                                                    />
+                                                </div>
+                                                <Show when=move || next_cursor().is_some()>
+                                                    <div class="load-more">
+                                                        <button
+                                                            type="button"
+                                                            class="btn btn-secondary"
+                                                            disabled=move || is_loading_more.get()
+                                                            on:click=handle_load_more
+                                                        >
+                                                            {move || if is_loading_more.get() { "Loading…" } else { "Load more" }}
+                                                        </button>
                                                     </div>
-                                                }.into_any()
-                                            }
+                                                </Show>
+                                                <Show when=move || load_more_failed.get()>
+                                                    <p class="error" role="alert">"Failed to load more ideas. Please try again."</p>
+                                                </Show>
+                                            }.into_any()
                                         }
                                         Err(_) => view! {
                                             <div class="error-state">
@@ -160,16 +296,13 @@ pub fn IdeasBoard() -> impl IntoView {
                                 <h3 class="sidebar-card-title">"Search Ideas"</h3>
                             </header>
                             <div class="sidebar-card-body">
-                                <label for="idea-search" class="sr-only">"Search Ideas"</label>
-                                <input
-                                    id="idea-search"
-                                    type="search"
-                                    name="search"
-                                    class="search-input"
-                                    placeholder="Search by title, content, tags…"
-                                    autocomplete="off"
-                                    prop:value=move || search_query.get()
-                                    on:input=move |ev| search_query.set(event_target_value(&ev))
+                                <IdeaSearchFilters
+                                    search=search
+                                    apply_search=apply_search
+                                    user_resource=user_resource
+                                    categories=categories
+                                    stages=stages
+                                    page_tag=tag
                                 />
                             </div>
                         </article>
@@ -178,6 +311,8 @@ pub fn IdeasBoard() -> impl IntoView {
                             user_resource=user_resource
                             ideas_resource=ideas_resource
                             stats_resource=stats_resource
+                            categories=categories
+                            voted_ideas=voted_ideas
                         />
 
                         <Suspense fallback=move || view! { <p class="loading">"Loading…"</p> }>
@@ -214,3 +349,202 @@ pub fn IdeasBoard() -> impl IntoView {
         </div>
     }
 }
+
+/// Where the board shows `search`. A search for the tag of the page it is on
+/// stays on that tag's page.
+fn search_url(search: &IdeaSearch, page_tag: Option<&str>) -> String {
+    match page_tag.filter(|slug| search.tag.as_deref() == Some(*slug)) {
+        Some(slug) => {
+            let query = search
+                .to_query()
+                .into_iter()
+                .filter(|(key, _)| *key != IdeaSearch::TAG);
+            format!(
+                "{}/{}{}",
+                paths::TAGS,
+                slug,
+                ParamsMap::from_iter(query).to_query_string()
+            )
+        }
+        None => format!(
+            "{}{}",
+            paths::HOME,
+            ParamsMap::from_iter(search.to_query()).to_query_string()
+        ),
+    }
+}
+
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Search text and filters for the board. Each change updates the URL, which
+/// runs the search.
+#[component]
+fn IdeaSearchFilters(
+    search: Memo<IdeaSearch>,
+    apply_search: Callback<IdeaSearch>,
+    user_resource: Resource<Result<Option<crate::auth::UserSession>, ServerFnError>>,
+    categories: Resource<Result<Vec<Category>, ServerFnError>>,
+    stages: Resource<Result<Vec<Stage>, ServerFnError>>,
+    page_tag: Option<Signal<String>>,
+) -> impl IntoView {
+    // What "Clear search" goes back to: the tag's page or the whole board
+    let unfiltered = move || IdeaSearch {
+        tag: page_tag.map(|tag| tag.get()),
+        ..IdeaSearch::default()
+    };
+    let is_filtered = move || {
+        let search = IdeaSearch {
+            sort: IdeaSort::default(),
+            ..search.get()
+        };
+        search != unfiltered()
+    };
+    let update = move |change: &dyn Fn(&mut IdeaSearch)| {
+        let mut next = search.get_untracked();
+        change(&mut next);
+        apply_search.run(next);
+    };
+    let is_logged_in = move || is_user_logged_in(&user_resource);
+
+    view! {
+        <form
+            class="search-filters"
+            role="search"
+            on:submit=move |ev| ev.prevent_default()
+        >
+            <label for="idea-search" class="sr-only">"Search Ideas"</label>
+            <input
+                id="idea-search"
+                type="search"
+                name="q"
+                class="search-input"
+                placeholder="Search ideas and comments…"
+                autocomplete="off"
+                prop:value=move || search.get().text
+                on:change=move |ev| {
+                    let text = event_target_value(&ev);
+                    update(&|next| {
+                        next.text = text.trim().to_string();
+                        next.sort = if next.text.is_empty() { IdeaSort::default() } else { IdeaSort::Relevance };
+                    })
+                }
+            />
+            <details class="search-filter-details" open=move || is_filtered() && search.get().text.is_empty()>
+                <summary>"Filters"</summary>
+                <Suspense fallback=|| ()>
+                    {move || stages.get().and_then(Result::ok).map(|stages| view! {
+                        <label class="search-filter">
+                            <span>"Stage"</span>
+                            <select
+                                prop:value=move || search.get().stage.unwrap_or_default()
+                                on:change=move |ev| {
+                                    let stage = non_empty(event_target_value(&ev));
+                                    update(&|next| next.stage = stage.clone())
+                                }
+                            >
+                                <option value="">"Any stage"</option>
+                                {stages
+                                    .into_iter()
+                                    .map(|stage| view! { <option value=stage.name.clone()>{stage.name.clone()}</option> })
+                                    .collect_view()}
+                            </select>
+                        </label>
+                    })}
+                </Suspense>
+                <Suspense fallback=|| ()>
+                    {move || categories.get().and_then(Result::ok).map(|categories| view! {
+                        <label class="search-filter">
+                            <span>"Category"</span>
+                            <select
+                                prop:value=move || search.get().category.unwrap_or_default()
+                                on:change=move |ev| {
+                                    let category = non_empty(event_target_value(&ev));
+                                    update(&|next| next.category = category.clone())
+                                }
+                            >
+                                <option value="">"All categories"</option>
+                                {categories
+                                    .into_iter()
+                                    .map(|category| view! {
+                                        <option value=category.slug>
+                                            {category.name}
+                                            {category.is_archived.then_some(" (archived)")}
+                                        </option>
+                                    })
+                                    .collect_view()}
+                            </select>
+                        </label>
+                    })}
+                </Suspense>
+                <label class="search-filter">
+                    <span>"Tag"</span>
+                    <input
+                        type="text"
+                        prop:value=move || search.get().tag.unwrap_or_default()
+                        on:change=move |ev| {
+                            let tag = non_empty(event_target_value(&ev));
+                            update(&|next| next.tag = tag.clone())
+                        }
+                    />
+                </label>
+                <label class="search-filter">
+                    <span>"Author"</span>
+                    <input
+                        type="text"
+                        prop:value=move || search.get().author.unwrap_or_default()
+                        on:change=move |ev| {
+                            let author = non_empty(event_target_value(&ev));
+                            update(&|next| next.author = author.clone())
+                        }
+                    />
+                </label>
+                <label class="search-filter">
+                    <span>"Posted from"</span>
+                    <input
+                        type="date"
+                        prop:value=move || search.get().posted_from.map(|date| date.to_string()).unwrap_or_default()
+                        on:change=move |ev| {
+                            let date = event_target_value(&ev).parse().ok();
+                            update(&|next| next.posted_from = date)
+                        }
+                    />
+                </label>
+                <label class="search-filter">
+                    <span>"Posted to"</span>
+                    <input
+                        type="date"
+                        prop:value=move || search.get().posted_to.map(|date| date.to_string()).unwrap_or_default()
+                        on:change=move |ev| {
+                            let date = event_target_value(&ev).parse().ok();
+                            update(&|next| next.posted_to = date)
+                        }
+                    />
+                </label>
+                <Show when=is_logged_in>
+                    <label class="search-filter search-filter-check">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || search.get().voted_by_me
+                            on:change=move |ev| {
+                                let voted = event_target_checked(&ev);
+                                update(&|next| next.voted_by_me = voted)
+                            }
+                        />
+                        <span>"Only ideas I voted for"</span>
+                    </label>
+                </Show>
+            </details>
+            <Show when=is_filtered>
+                <A
+                    href=move || search_url(&unfiltered(), page_tag.map(|tag| tag.get()).as_deref())
+                    attr:class="search-clear"
+                >
+                    "Clear search"
+                </A>
+            </Show>
+        </form>
+    }
+}
@@ -1,22 +1,31 @@
-use std::collections::HashMap;
-
 use leptos::prelude::*;
 
 use crate::auth::UserSession;
-use crate::models::IdeaWithAuthor;
+use crate::models::{IdeaPage, IdeaWithAuthor, ResponseSummary, SnippetPart};
 use crate::routes::async_helpers::spawn_server_action;
-use crate::routes::view_helpers::{format_relative_time, is_user_logged_in, stage_badge_color};
+use crate::routes::view_helpers::{
+    edited_marker, format_relative_time, is_user_logged_in, stage_badge_color,
+};
 
 use super::super::toggle_vote;
 
 #[component]
 pub(super) fn IdeaCard(
     idea_with_author: IdeaWithAuthor,
+    /// Passage that matched the search text, if any
+    #[prop(optional)]
+    snippet: Vec<SnippetPart>,
+    /// Visible comments on the idea
+    comment_count: i64,
+    category_name: String,
+    /// Badge color of the idea's stage
+    stage_color: String,
+    /// Latest official response from staff, if any
+    latest_response: Option<ResponseSummary>,
     rank: usize,
     user_resource: Resource<Result<Option<UserSession>, ServerFnError>>,
     voted_ideas: RwSignal<Vec<i32>>,
-    comment_counts_resource: Resource<Result<HashMap<i32, i64>, ServerFnError>>,
-    ideas_resource: Resource<Result<Vec<IdeaWithAuthor>, ServerFnError>>,
+    ideas_resource: Resource<Result<IdeaPage, ServerFnError>>,
 ) -> impl IntoView {
     let idea_id = idea_with_author.idea.id;
     let vote_count = RwSignal::new(idea_with_author.idea.vote_count);
@@ -25,19 +34,12 @@ pub(super) fn IdeaCard(
     let stage = idea_with_author.idea.stage.clone();
     let is_pinned = idea_with_author.idea.is_pinned();
     let created_at = idea_with_author.idea.created_at;
+    let edited = idea_with_author.idea.edited_at.as_ref().map(edited_marker);
     let author_name = idea_with_author.author_name.clone();
 
     let has_voted = move || voted_ideas.get().contains(&idea_id);
     let is_logged_in = move || is_user_logged_in(&user_resource);
 
-    let comment_count = move || {
-        comment_counts_resource
-            .get()
-            .and_then(|r| r.ok())
-            .and_then(|counts| counts.get(&idea_id).copied())
-            .unwrap_or(0)
-    };
-
     let is_toggling = RwSignal::new(false);
 
     let handle_vote = move |_| {
@@ -104,7 +106,7 @@ pub(super) fn IdeaCard(
     };
 
     let relative_time = format_relative_time(&created_at);
-    let stage_color = stage_badge_color(&stage);
+    let stage_color = stage_badge_color(&stage_color);
 
     view! {
         <div class="spark-item" class:pinned=is_pinned class:voted=has_voted>
@@ -131,18 +133,38 @@ pub(super) fn IdeaCard(
                 }}
                 <h3 class="spark-title">{title}</h3>
                 <p class="spark-text">{content}</p>
+                {(!snippet.is_empty()).then(|| view! {
+                    <p class="spark-snippet">
+                        {snippet
+                            .into_iter()
+                            .map(|part| {
+                                if part.highlighted {
+                                    view! { <mark>{part.text}</mark> }.into_any()
+                                } else {
+                                    part.text.into_any()
+                                }
+                            })
+                            .collect_view()}
+                    </p>
+                })}
+                {latest_response.map(|response| view! {
+                    <p class="spark-response">
+                        <span class="spark-response-label">{format!("Official response · {}", response.stage)}</span>
+                        " "
+                        {response.excerpt}
+                    </p>
+                })}
                 <div class="spark-meta">
                     <span class=format!("stage-badge stage-{}", stage_color)>{stage.clone()}</span>
+                    <span class="category-badge">{category_name}</span>
                     <span class="author-name">"by " {author_name}</span>
                     <span class="spark-time">{format!("submitted {}", relative_time)}</span>
+                    {edited.map(|(label, title)| view! { <span class="edited-marker" title=title>{label}</span> })}
                     <span class="spark-comments-badge">
-                        {move || {
-                            let count = comment_count();
-                            if count == 1 {
-                                "1 comment".to_string()
-                            } else {
-                                format!("{} comments", count)
-                            }
+                        {if comment_count == 1 {
+                            "1 comment".to_string()
+                        } else {
+                            format!("{} comments", comment_count)
                         }}
                     </span>
                 </div>
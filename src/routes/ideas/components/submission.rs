@@ -3,25 +3,78 @@ use leptos::prelude::*;
 use leptos_router::components::A;
 
 use crate::auth::UserSession;
-use crate::models::IdeaWithAuthor;
+use crate::models::{Category, IdeaPage, SimilarIdea};
 use crate::routes::async_helpers::spawn_server_action;
 use crate::routes::view_helpers::is_user_logged_in;
 
-use super::super::create_idea_auth;
+use super::super::{add_vote, create_idea_auth, find_similar_ideas, MIN_SIMILAR_TITLE_CHARS};
+use super::tag_input::TagChipsInput;
+
+/// Pause in typing before the draft is checked for similar ideas
+const SIMILAR_SEARCH_DELAY: std::time::Duration = std::time::Duration::from_millis(400);
 
 #[component]
 pub(super) fn IdeaSubmissionDialog(
     user_resource: Resource<Result<Option<UserSession>, ServerFnError>>,
-    ideas_resource: Resource<Result<Vec<IdeaWithAuthor>, ServerFnError>>,
+    ideas_resource: Resource<Result<IdeaPage, ServerFnError>>,
     stats_resource: Resource<Result<(i64, i64), ServerFnError>>,
+    categories: Resource<Result<Vec<Category>, ServerFnError>>,
+    voted_ideas: RwSignal<Vec<i32>>,
 ) -> impl IntoView {
     let is_open = RwSignal::new(false);
     let title = RwSignal::new(String::new());
     let content = RwSignal::new(String::new());
-    let tags = RwSignal::new(String::new());
+    // Id of the chosen category, empty until one is picked
+    let category = RwSignal::new(String::new());
+    let tags = RwSignal::new(Vec::<String>::new());
+    // Tag text typed but not yet turned into a chip
+    let tag_text = RwSignal::new(String::new());
+    let tag_list = move || {
+        let mut names = tags.get_untracked();
+        names.push(tag_text.get_untracked());
+        names.join(", ")
+    };
     let error_message = RwSignal::new(Option::<String>::None);
+    // Shown after submitting an idea that waits for a moderator's approval
+    let pending_notice = RwSignal::new(false);
+    // Title of the existing idea the user voted for instead of posting, and
+    // whether that added a vote or they had already voted for it
+    let voted_instead = RwSignal::new(Option::<(String, bool)>::None);
     let is_submitting = RwSignal::new(false);
 
+    // Title, description and tags as of the last pause in typing
+    let draft = RwSignal::new((String::new(), String::new(), String::new()));
+    let search_timer = StoredValue::new(Option::<TimeoutHandle>::None);
+    let schedule_similar_search = move || {
+        if let Some(handle) = search_timer.get_value() {
+            handle.clear();
+        }
+        let handle = set_timeout_with_handle(
+            move || draft.set((title.get_untracked(), content.get_untracked(), tag_list())),
+            SIMILAR_SEARCH_DELAY,
+        )
+        .ok();
+        search_timer.set_value(handle);
+    };
+    let similar_ideas = Resource::new(
+        move || draft.get(),
+        |(title, content, tags)| async move {
+            if title.trim().chars().count() < MIN_SIMILAR_TITLE_CHARS {
+                return Ok(Vec::new());
+            }
+            find_similar_ideas(title, content, tags).await
+        },
+    );
+    let reset_form = move || {
+        title.set(String::new());
+        content.set(String::new());
+        category.set(String::new());
+        tags.set(Vec::new());
+        tag_text.set(String::new());
+        draft.set(Default::default());
+        is_open.set(false);
+    };
+
     let max_title_chars: usize = 100;
     let max_content_chars: usize = 500;
     let title_count = move || title.get().len();
@@ -37,6 +90,7 @@ pub(super) fn IdeaSubmissionDialog(
     let can_submit = move || {
         !title.get().trim().is_empty()
             && !content.get().trim().is_empty()
+            && category.get().parse::<i32>().is_ok()
             && title.get().len() <= max_title_chars
             && content.get().len() <= max_content_chars
             && !is_submitting.get()
@@ -49,19 +103,22 @@ pub(super) fn IdeaSubmissionDialog(
         }
         is_submitting.set(true);
         error_message.set(None);
+        pending_notice.set(false);
+        voted_instead.set(None);
 
         let title_value = title.get();
         let content_value = content.get();
-        let tags_value = tags.get();
+        let tags_value = tag_list();
+        let Ok(category_id) = category.get().parse::<i32>() else {
+            return;
+        };
         spawn_server_action(
-            create_idea_auth(title_value, content_value, tags_value),
-            move |_| {
+            create_idea_auth(title_value, content_value, tags_value, category_id),
+            move |idea| {
+                pending_notice.set(idea.is_pending);
                 ideas_resource.refetch();
                 stats_resource.refetch();
-                title.set(String::new());
-                content.set(String::new());
-                tags.set(String::new());
-                is_open.set(false);
+                reset_form();
                 is_submitting.set(false);
             },
             move |e| {
@@ -71,6 +128,26 @@ pub(super) fn IdeaSubmissionDialog(
         );
     };
 
+    let handle_vote_instead = move |idea: SimilarIdea| {
+        error_message.set(None);
+        spawn_server_action(
+            add_vote(idea.id),
+            move |added| {
+                voted_ideas.update(|ids| {
+                    if !ids.contains(&idea.id) {
+                        ids.push(idea.id);
+                    }
+                });
+                voted_instead.set(Some((idea.title, added)));
+                pending_notice.set(false);
+                ideas_resource.refetch();
+                stats_resource.refetch();
+                reset_form();
+            },
+            move |e| error_message.set(Some(e.to_string())),
+        );
+    };
+
     view! {
         <article class="sidebar-card callout callout-primary">
             <header class="sidebar-card-header">
@@ -78,6 +155,23 @@ pub(super) fn IdeaSubmissionDialog(
             </header>
             <div class="sidebar-card-body">
                 <p class="sidebar-intro">"Share your suggestions to improve UAB IT services."</p>
+                <Show when=move || pending_notice.get()>
+                    <p class="sidebar-notice" role="status">
+                        "Thanks! Your idea will appear once a moderator approves it."
+                    </p>
+                </Show>
+                {move || voted_instead.get().map(|(idea_title, added)| {
+                    let message = if added {
+                        "Thanks! Your vote was added to \""
+                    } else {
+                        "You had already voted for \""
+                    };
+                    view! {
+                        <p class="sidebar-notice" role="status">
+                            {message} {idea_title} "\"."
+                        </p>
+                    }
+                })}
                 <Suspense fallback=move || view! { <p class="loading">"…"</p> }>
                     <Show when=is_logged_in fallback=move || view! {
                         <A href="/login" attr:class="submit-btn dialog-trigger-btn btn btn-primary">"Log in"</A>
@@ -110,6 +204,7 @@ pub(super) fn IdeaSubmissionDialog(
                                                 prop:value=move || title.get()
                                                 on:input=move |ev| {
                                                     title.set(event_target_value(&ev));
+                                                    schedule_similar_search();
                                                 }
                                             />
                                             <span class="char-counter" class:warning=title_warning class:error=title_error>
@@ -126,22 +221,62 @@ pub(super) fn IdeaSubmissionDialog(
                                                 prop:value=move || content.get()
                                                 on:input=move |ev| {
                                                     content.set(event_target_value(&ev));
+                                                    schedule_similar_search();
                                                 }
                                             />
                                             <span class="char-counter" class:warning=content_warning class:error=content_error>
                                                 {move || format!("{}/{}", content_count(), max_content_chars)}
                                             </span>
                                         </div>
+                                        <div class="form-group">
+                                            <label class="form-label" for="idea-category">"Category"</label>
+                                            <Suspense fallback=|| ()>
+                                                {move || categories.get().and_then(Result::ok).map(|categories| view! {
+                                                    <select
+                                                        id="idea-category"
+                                                        class="dialog-input"
+                                                        required
+                                                        prop:value=move || category.get()
+                                                        on:change=move |ev| category.set(event_target_value(&ev))
+                                                    >
+                                                        <option value="" disabled>"Choose the service area"</option>
+                                                        {categories
+                                                            .into_iter()
+                                                            .filter(|category| !category.is_archived)
+                                                            .map(|category| view! {
+                                                                <option value=category.id.to_string() title=category.description>
+                                                                    {category.name}
+                                                                </option>
+                                                            })
+                                                            .collect_view()}
+                                                    </select>
+                                                })}
+                                            </Suspense>
+                                        </div>
                                         <div class="form-group">
                                             <label class="form-label" for="idea-tags">"Tags"</label>
-                                            <input
-                                                id="idea-tags"
-                                                type="text"
-                                                class="dialog-input"
-                                                placeholder="e.g. accessibility, software, hardware"
-                                                bind:value=tags
+                                            <TagChipsInput
+                                                input_id="idea-tags"
+                                                tags=tags
+                                                text=tag_text
+                                                on_change=Callback::new(move |_| schedule_similar_search())
                                             />
                                         </div>
+                                        <Transition fallback=|| ()>
+                                            {move || {
+                                                similar_ideas
+                                                    .get()
+                                                    .and_then(Result::ok)
+                                                    .filter(|ideas| !ideas.is_empty())
+                                                    .map(|ideas| view! {
+                                                        <SimilarIdeasList
+                                                            ideas=ideas
+                                                            voted_ideas=voted_ideas
+                                                            on_vote=Callback::new(handle_vote_instead)
+                                                        />
+                                                    })
+                                            }}
+                                        </Transition>
                                         <div class="dialog-footer">
                                             <button
                                                 type="button"
@@ -149,7 +284,8 @@ pub(super) fn IdeaSubmissionDialog(
                                                 on:click=move |_| {
                                                     is_open.set(false);
                                                     error_message.set(None);
-                                                    tags.set(String::new());
+                                                    tags.set(Vec::new());
+                                                    tag_text.set(String::new());
                                                 }
                                             >
                                                 "Cancel"
@@ -171,3 +307,51 @@ pub(super) fn IdeaSubmissionDialog(
         </article>
     }
 }
+
+/// Existing ideas that look like the draft, each with a way to vote for it
+/// instead of posting a duplicate
+#[component]
+fn SimilarIdeasList(
+    ideas: Vec<SimilarIdea>,
+    voted_ideas: RwSignal<Vec<i32>>,
+    on_vote: Callback<SimilarIdea>,
+) -> impl IntoView {
+    view! {
+        <section class="similar-ideas" aria-labelledby="similar-ideas-title">
+            <h3 id="similar-ideas-title" class="similar-ideas-title">"Similar ideas already posted"</h3>
+            <ul>
+                {ideas
+                    .into_iter()
+                    .map(|idea| {
+                        let idea_id = idea.id;
+                        let has_voted = move || voted_ideas.get().contains(&idea_id);
+                        let idea_for_vote = idea.clone();
+                        view! {
+                            <li class="similar-idea">
+                                <a href=format!("/ideas/{}", idea_id) target="_blank">{idea.title}</a>
+                                <span class="similar-idea-meta">
+                                    {idea.stage} " · " {idea.vote_count} " votes"
+                                </span>
+                                <Show
+                                    when=move || !has_voted()
+                                    fallback=|| view! { <span class="similar-idea-meta">"You voted for this"</span> }
+                                >
+                                    <button
+                                        type="button"
+                                        class="btn btn-secondary"
+                                        on:click={
+                                            let idea = idea_for_vote.clone();
+                                            move |_| on_vote.run(idea.clone())
+                                        }
+                                    >
+                                        "Vote for this instead"
+                                    </button>
+                                </Show>
+                            </li>
+                        }
+                    })
+                    .collect_view()}
+            </ul>
+        </section>
+    }
+}
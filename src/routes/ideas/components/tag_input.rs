@@ -0,0 +1,152 @@
+use leptos::prelude::*;
+
+use crate::models::{parse_tag_names, slugify, TagWithCount, MAX_TAGS_PER_IDEA, MAX_TAG_CHARS};
+
+use super::super::suggest_tags;
+
+/// Tags as removable chips, with suggestions from the tags already in use.
+/// Enter or a comma turns the typed text into a chip; text still being typed
+/// stays in `text` so the form can include it.
+#[component]
+pub(super) fn TagChipsInput(
+    input_id: &'static str,
+    tags: RwSignal<Vec<String>>,
+    text: RwSignal<String>,
+    /// Runs after the chips change
+    on_change: Callback<()>,
+) -> impl IntoView {
+    let suggestions = Resource::new(
+        move || text.get(),
+        |prefix| async move {
+            if slugify(&prefix).is_empty() {
+                return Ok(Vec::new());
+            }
+            suggest_tags(prefix).await
+        },
+    );
+    let is_full = move || tags.with(|tags| tags.len() >= MAX_TAGS_PER_IDEA);
+
+    let add_tags = move |input: &str| {
+        tags.update(|tags| {
+            for name in parse_tag_names(input) {
+                let slug = slugify(&name);
+                if tags.len() < MAX_TAGS_PER_IDEA
+                    && !slug.is_empty()
+                    && !tags.iter().any(|tag| slugify(tag) == slug)
+                {
+                    tags.push(name);
+                }
+            }
+        });
+        text.set(String::new());
+        on_change.run(());
+    };
+    let remove_tag = move |name: &str| {
+        tags.update(|tags| tags.retain(|tag| tag != name));
+        on_change.run(());
+    };
+
+    let handle_keydown = move |ev: leptos::ev::KeyboardEvent| match ev.key().as_str() {
+        "Enter" | "," => {
+            ev.prevent_default();
+            add_tags(&text.get_untracked());
+        }
+        "Backspace" if text.get_untracked().is_empty() => {
+            if let Some(last) = tags.get_untracked().last() {
+                remove_tag(last);
+            }
+        }
+        _ => {}
+    };
+
+    view! {
+        <div class="tag-chips-input">
+            <ul class="tag-chips" aria-label="Selected tags">
+                <For
+                    each=move || tags.get()
+                    key=|tag| tag.clone()
+                    children=move |tag: String| {
+                        let label = format!("Remove tag {tag}");
+                        let name = tag.clone();
+                        view! {
+                            <li class="tag-chip">
+                                {tag}
+                                <button
+                                    type="button"
+                                    class="tag-chip-remove"
+                                    aria-label=label
+                                    on:click=move |_| remove_tag(&name)
+                                >
+                                    "×"
+                                </button>
+                            </li>
+                        }
+                    }
+                />
+            </ul>
+            <input
+                id=input_id
+                type="text"
+                class="dialog-input"
+                autocomplete="off"
+                maxlength=MAX_TAG_CHARS
+                disabled=is_full
+                placeholder=move || {
+                    if is_full() {
+                        format!("Up to {MAX_TAGS_PER_IDEA} tags")
+                    } else {
+                        "e.g. accessibility, software, hardware".to_string()
+                    }
+                }
+                prop:value=move || text.get()
+                on:input=move |ev| {
+                    let value = event_target_value(&ev);
+                    // Pasted lists become chips right away
+                    if value.contains(',') {
+                        add_tags(&value);
+                    } else {
+                        text.set(value);
+                    }
+                }
+                on:keydown=handle_keydown
+            />
+            <Transition fallback=|| ()>
+                {move || {
+                    suggestions
+                        .get()
+                        .and_then(Result::ok)
+                        .map(|found| {
+                            let chosen: Vec<String> = tags.with(|tags| tags.iter().map(|tag| slugify(tag)).collect());
+                            found
+                                .into_iter()
+                                .filter(|suggestion| !chosen.contains(&suggestion.tag.slug))
+                                .collect::<Vec<TagWithCount>>()
+                        })
+                        .filter(|found| !found.is_empty())
+                        .map(|found| view! {
+                            <ul class="tag-suggestions" aria-label="Suggested tags">
+                                {found
+                                    .into_iter()
+                                    .map(|suggestion| {
+                                        let name = suggestion.tag.name.clone();
+                                        view! {
+                                            <li>
+                                                <button
+                                                    type="button"
+                                                    class="tag-suggestion"
+                                                    on:click=move |_| add_tags(&name)
+                                                >
+                                                    {suggestion.tag.name}
+                                                    <span class="tag-suggestion-count">{suggestion.idea_count}</span>
+                                                </button>
+                                            </li>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </ul>
+                        })
+                }}
+            </Transition>
+        </div>
+    }
+}
@@ -1,20 +1,29 @@
 pub use account::AccountPage;
 pub use admin::*;
+pub use flag_reason::FlagReasonPrompt;
 pub use idea_detail::*;
 pub use ideas::*;
 pub use login::Login;
+pub use moderation_reason::*;
+pub use notification_bell::NotificationBell;
 pub use reset_password::ResetPassword;
+pub use roadmap::*;
 pub use signup::Signup;
 pub mod paths;
 
 mod account;
 mod admin;
 mod async_helpers;
+mod diff_helpers;
 mod error_helpers;
+mod flag_reason;
 mod idea_detail;
 mod ideas;
 mod login;
+mod moderation_reason;
+mod notification_bell;
 mod reset_password;
+mod roadmap;
 mod signup;
 #[cfg(feature = "ssr")]
 mod validation_helpers;
@@ -0,0 +1,147 @@
+use leptos::prelude::*;
+
+/// Canned reasons offered when `MODERATION_REASONS` is not set.
+#[cfg(feature = "ssr")]
+pub const DEFAULT_MODERATION_REASONS: [&str; 5] = [
+    "Off-topic for the idea board",
+    "Duplicate of an existing idea",
+    "Inappropriate or offensive language",
+    "Spam or advertising",
+    "Contains personal information",
+];
+
+/// Value of the select option that switches the prompt to free text.
+const OTHER_REASON: &str = "__other__";
+
+// ============================================================================
+// SERVER FUNCTIONS
+// ============================================================================
+
+#[server]
+pub async fn get_moderation_reasons() -> Result<Vec<String>, ServerFnError> {
+    use crate::auth::require_moderator;
+    require_moderator().await?;
+
+    Ok(std::env::var("MODERATION_REASONS")
+        .ok()
+        .map(|value| parse_moderation_reasons(&value))
+        .filter(|reasons| !reasons.is_empty())
+        .unwrap_or_else(|| {
+            DEFAULT_MODERATION_REASONS
+                .iter()
+                .map(|reason| reason.to_string())
+                .collect()
+        }))
+}
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Inline prompt asking a moderator why they are hiding something. The reason
+/// is shown to the author, so the prompt will not confirm without one.
+#[component]
+pub fn ModerationReasonPrompt(
+    /// Label of the confirm button, e.g. "Mark Off-Topic"
+    confirm_label: &'static str,
+    on_confirm: Callback<String>,
+    on_cancel: Callback<()>,
+) -> impl IntoView {
+    let reasons = Resource::new(|| (), |_| get_moderation_reasons());
+    let selected = RwSignal::new(String::new());
+    let custom = RwSignal::new(String::new());
+    let error = RwSignal::new(Option::<String>::None);
+
+    let submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let reason = if selected.get() == OTHER_REASON {
+            custom.get()
+        } else {
+            selected.get()
+        };
+        if reason.trim().is_empty() {
+            error.set(Some(
+                "Please give a reason; it is shown to the author.".to_string(),
+            ));
+            return;
+        }
+        error.set(None);
+        on_confirm.run(reason.trim().to_string());
+    };
+
+    view! {
+        <form class="moderation-reason-prompt" on:submit=submit>
+            <Show when=move || error.get().is_some()>
+                <div class="dialog-alert dialog-alert-error" role="alert" aria-live="polite">
+                    {move || error.get().unwrap_or_default()}
+                </div>
+            </Show>
+            <label class="moderation-reason-field">
+                <span>"Reason (shown to the author)"</span>
+                <select
+                    on:change=move |ev| selected.set(event_target_value(&ev))
+                    prop:value=move || selected.get()
+                >
+                    <option value="">"Choose a reason…"</option>
+                    {move || {
+                        reasons
+                            .get()
+                            .and_then(|result| result.ok())
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|reason| view! { <option value=reason.clone()>{reason.clone()}</option> })
+                            .collect_view()
+                    }}
+                    <option value=OTHER_REASON>"Other…"</option>
+                </select>
+            </label>
+            <Show when=move || selected.get() == OTHER_REASON>
+                <textarea
+                    class="dialog-textarea"
+                    aria-label="Reason"
+                    placeholder="e.g. duplicate of #42"
+                    maxlength=500
+                    bind:value=custom
+                />
+            </Show>
+            <div class="dialog-footer">
+                <button type="button" class="btn-cancel" on:click=move |_| on_cancel.run(())>
+                    "Cancel"
+                </button>
+                <button type="submit" class="btn btn-danger">{confirm_label}</button>
+            </div>
+        </form>
+    }
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+/// Split a `|`-separated list of canned reasons, dropping blank entries.
+#[cfg(feature = "ssr")]
+fn parse_moderation_reasons(value: &str) -> Vec<String> {
+    value
+        .split('|')
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::parse_moderation_reasons;
+
+    #[test]
+    fn parses_pipe_separated_reasons_and_skips_blanks() {
+        assert_eq!(
+            parse_moderation_reasons(" Spam | | Duplicate of an earlier idea|"),
+            vec![
+                "Spam".to_string(),
+                "Duplicate of an earlier idea".to_string()
+            ]
+        );
+        assert!(parse_moderation_reasons("  |  ").is_empty());
+    }
+}
@@ -0,0 +1,183 @@
+use leptos::prelude::*;
+
+use crate::models::{Notification, NotificationSummary};
+use crate::routes::account::mark_my_notifications_read;
+use crate::routes::async_helpers::spawn_server_action_refetch_resource;
+#[cfg(feature = "ssr")]
+use crate::routes::error_helpers::server_fn_error_with_log;
+use crate::routes::paths;
+use crate::routes::view_helpers::format_relative_time;
+
+/// Notifications listed in the bell menu
+#[cfg(feature = "ssr")]
+const RECENT_NOTIFICATIONS: i64 = 5;
+
+// ============================================================================
+// SERVER FUNCTIONS
+// ============================================================================
+
+#[server]
+pub async fn get_notification_summary() -> Result<NotificationSummary, ServerFnError> {
+    use crate::auth::require_auth;
+    let user = require_auth().await?;
+
+    let fetch_error = |e| {
+        server_fn_error_with_log(
+            "Failed to fetch notification summary",
+            e,
+            "Failed to fetch your notifications",
+        )
+    };
+    let unread = Notification::count_unread(user.id)
+        .await
+        .map_err(fetch_error)?;
+    let recent = Notification::get_recent_for_user(user.id, RECENT_NOTIFICATIONS)
+        .await
+        .map_err(fetch_error)?;
+
+    Ok(NotificationSummary { unread, recent })
+}
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Bell in the NavBar showing the unread count, with a menu of the latest
+/// notifications.
+#[component]
+pub fn NotificationBell() -> impl IntoView {
+    let summary = Resource::new(|| (), |_| get_notification_summary());
+    let open = RwSignal::new(false);
+
+    let unread = move || {
+        summary
+            .get()
+            .and_then(|result| result.ok())
+            .map(|summary| summary.unread)
+            .unwrap_or_default()
+    };
+    let toggle = move |_| {
+        if !open.get() {
+            summary.refetch();
+        }
+        open.update(|open| *open = !*open);
+    };
+
+    view! {
+        <div class="notification-bell">
+            <button
+                type="button"
+                class="nav-link notification-bell-toggle"
+                aria-haspopup="true"
+                aria-expanded=move || open.get().to_string()
+                aria-label=move || bell_label(unread())
+                on:click=toggle
+            >
+                <span aria-hidden="true">"🔔"</span>
+                <Show when=move || { unread() > 0 }>
+                    <span class="notification-badge" aria-hidden="true">
+                        {move || badge_text(unread())}
+                    </span>
+                </Show>
+            </button>
+            <Show when=move || open.get()>
+                <div class="notification-menu">
+                    <div class="notification-menu-header">
+                        <strong>"Notifications"</strong>
+                        <Show when=move || { unread() > 0 }>
+                            <button
+                                type="button"
+                                class="btn-link"
+                                on:click=move |_| {
+                                    spawn_server_action_refetch_resource(
+                                        mark_my_notifications_read(),
+                                        summary,
+                                    );
+                                }
+                            >"Mark all read"</button>
+                        </Show>
+                    </div>
+                    <Suspense fallback=|| view! { <p class="notification-menu-empty">"Loading…"</p> }>
+                        {move || summary.get().map(|result| match result {
+                            Ok(summary) if summary.recent.is_empty() => {
+                                view! { <p class="notification-menu-empty">"You have no notifications"</p> }
+                                    .into_any()
+                            }
+                            Ok(summary) => view! {
+                                <ul class="notification-menu-list">
+                                    {summary
+                                        .recent
+                                        .into_iter()
+                                        .map(|n| view! { <NotificationMenuItem notification=n/> })
+                                        .collect_view()}
+                                </ul>
+                            }
+                                .into_any(),
+                            Err(_) => view! {
+                                <p class="notification-menu-empty error">"Failed to load notifications"</p>
+                            }
+                                .into_any(),
+                        })}
+                    </Suspense>
+                    <a href=paths::PROFILE class="notification-menu-footer" on:click=move |_| open.set(false)>
+                        "All notifications and email settings"
+                    </a>
+                </div>
+            </Show>
+        </div>
+    }
+}
+
+#[component]
+fn NotificationMenuItem(notification: Notification) -> impl IntoView {
+    let meta = format_relative_time(&notification.created_at);
+    view! {
+        <li class="notification-menu-item" class:unread=!notification.is_read>
+            {match notification.link {
+                Some(link) => view! { <a href=link>{notification.message}</a> }.into_any(),
+                None => view! { <span>{notification.message}</span> }.into_any(),
+            }}
+            <span class="notification-menu-meta">{meta}</span>
+        </li>
+    }
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+/// Badge text, capped so it fits the bell
+fn badge_text(unread: i64) -> String {
+    if unread > 99 {
+        "99+".to_string()
+    } else {
+        unread.to_string()
+    }
+}
+
+fn bell_label(unread: i64) -> String {
+    match unread {
+        0 => "Notifications".to_string(),
+        1 => "Notifications (1 unread)".to_string(),
+        n => format!("Notifications ({n} unread)"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{badge_text, bell_label};
+
+    #[test]
+    fn badge_is_capped() {
+        assert_eq!(badge_text(7), "7");
+        assert_eq!(badge_text(99), "99");
+        assert_eq!(badge_text(150), "99+");
+    }
+
+    #[test]
+    fn bell_label_mentions_unread_count() {
+        assert_eq!(bell_label(0), "Notifications");
+        assert_eq!(bell_label(1), "Notifications (1 unread)");
+        assert_eq!(bell_label(4), "Notifications (4 unread)");
+    }
+}
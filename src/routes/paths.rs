@@ -2,5 +2,8 @@ pub const HOME: &str = "/";
 pub const LOGIN: &str = "/login";
 pub const SIGNUP: &str = "/signup";
 pub const RESET_PASSWORD: &str = "/reset_password";
+pub const PROFILE: &str = "/profile";
 pub const ADMIN: &str = "/admin";
+pub const TAGS: &str = "/tags";
+pub const ROADMAP: &str = "/roadmap";
 pub const CAS_LOGIN: &str = "/auth/cas/login";
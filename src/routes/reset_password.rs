@@ -6,16 +6,6 @@ use leptos_router::{hooks::use_query, params::Params};
 #[cfg(feature = "ssr")]
 use serde::{Deserialize, Serialize};
 
-#[cfg(feature = "ssr")]
-struct EmailCredentials {
-    email: String,
-    passwd: String,
-    smtp_server: String,
-}
-
-#[cfg(feature = "ssr")]
-static EMAIL_CREDS: std::sync::OnceLock<EmailCredentials> = std::sync::OnceLock::new();
-
 #[cfg(feature = "ssr")]
 #[derive(Deserialize, Serialize)]
 struct ResetTokenClaims {
@@ -23,28 +13,6 @@ struct ResetTokenClaims {
     exp: usize,
 }
 
-#[cfg(feature = "ssr")]
-fn get_email_creds() -> Result<&'static EmailCredentials, ServerFnError> {
-    if let Some(creds) = EMAIL_CREDS.get() {
-        return Ok(creds);
-    }
-
-    let creds = EmailCredentials {
-        email: env::var("MAILER_EMAIL")
-            .map_err(|e| ServerFnError::new(format!("MAILER_EMAIL is not configured: {e}")))?,
-        passwd: env::var("MAILER_PASSWD")
-            .map_err(|e| ServerFnError::new(format!("MAILER_PASSWD is not configured: {e}")))?,
-        smtp_server: env::var("MAILER_SMTP_SERVER").map_err(|e| {
-            ServerFnError::new(format!("MAILER_SMTP_SERVER is not configured: {e}"))
-        })?,
-    };
-
-    let _ = EMAIL_CREDS.set(creds);
-    EMAIL_CREDS
-        .get()
-        .ok_or_else(|| ServerFnError::new("Failed to initialize email credentials"))
-}
-
 #[cfg(feature = "ssr")]
 fn reset_token_secret() -> Result<String, ServerFnError> {
     env::var("RESET_TOKEN_SECRET")
@@ -103,13 +71,6 @@ pub async fn reset_password_1(email: String) -> Result<String, ServerFnError> {
         }
     };
     if exists {
-        let creds = match get_email_creds() {
-            Ok(creds) => creds,
-            Err(error) => {
-                tracing::error!(?error, "password reset email credentials not configured");
-                return Ok(String::from("Check your email"));
-            }
-        };
         let host = leptos_axum::extract::<axum_extra::extract::Host>()
             .await
             .map(|h| h.0)
@@ -127,31 +88,15 @@ pub async fn reset_password_1(email: String) -> Result<String, ServerFnError> {
             }
         };
         let uri = format!("{schema}://{host}/reset_password?token={token}");
-        let message = mail_send::mail_builder::MessageBuilder::new()
-            .from(("Realworld Leptos", creds.email.as_str()))
-            .to(vec![("You", email.as_str())])
-            .subject("Your password reset from realworld leptos")
-            .text_body(format!(
-                "You can reset your password accessing the following link: {uri}"
-            ));
-
-        let mut client = match mail_send::SmtpClientBuilder::new(creds.smtp_server.as_str(), 587)
-            .implicit_tls(false)
-            .credentials((creds.email.as_str(), creds.passwd.as_str()))
-            .connect()
-            .await
+        // Queued so the request never waits on SMTP; see `crate::mailer`
+        if let Err(error) = crate::models::EmailNotification::enqueue(
+            &email,
+            "Your password reset from realworld leptos",
+            &format!("You can reset your password accessing the following link: {uri}"),
+        )
+        .await
         {
-            Ok(client) => client,
-            Err(error) => {
-                tracing::error!(
-                    ?error,
-                    "failed to connect to smtp server for password reset"
-                );
-                return Ok(String::from("Check your email"));
-            }
-        };
-        if let Err(error) = client.send(message).await {
-            tracing::error!(?error, "failed to send password reset email");
+            tracing::error!(?error, "failed to queue password reset email");
         }
     }
     Ok(String::from("Check your email"))
@@ -161,7 +106,7 @@ fn validate_reset(password: &str, confirm: &str) -> bool {
     password == confirm
 }
 
-fn action_status_message<F>(read_result: F, log_context: &'static str) -> String
+pub(crate) fn action_status_message<F>(read_result: F, log_context: &'static str) -> String
 where
     F: FnOnce() -> Option<Result<String, ServerFnError>>,
 {
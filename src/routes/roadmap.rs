@@ -0,0 +1,49 @@
+use crate::models::Roadmap;
+#[cfg(feature = "ssr")]
+use crate::routes::error_helpers::server_fn_error_with_log;
+use leptos::prelude::*;
+
+mod components;
+use components::RoadmapBoard;
+
+// ============================================================================
+// SERVER FUNCTIONS
+// ============================================================================
+
+/// Public ideas grouped for the roadmap, with the ideas closed recently and
+/// the categories the viewer may move ideas in.
+#[server]
+pub async fn get_roadmap() -> Result<Roadmap, ServerFnError> {
+    use crate::auth::get_user;
+    use crate::models::{Category, RoadmapIdea, Stage, RECENTLY_COMPLETED_DAYS};
+
+    let fetch_error =
+        |e| server_fn_error_with_log("Failed to fetch roadmap", e, "Failed to fetch roadmap");
+    let moderator_scope = match get_user().await? {
+        Some(user) if user.is_admin() => Some(Vec::new()),
+        Some(user) if user.is_moderator() => Some(
+            Category::moderator_scope(user.id)
+                .await
+                .map_err(fetch_error)?,
+        ),
+        _ => None,
+    };
+    Ok(Roadmap {
+        stages: Stage::get_all().await.map_err(fetch_error)?,
+        open: RoadmapIdea::get_open().await.map_err(fetch_error)?,
+        recently_completed: RoadmapIdea::get_recently_completed(RECENTLY_COMPLETED_DAYS)
+            .await
+            .map_err(fetch_error)?,
+        moderator_scope,
+    })
+}
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Kanban view of the ideas in progress, at `/roadmap`
+#[component]
+pub fn RoadmapPage() -> impl IntoView {
+    view! { <RoadmapBoard/> }
+}
@@ -0,0 +1,240 @@
+use leptos::prelude::*;
+use leptos_meta::Title;
+
+use crate::models::{
+    find_stage, next_stages, Roadmap, RoadmapIdea, Stage, RECENTLY_COMPLETED_DAYS,
+};
+use crate::routes::admin::update_idea_stage_action;
+use crate::routes::async_helpers::spawn_server_action_with_error;
+use crate::routes::view_helpers::{format_relative_time, stage_badge_color};
+
+use super::get_roadmap;
+
+/// The roadmap's columns and recently completed lane. Moderators move ideas
+/// by dragging a card to another column or with the card's stage select.
+#[component]
+pub(super) fn RoadmapBoard() -> impl IntoView {
+    let auth_refresh = expect_context::<crate::auth::AuthRefresh>().0;
+    let roadmap = Resource::new(
+        move || auth_refresh.get(),
+        |_| async { get_roadmap().await },
+    );
+    let move_error = RwSignal::new(Option::<String>::None);
+    // The card being dragged: idea id and its stage
+    let dragging = RwSignal::new(Option::<(i32, String)>::None);
+
+    let can_move = Signal::derive(
+        move || matches!(roadmap.get(), Some(Ok(roadmap)) if roadmap.moderator_scope.is_some()),
+    );
+    let move_idea = Callback::new(move |(idea_id, to_stage): (i32, String)| {
+        move_error.set(None);
+        spawn_server_action_with_error(
+            update_idea_stage_action(idea_id, to_stage, None),
+            move |_| roadmap.refetch(),
+            move_error,
+        );
+    });
+
+    view! {
+        <Title text="Roadmap — UAB IT Idea Board"/>
+        <div class="container page roadmap-page">
+            <div class="roadmap-header">
+                <h1>"Roadmap"</h1>
+                <p>
+                    "What UAB IT is working on, by stage. "
+                    <Show when=move || can_move.get()>
+                        "Drag a card to another column, or pick a stage on the card, to move an idea."
+                    </Show>
+                </p>
+            </div>
+            {move || move_error.get().map(|error| view! { <p class="error" role="alert">{error}</p> })}
+
+            <Transition fallback=|| view! { <p class="loading">"Loading roadmap…"</p> }>
+                {move || roadmap.get().map(|result| match result {
+                    Ok(roadmap) => view! {
+                        <RoadmapColumns
+                            roadmap=roadmap
+                            dragging=dragging
+                            move_idea=move_idea
+                        />
+                    }
+                        .into_any(),
+                    Err(e) => view! { <p class="error">{format!("Error loading roadmap: {}", e)}</p> }.into_any(),
+                })}
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn RoadmapColumns(
+    roadmap: Roadmap,
+    dragging: RwSignal<Option<(i32, String)>>,
+    move_idea: Callback<(i32, String)>,
+) -> impl IntoView {
+    let stages = StoredValue::new(roadmap.stages.clone());
+    let columns = roadmap
+        .columns()
+        .into_iter()
+        .map(|(stage, ideas)| {
+            let column_stage = stage.name.clone();
+            let heading = stage.name.clone();
+            let badge_class = format!("stage-badge stage-{}", stage_badge_color(&stage.color));
+            let count = ideas.len();
+            let cards = ideas
+                .into_iter()
+                .cloned()
+                .map(|idea| {
+                    let can_move = roadmap.can_move(&idea);
+                    roadmap_card(idea, stages, can_move, dragging, move_idea)
+                })
+                .collect_view();
+            // Only columns the dragged idea may move to accept the drop
+            let accepts = Memo::new(move |_| {
+                dragging.get().is_some_and(|(_, from)| {
+                    stages.with_value(|stages| {
+                        next_stages(stages, &from)
+                            .iter()
+                            .any(|to| to.name == column_stage)
+                    })
+                })
+            });
+            let drop_stage = stage.name.clone();
+
+            view! {
+                <section
+                    class="roadmap-column"
+                    class:drop-target=move || accepts.get()
+                    aria-label=format!("{} ideas", heading)
+                    on:dragover=move |ev| {
+                        if accepts.get_untracked() {
+                            ev.prevent_default();
+                        }
+                    }
+                    on:drop=move |ev| {
+                        ev.prevent_default();
+                        if let Some((idea_id, _)) = dragging.get_untracked() {
+                            dragging.set(None);
+                            move_idea.run((idea_id, drop_stage.clone()));
+                        }
+                    }
+                >
+                    <h2 class="roadmap-column-title">
+                        <span class=badge_class>{heading.clone()}</span>
+                        <span class="roadmap-column-count">{count}</span>
+                    </h2>
+                    {if count == 0 {
+                        view! { <p class="roadmap-empty">"No ideas"</p> }.into_any()
+                    } else {
+                        view! { <ul class="roadmap-cards">{cards}</ul> }.into_any()
+                    }}
+                </section>
+            }
+        })
+        .collect_view();
+
+    let completed = roadmap.recently_completed;
+    view! {
+        <div class="roadmap-columns">{columns}</div>
+        <section class="roadmap-completed" aria-label="Recently completed">
+            <h2 class="roadmap-column-title">"Recently completed"</h2>
+            <p class="roadmap-completed-note">
+                {format!("Ideas closed in the last {} days.", RECENTLY_COMPLETED_DAYS)}
+            </p>
+            {if completed.is_empty() {
+                view! { <p class="roadmap-empty">"Nothing has been completed recently."</p> }.into_any()
+            } else {
+                view! {
+                    <ul class="roadmap-cards roadmap-completed-cards">
+                        {completed
+                            .into_iter()
+                            .map(|idea| completed_card(idea, stages))
+                            .collect_view()}
+                    </ul>
+                }
+                    .into_any()
+            }}
+        </section>
+    }
+}
+
+fn roadmap_card(
+    idea: RoadmapIdea,
+    stages: StoredValue<Vec<Stage>>,
+    can_move: bool,
+    dragging: RwSignal<Option<(i32, String)>>,
+    move_idea: Callback<(i32, String)>,
+) -> impl IntoView {
+    let idea_id = idea.id;
+    let stage = idea.stage.clone();
+    let targets: Vec<String> = stages.with_value(|stages| {
+        next_stages(stages, &idea.stage)
+            .into_iter()
+            .map(|to| to.name.clone())
+            .collect()
+    });
+    let has_targets = !targets.is_empty();
+    let title = idea.title.clone();
+
+    view! {
+        <li
+            class="roadmap-card"
+            class:dragging=move || dragging.get().is_some_and(|(id, _)| id == idea_id)
+            draggable=if can_move { "true" } else { "false" }
+            on:dragstart=move |ev| {
+                if let Some(transfer) = ev.data_transfer() {
+                    let _ = transfer.set_data("text/plain", &idea_id.to_string());
+                }
+                dragging.set(Some((idea_id, stage.clone())));
+            }
+            on:dragend=move |_| dragging.set(None)
+        >
+            <a class="roadmap-card-title" href=format!("/ideas/{}", idea_id)>{idea.title}</a>
+            <div class="roadmap-card-meta">
+                <span class="roadmap-votes" title="Sparks">"▲ " {idea.vote_count}</span>
+                <span class="category-badge">{idea.category_name}</span>
+            </div>
+            <Show when=move || can_move && has_targets>
+                <select
+                    class="roadmap-move"
+                    aria-label=format!("Move {} to another stage", title)
+                    prop:value=""
+                    on:change=move |ev| {
+                        let to_stage = event_target_value(&ev);
+                        if !to_stage.is_empty() {
+                            move_idea.run((idea_id, to_stage));
+                        }
+                    }
+                >
+                    <option value="">"Move to…"</option>
+                    {targets
+                        .iter()
+                        .map(|name| view! { <option value=name.clone()>{name.clone()}</option> })
+                        .collect_view()}
+                </select>
+            </Show>
+        </li>
+    }
+}
+
+fn completed_card(idea: RoadmapIdea, stages: StoredValue<Vec<Stage>>) -> impl IntoView {
+    let color = stages.with_value(|stages| {
+        find_stage(stages, &idea.stage).map_or_else(String::new, |found| found.color.clone())
+    });
+    let badge_class = format!("stage-badge stage-{}", stage_badge_color(&color));
+    let closed = idea.entered_stage_at.map(|at| {
+        let exact = at.format("%Y-%m-%d %H:%M UTC").to_string();
+        view! { <span class="roadmap-closed" title=exact>{format!("closed {}", format_relative_time(&at))}</span> }
+    });
+
+    view! {
+        <li class="roadmap-card">
+            <a class="roadmap-card-title" href=format!("/ideas/{}", idea.id)>{idea.title}</a>
+            <div class="roadmap-card-meta">
+                <span class=badge_class>{idea.stage}</span>
+                <span class="roadmap-votes" title="Sparks">"▲ " {idea.vote_count}</span>
+                {closed}
+            </div>
+        </li>
+    }
+}
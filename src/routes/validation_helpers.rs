@@ -1,13 +1,33 @@
 use leptos::prelude::ServerFnError;
 
-fn contains_profanity(text: &str) -> bool {
-    crate::profanity::contains_profanity(text)
+use crate::models::{
+    parse_tag_names, slugify, FilterMatch, FilterSeverity, FlagReason, MAX_CATEGORY_NAME_CHARS,
+    MAX_RESPONSE_CHARS, MAX_TAGS_PER_IDEA, MAX_TAG_CHARS,
+};
+use crate::profanity::WordFilterSet;
+
+/// Run the word filter. A blocking match is an error; a hold or flag match
+/// is returned so the caller can act on it once the post is saved.
+fn check_word_filter(
+    filters: &WordFilterSet,
+    texts: &[&str],
+    blocked_message: &str,
+) -> Result<Option<FilterMatch>, ServerFnError> {
+    match filters.check(texts) {
+        Some(found) if found.severity == FilterSeverity::Block => {
+            Err(ServerFnError::new(blocked_message))
+        }
+        found => Ok(found),
+    }
 }
 
+/// Validate a new or edited idea, returning the word filter rule it matched
+/// if the idea should be held or flagged.
 pub(crate) fn validate_idea_title_and_content(
     title: &str,
     content: &str,
-) -> Result<(), ServerFnError> {
+    filters: &WordFilterSet,
+) -> Result<Option<FilterMatch>, ServerFnError> {
     if title.trim().is_empty() {
         return Err(ServerFnError::new("Idea title cannot be empty"));
     }
@@ -24,41 +44,152 @@ pub(crate) fn validate_idea_title_and_content(
             "Idea description cannot exceed 500 characters",
         ));
     }
-    if contains_profanity(title) || contains_profanity(content) {
+    check_word_filter(
+        filters,
+        &[title, content],
+        "Your submission contains inappropriate language. Please revise and try again.",
+    )
+}
+
+/// Validate a comma-separated tag list, returning the distinct tag names.
+pub(crate) fn validate_idea_tags(tags: &str) -> Result<Vec<String>, ServerFnError> {
+    if tags.len() > 200 {
+        return Err(ServerFnError::new("Tags cannot exceed 200 characters"));
+    }
+    let names = parse_tag_names(tags);
+    if names.len() > MAX_TAGS_PER_IDEA {
+        return Err(ServerFnError::new(format!(
+            "An idea can have at most {MAX_TAGS_PER_IDEA} tags"
+        )));
+    }
+    for name in &names {
+        if name.chars().count() > MAX_TAG_CHARS {
+            return Err(ServerFnError::new(format!(
+                "Tag \"{name}\" cannot exceed {MAX_TAG_CHARS} characters"
+            )));
+        }
+        if slugify(name).is_empty() {
+            return Err(ServerFnError::new(format!(
+                "Tag \"{name}\" needs at least one letter or number"
+            )));
+        }
+    }
+    Ok(names)
+}
+
+/// Validate a category name, returning it trimmed with its slug.
+pub(crate) fn validate_category_name(name: &str) -> Result<(String, String), ServerFnError> {
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    if name.is_empty() {
+        return Err(ServerFnError::new("Category name cannot be empty"));
+    }
+    if name.chars().count() > MAX_CATEGORY_NAME_CHARS {
+        return Err(ServerFnError::new(format!(
+            "Category name cannot exceed {MAX_CATEGORY_NAME_CHARS} characters"
+        )));
+    }
+    let slug = slugify(&name);
+    if slug.is_empty() {
         return Err(ServerFnError::new(
-            "Your submission contains inappropriate language. Please revise and try again.",
+            "Category name needs at least one letter or number",
         ));
     }
-    Ok(())
+    Ok((name, slug))
 }
 
-pub(crate) fn validate_idea_tags(tags: &str) -> Result<(), ServerFnError> {
-    if tags.len() > 200 {
-        return Err(ServerFnError::new("Tags cannot exceed 200 characters"));
+/// Validate an optional official response, returning it trimmed, or `None`
+/// when it is blank.
+pub(crate) fn validate_official_response(
+    response: Option<&str>,
+) -> Result<Option<String>, ServerFnError> {
+    let Some(response) = response.map(str::trim).filter(|r| !r.is_empty()) else {
+        return Ok(None);
+    };
+    if response.chars().count() > MAX_RESPONSE_CHARS {
+        return Err(ServerFnError::new(format!(
+            "Official response cannot exceed {MAX_RESPONSE_CHARS} characters"
+        )));
     }
-    Ok(())
+    Ok(Some(response.to_string()))
 }
 
-pub(crate) fn validate_comment_content(content: &str) -> Result<(), ServerFnError> {
+/// Validate a new or edited comment, returning the word filter rule it
+/// matched if the comment should be held or flagged.
+pub(crate) fn validate_comment_content(
+    content: &str,
+    filters: &WordFilterSet,
+) -> Result<Option<FilterMatch>, ServerFnError> {
     if content.trim().is_empty() {
         return Err(ServerFnError::new("Comment cannot be empty"));
     }
     if content.len() > 500 {
         return Err(ServerFnError::new("Comment cannot exceed 500 characters"));
     }
-    if contains_profanity(content) {
+    check_word_filter(
+        filters,
+        &[content],
+        "Your comment contains inappropriate language. Please revise and try again.",
+    )
+}
+
+/// A moderation reason is shown to the author, so it must be present and
+/// reasonably short. Returns the trimmed reason.
+pub(crate) fn validate_moderation_reason(reason: &str) -> Result<String, ServerFnError> {
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err(ServerFnError::new("A moderation reason is required"));
+    }
+    if reason.len() > 500 {
+        return Err(ServerFnError::new(
+            "Moderation reason cannot exceed 500 characters",
+        ));
+    }
+    Ok(reason.to_string())
+}
+
+/// Parse the reason a user gave for a flag. Free text is optional except for
+/// "other", where it is the only explanation. Returns the trimmed details.
+pub(crate) fn validate_flag_reason(
+    reason: Option<&str>,
+    details: Option<&str>,
+) -> Result<(FlagReason, Option<String>), ServerFnError> {
+    let reason = reason
+        .and_then(FlagReason::from_name)
+        .ok_or_else(|| ServerFnError::new("Choose why you are flagging this"))?;
+    let details = details
+        .map(str::trim)
+        .filter(|details| !details.is_empty())
+        .map(str::to_string);
+    if details
+        .as_ref()
+        .is_some_and(|details| details.chars().count() > 500)
+    {
+        return Err(ServerFnError::new(
+            "Flag details cannot exceed 500 characters",
+        ));
+    }
+    if reason == FlagReason::Other && details.is_none() {
         return Err(ServerFnError::new(
-            "Your comment contains inappropriate language. Please revise and try again.",
+            "Please describe the problem when choosing Other",
         ));
     }
-    Ok(())
+    Ok((reason, details))
+}
+
+/// Flags are only raised against ideas and comments.
+pub(crate) fn validate_flag_target(target_type: &str) -> Result<(), ServerFnError> {
+    match target_type {
+        "idea" | "comment" => Ok(()),
+        _ => Err(ServerFnError::new("Only ideas and comments can be flagged")),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::profanity::tests::test_filters;
 
-    fn assert_error_contains(result: Result<(), ServerFnError>, expected: &str) {
+    fn assert_error_contains<T: std::fmt::Debug>(result: Result<T, ServerFnError>, expected: &str) {
         let err = result.expect_err("expected validation to fail");
         let error_message = err.to_string();
         assert!(
@@ -67,21 +198,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn category_names_are_trimmed_and_slugged() {
+        assert_eq!(
+            validate_category_name("  Identity  &   Access ").unwrap(),
+            (
+                "Identity & Access".to_string(),
+                "identity-access".to_string()
+            )
+        );
+        assert_error_contains(validate_category_name("   "), "cannot be empty");
+        assert_error_contains(validate_category_name("&&"), "at least one letter");
+        assert_error_contains(
+            validate_category_name(&"a".repeat(61)),
+            "cannot exceed 60 characters",
+        );
+    }
+
+    #[test]
+    fn official_responses_are_optional_and_bounded() {
+        assert_eq!(validate_official_response(None).unwrap(), None);
+        assert_eq!(validate_official_response(Some("  \n ")).unwrap(), None);
+        assert_eq!(
+            validate_official_response(Some("  Piloting in **May** ")).unwrap(),
+            Some("Piloting in **May**".to_string())
+        );
+        assert_error_contains(
+            validate_official_response(Some(&"a".repeat(5001))),
+            "cannot exceed 5000 characters",
+        );
+    }
+
     #[test]
     fn idea_title_and_content_accept_valid_boundaries() {
         let title = "a".repeat(100);
         let content = "b".repeat(500);
-        assert!(validate_idea_title_and_content(&title, &content).is_ok());
+        assert!(validate_idea_title_and_content(&title, &content, &test_filters()).is_ok());
     }
 
     #[test]
     fn idea_title_and_content_reject_empty_fields() {
         assert_error_contains(
-            validate_idea_title_and_content("   ", "content"),
+            validate_idea_title_and_content("   ", "content", &test_filters()),
             "Idea title cannot be empty",
         );
         assert_error_contains(
-            validate_idea_title_and_content("title", "   "),
+            validate_idea_title_and_content("title", "   ", &test_filters()),
             "Idea description cannot be empty",
         );
     }
@@ -91,11 +253,11 @@ mod tests {
         let long_title = "a".repeat(101);
         let long_content = "b".repeat(501);
         assert_error_contains(
-            validate_idea_title_and_content(&long_title, "content"),
+            validate_idea_title_and_content(&long_title, "content", &test_filters()),
             "Idea title cannot exceed 100 characters",
         );
         assert_error_contains(
-            validate_idea_title_and_content("title", &long_content),
+            validate_idea_title_and_content("title", &long_content, &test_filters()),
             "Idea description cannot exceed 500 characters",
         );
     }
@@ -103,7 +265,7 @@ mod tests {
     #[test]
     fn idea_title_and_content_reject_profanity() {
         assert_error_contains(
-            validate_idea_title_and_content("clean", "this is shit"),
+            validate_idea_title_and_content("clean", "this is shit", &test_filters()),
             "contains inappropriate language",
         );
     }
@@ -117,25 +279,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn idea_tags_are_normalized_and_limited() {
+        assert_eq!(
+            validate_idea_tags(" VPN , vpn, remote  access ").unwrap(),
+            vec!["VPN".to_string(), "remote access".to_string()]
+        );
+        let too_many: Vec<String> = (0..=MAX_TAGS_PER_IDEA).map(|i| format!("tag{i}")).collect();
+        assert_error_contains(validate_idea_tags(&too_many.join(",")), "at most 8 tags");
+        assert_error_contains(
+            validate_idea_tags(&"t".repeat(MAX_TAG_CHARS + 1)),
+            "cannot exceed 32 characters",
+        );
+        assert_error_contains(validate_idea_tags("???"), "at least one letter or number");
+    }
+
     #[test]
     fn comment_content_accepts_valid_boundary() {
         let content = "c".repeat(500);
-        assert!(validate_comment_content(&content).is_ok());
+        assert!(validate_comment_content(&content, &test_filters()).is_ok());
     }
 
     #[test]
     fn comment_content_rejects_empty_overlength_and_profanity() {
-        assert_error_contains(validate_comment_content("   "), "Comment cannot be empty");
         assert_error_contains(
-            validate_comment_content(&"c".repeat(501)),
+            validate_comment_content("   ", &test_filters()),
+            "Comment cannot be empty",
+        );
+        assert_error_contains(
+            validate_comment_content(&"c".repeat(501), &test_filters()),
             "Comment cannot exceed 500 characters",
         );
         assert_error_contains(
-            validate_comment_content("what the fuck"),
+            validate_comment_content("what the fuck", &test_filters()),
             "contains inappropriate language",
         );
     }
 
+    #[test]
+    fn word_filter_hold_and_flag_matches_are_returned() {
+        let held = validate_comment_content("darn printers", &test_filters())
+            .unwrap()
+            .expect("should match");
+        assert_eq!(held.severity, FilterSeverity::Hold);
+        let flagged = validate_idea_title_and_content("Printers", "buy now", &test_filters())
+            .unwrap()
+            .expect("should match");
+        assert_eq!(flagged.rule_id, 7);
+        assert_eq!(
+            validate_comment_content("more printers", &test_filters()).unwrap(),
+            None
+        );
+    }
+
     /// Mirrors the validation order in `update_idea_content_mod`: tags are
     /// validated before title/content so a tag error is surfaced first when
     /// both inputs are invalid.
@@ -145,7 +341,7 @@ mod tests {
         tags: &str,
     ) -> Result<(), ServerFnError> {
         validate_idea_tags(tags)?;
-        validate_idea_title_and_content(title, content)?;
+        validate_idea_title_and_content(title, content, &test_filters())?;
         Ok(())
     }
 
@@ -158,4 +354,64 @@ mod tests {
             "Tags cannot exceed 200 characters",
         );
     }
+
+    #[test]
+    fn moderation_reason_is_trimmed_and_required() {
+        assert_eq!(
+            validate_moderation_reason("  duplicate of #42 ").unwrap(),
+            "duplicate of #42"
+        );
+        assert_error_contains(
+            validate_moderation_reason("  "),
+            "A moderation reason is required",
+        );
+        assert_error_contains(
+            validate_moderation_reason(&"r".repeat(501)),
+            "Moderation reason cannot exceed 500 characters",
+        );
+    }
+
+    #[test]
+    fn flag_reason_requires_known_category() {
+        assert_eq!(
+            validate_flag_reason(Some("spam"), Some("   ")).unwrap(),
+            (FlagReason::Spam, None)
+        );
+        assert_error_contains(
+            validate_flag_reason(Some("rude"), None),
+            "Choose why you are flagging this",
+        );
+        assert_error_contains(
+            validate_flag_reason(None, None),
+            "Choose why you are flagging this",
+        );
+    }
+
+    #[test]
+    fn flag_reason_other_needs_details() {
+        assert_error_contains(
+            validate_flag_reason(Some("other"), Some(" ")),
+            "Please describe the problem",
+        );
+        assert_eq!(
+            validate_flag_reason(Some("other"), Some(" posted twice ")).unwrap(),
+            (FlagReason::Other, Some("posted twice".to_string()))
+        );
+        assert_error_contains(
+            validate_flag_reason(Some("abusive"), Some(&"d".repeat(501))),
+            "Flag details cannot exceed 500 characters",
+        );
+        // The limit counts characters, not bytes
+        assert!(validate_flag_reason(Some("abusive"), Some(&"é".repeat(500))).is_ok());
+    }
+
+    #[test]
+    fn flag_target_is_an_idea_or_comment() {
+        assert!(validate_flag_target("idea").is_ok());
+        assert!(validate_flag_target("comment").is_ok());
+        assert_error_contains(
+            validate_flag_target("user"),
+            "Only ideas and comments can be flagged",
+        );
+    }
 }
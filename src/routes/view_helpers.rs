@@ -1,6 +1,7 @@
 use leptos::prelude::{Get, Resource, ServerFnError};
 
 use crate::auth::UserSession;
+use crate::models::{Idea, StageColor};
 
 pub fn format_relative_time(dt: &chrono::DateTime<chrono::Utc>) -> String {
     let now = chrono::Utc::now();
@@ -19,16 +20,48 @@ pub fn format_relative_time(dt: &chrono::DateTime<chrono::Utc>) -> String {
     }
 }
 
-pub fn stage_badge_color(stage: &str) -> &'static str {
-    match stage {
-        "Ideate" => "ideate",
-        "Review" => "review",
-        "In Progress" => "progress",
-        "Completed" => "completed",
-        _ => "default",
+/// Text and tooltip for the "edited" marker on ideas and comments.
+pub fn edited_marker(edited_at: &chrono::DateTime<chrono::Utc>) -> (String, String) {
+    (
+        format!("edited {}", format_relative_time(edited_at)),
+        format!("Edited {}", edited_at.format("%Y-%m-%d %H:%M UTC")),
+    )
+}
+
+/// Text shown to an author on content a moderator hid.
+pub fn moderation_notice(reason: Option<&str>) -> String {
+    match reason {
+        Some(reason) => format!("Hidden by moderator: {}", reason),
+        None => "Hidden by moderator".to_string(),
+    }
+}
+
+/// Text shown to an author on content held for review after repeated flags.
+pub const REVIEW_NOTICE: &str = "Hidden while moderators review reports";
+
+/// Why an idea is not public, for its author and moderators; `None` when it
+/// is.
+pub fn idea_visibility_notice(idea: &Idea) -> Option<String> {
+    if let Some(target_id) = idea.merged_into {
+        Some(format!("Merged into idea #{}", target_id))
+    } else if idea.is_off_topic {
+        Some(moderation_notice(idea.moderation_reason.as_deref()))
+    } else if idea.is_pending {
+        Some("Waiting for moderator approval".to_string())
+    } else if !idea.is_public {
+        Some(REVIEW_NOTICE.to_string())
+    } else {
+        None
     }
 }
 
+/// Class suffix for a badge in a stage's color; unknown colors are gray.
+pub fn stage_badge_color(color: &str) -> &'static str {
+    StageColor::from_name(color)
+        .unwrap_or(StageColor::Gray)
+        .as_str()
+}
+
 pub fn is_user_logged_in(
     user_resource: &Resource<Result<Option<UserSession>, ServerFnError>>,
 ) -> bool {
@@ -49,8 +82,11 @@ fn user_session_state_is_logged_in(
 
 #[cfg(test)]
 mod tests {
-    use super::user_session_state_is_logged_in;
+    use super::{
+        idea_visibility_notice, moderation_notice, user_session_state_is_logged_in, REVIEW_NOTICE,
+    };
     use crate::auth::UserSession;
+    use crate::models::Idea;
     use leptos::prelude::ServerFnError;
 
     #[test]
@@ -69,4 +105,61 @@ mod tests {
             }
         )))));
     }
+
+    #[test]
+    fn moderation_notice_includes_reason_when_given() {
+        assert_eq!(
+            moderation_notice(Some("duplicate of #42")),
+            "Hidden by moderator: duplicate of #42"
+        );
+        assert_eq!(moderation_notice(None), "Hidden by moderator");
+    }
+
+    #[test]
+    fn visibility_notice_explains_why_an_idea_is_hidden() {
+        let mut idea = Idea {
+            id: 1,
+            user_id: 1,
+            title: "Bike racks".to_string(),
+            content: "More bike racks".to_string(),
+            tags: String::new(),
+            stage: "Ideate".to_string(),
+            is_public: true,
+            is_off_topic: false,
+            pinned_at: None,
+            created_at: chrono::Utc::now(),
+            vote_count: 0,
+            comments_enabled: true,
+            edited_at: None,
+            moderation_reason: None,
+            is_pending: false,
+            merged_into: None,
+            category_id: 1,
+        };
+        assert_eq!(idea_visibility_notice(&idea), None);
+
+        idea.is_public = false;
+        assert_eq!(
+            idea_visibility_notice(&idea).as_deref(),
+            Some(REVIEW_NOTICE)
+        );
+
+        idea.is_pending = true;
+        assert_eq!(
+            idea_visibility_notice(&idea).as_deref(),
+            Some("Waiting for moderator approval")
+        );
+
+        idea.is_off_topic = true;
+        assert_eq!(
+            idea_visibility_notice(&idea).as_deref(),
+            Some("Hidden by moderator")
+        );
+
+        idea.merged_into = Some(7);
+        assert_eq!(
+            idea_visibility_notice(&idea).as_deref(),
+            Some("Merged into idea #7")
+        );
+    }
 }
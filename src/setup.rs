@@ -19,6 +19,15 @@ pub async fn init_app(configuration_path: Option<&str>) -> Result<(), String> {
         .await
         .map_err(|e| format!("Failed to bootstrap admin user: {e}"))?;
 
+    // Load the word filter terms
+    crate::profanity::reload()
+        .await
+        .map_err(|e| format!("Failed to load word filter: {e}"))?;
+
+    // Drain queued email in the background
+    crate::mailer::spawn_email_worker();
+    crate::notifier::spawn_digest_worker();
+
     // Get leptos configuration
     let conf = get_configuration(configuration_path)
         .map_err(|e| format!("Failed to load Leptos configuration: {e}"))?;
@@ -46,10 +55,15 @@ pub async fn init_app(configuration_path: Option<&str>) -> Result<(), String> {
             "/admin/export/comments.csv",
             axum::routing::get(crate::routes::admin_export_comments_csv),
         )
+        .route(
+            "/admin/export/moderation_log.csv",
+            axum::routing::get(crate::routes::admin_export_moderation_log_csv),
+        )
         .leptos_routes(&leptos_options, routes, {
             let leptos_options = leptos_options.clone();
             move || shell(leptos_options.clone())
         })
+        .route_layer(axum::middleware::from_fn(crate::auth::session_middleware))
         .fallback_service(serve_dir)
         .layer(
             tower_http::trace::TraceLayer::new_for_http()
@@ -64,7 +78,6 @@ pub async fn init_app(configuration_path: Option<&str>) -> Result<(), String> {
                     tower_http::trace::DefaultOnFailure::new().level(tracing::Level::ERROR),
                 ),
         )
-        // .layer(axum::middleware::from_fn(crate::auth::auth_middleware)) // Disabled for anonymous idea board
         .with_state(leptos_options);
 
     let listener = tokio::net::TcpListener::bind(addr)